@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::build::{DeclaredDependency, LineIndex};
+use crate::maven::MAVEN_CENTRAL_BASE;
+
+/// Default name of the rules_jvm_external pin file
+pub const MAVEN_INSTALL_JSON: &str = "maven_install.json";
+
+#[derive(Error, Debug)]
+pub enum BazelError {
+    #[error("Invalid maven_install.json: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Unsupported maven_install.json: {0}")]
+    Unsupported(String),
+}
+
+/// The parts of a rules_jvm_external v2 pin file that are checked
+#[derive(Debug, Deserialize)]
+struct PinFile {
+    version: Option<Value>,
+    artifacts: Option<BTreeMap<String, PinEntry>>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    repositories: BTreeMap<String, Vec<String>>,
+    dependency_tree: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinEntry {
+    version: Option<String>,
+}
+
+/// An entry of the `artifacts` section
+#[derive(Debug, Clone)]
+pub struct PinnedArtifact {
+    /// Key as written in the pin file, e.g. "com.google.guava:guava" or
+    /// "io.netty:netty-transport-native-epoll:jar:linux-x86_64"
+    pub key: String,
+    pub declaration: DeclaredDependency,
+}
+
+/// Artifacts pinned by Bazel's `maven_install.json`
+#[derive(Debug, Clone, Default)]
+pub struct MavenInstall {
+    /// Format version of the pin file
+    pub version: Option<String>,
+    /// Repositories in the order they are listed
+    pub repositories: Vec<String>,
+    pub artifacts: Vec<PinnedArtifact>,
+    /// Artifact key to the keys of its direct dependencies
+    pub dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl MavenInstall {
+    pub fn parse(content: &str) -> Result<Self, BazelError> {
+        let file: PinFile = serde_json::from_str(content)?;
+        let Some(artifacts) = file.artifacts else {
+            let reason = if file.dependency_tree.is_some() {
+                "version 1 pin files are not supported; re-pin with a current rules_jvm_external"
+            } else {
+                "no artifacts section"
+            };
+            return Err(BazelError::Unsupported(reason.to_string()));
+        };
+
+        // serde_json sorts object keys, so restore the order of the file
+        let mut repositories: Vec<&String> = file.repositories.keys().collect();
+        repositories.sort_by_key(|url| content.find(&format!("\"{}\"", url)));
+
+        let artifact_repository = |key: &str| {
+            repositories
+                .iter()
+                .find(|url| file.repositories[url.as_str()].iter().any(|k| k == key))
+                .or(repositories.first())
+                .map(|url| url.trim_end_matches('/').to_string())
+                .filter(|url| url != MAVEN_CENTRAL_BASE)
+        };
+
+        let lines = LineIndex::new(content);
+        let artifacts_start = content.find("\"artifacts\"").unwrap_or(0);
+        let line_of = |key: &str| {
+            content[artifacts_start..]
+                .find(&format!("\"{}\"", key))
+                .map(|offset| lines.line_at(artifacts_start + offset))
+                .unwrap_or(0)
+        };
+
+        let artifacts = artifacts
+            .into_iter()
+            .map(|(key, entry)| {
+                let mut parts = key.split(':');
+                let group_id = parts.next().unwrap_or_default().to_string();
+                let artifact_id = parts.next().unwrap_or_default().to_string();
+                let unresolved_reason = if artifact_id.is_empty() {
+                    Some(format!("Not a Maven artifact key: {}", key))
+                } else if entry.version.is_none() {
+                    Some("No version pinned".to_string())
+                } else {
+                    None
+                };
+                let declaration = DeclaredDependency {
+                    group_id,
+                    artifact_id,
+                    section: "artifacts".to_string(),
+                    scope: None,
+                    declared_version: entry.version.clone(),
+                    version_property: None,
+                    resolved_version: entry.version,
+                    repository: artifact_repository(&key),
+                    line: line_of(&key),
+                    unresolved_reason,
+                };
+                PinnedArtifact { key, declaration }
+            })
+            .collect();
+
+        Ok(MavenInstall {
+            version: file.version.map(|v| match v {
+                Value::String(s) => s,
+                other => other.to_string(),
+            }),
+            repositories: repositories
+                .into_iter()
+                .map(|url| url.trim_end_matches('/').to_string())
+                .collect(),
+            artifacts,
+            dependencies: file.dependencies,
+        })
+    }
+
+    pub fn declarations(&self) -> Vec<DeclaredDependency> {
+        self.artifacts
+            .iter()
+            .map(|a| a.declaration.clone())
+            .collect()
+    }
+
+    /// Keys of the artifacts that depend directly on `key`
+    pub fn required_by(&self, key: &str) -> Vec<&str> {
+        self.dependencies
+            .iter()
+            .filter(|(_, deps)| deps.iter().any(|d| d == key))
+            .map(|(parent, _)| parent.as_str())
+            .collect()
+    }
+
+    /// Artifacts no other pinned artifact depends on; usually the ones
+    /// requested in the `maven_install` rule
+    pub fn top_level(&self) -> Vec<&PinnedArtifact> {
+        self.artifacts
+            .iter()
+            .filter(|a| {
+                self.dependencies
+                    .values()
+                    .all(|deps| !deps.contains(&a.key))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PINS: &str = r#"{
+  "__AUTOGENERATED_FILE_DO_NOT_MODIFY_THIS_FILE_MANUALLY": "THERE_IS_NO_DATA_ONLY_ZUUL",
+  "__INPUT_ARTIFACTS_HASH": 1234,
+  "__RESOLVED_ARTIFACTS_HASH": 5678,
+  "artifacts": {
+    "com.acme:internal-lib": {
+      "shasums": { "jar": "aa" },
+      "version": "1.4.0"
+    },
+    "com.google.guava:failureaccess": {
+      "shasums": { "jar": "bb" },
+      "version": "1.0.1"
+    },
+    "com.google.guava:guava": {
+      "shasums": { "jar": "cc", "sources": null },
+      "version": "32.1.2-jre"
+    },
+    "io.netty:netty-transport-native-epoll:jar:linux-x86_64": {
+      "shasums": { "jar": "dd" },
+      "version": "4.1.100.Final"
+    }
+  },
+  "dependencies": {
+    "com.acme:internal-lib": ["com.google.guava:guava"],
+    "com.google.guava:guava": ["com.google.guava:failureaccess"]
+  },
+  "repositories": {
+    "https://repo1.maven.org/maven2/": [
+      "com.google.guava:failureaccess",
+      "com.google.guava:guava",
+      "io.netty:netty-transport-native-epoll:jar:linux-x86_64"
+    ],
+    "https://nexus.acme.com/repository/releases/": [
+      "com.acme:internal-lib"
+    ]
+  },
+  "version": "2"
+}"#;
+
+    #[test]
+    fn test_parse_pins() {
+        let pins = MavenInstall::parse(PINS).unwrap();
+        assert_eq!(pins.version.as_deref(), Some("2"));
+        assert_eq!(
+            pins.repositories,
+            vec![
+                "https://repo1.maven.org/maven2",
+                "https://nexus.acme.com/repository/releases"
+            ]
+        );
+
+        let coordinates: Vec<String> = pins
+            .declarations()
+            .iter()
+            .filter_map(DeclaredDependency::checkable_coordinate)
+            .collect();
+        assert_eq!(
+            coordinates,
+            vec![
+                "com.acme:internal-lib:1.4.0",
+                "com.google.guava:failureaccess:1.0.1",
+                "com.google.guava:guava:32.1.2-jre",
+                "io.netty:netty-transport-native-epoll:4.1.100.Final"
+            ]
+        );
+        assert_eq!(pins.artifacts[0].declaration.line, 6);
+        assert_eq!(
+            pins.artifacts[0].declaration.repository(),
+            "https://nexus.acme.com/repository/releases"
+        );
+        assert_eq!(pins.artifacts[2].declaration.repository, None);
+    }
+
+    #[test]
+    fn test_dependency_graph() {
+        let pins = MavenInstall::parse(PINS).unwrap();
+        assert_eq!(
+            pins.required_by("com.google.guava:guava"),
+            vec!["com.acme:internal-lib"]
+        );
+        let top_level: Vec<&str> = pins.top_level().iter().map(|a| a.key.as_str()).collect();
+        assert_eq!(
+            top_level,
+            vec![
+                "com.acme:internal-lib",
+                "io.netty:netty-transport-native-epoll:jar:linux-x86_64"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_version_one_is_rejected() {
+        let err = MavenInstall::parse(r#"{"dependency_tree": {"dependencies": []}}"#).unwrap_err();
+        assert!(matches!(err, BazelError::Unsupported(_)));
+    }
+}
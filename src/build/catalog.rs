@@ -0,0 +1,422 @@
+use std::collections::{BTreeMap, HashMap};
+
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::build::gradle::plugin_marker;
+use crate::build::DeclaredDependency;
+use crate::maven::GRADLE_PLUGIN_PORTAL;
+use crate::models::VersionRange;
+
+#[derive(Error, Debug)]
+pub enum CatalogError {
+    #[error("Invalid version catalog: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// An entry of the `[versions]` table
+#[derive(Debug, Clone)]
+pub struct CatalogVersion {
+    pub name: String,
+    /// The required version; `None` for rich versions without one
+    pub version: Option<String>,
+    pub line: usize,
+}
+
+/// A `[libraries]` or `[plugins]` entry
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub alias: String,
+    pub declaration: DeclaredDependency,
+}
+
+/// A `[bundles]` entry
+#[derive(Debug, Clone)]
+pub struct CatalogBundle {
+    pub name: String,
+    pub libraries: Vec<String>,
+    pub line: usize,
+}
+
+/// Libraries and plugins sharing a `version.ref`
+#[derive(Debug, Clone, Default)]
+pub struct VersionRefMembers {
+    pub libraries: Vec<String>,
+    pub plugins: Vec<String>,
+}
+
+/// A parsed Gradle version catalog (`gradle/libs.versions.toml`)
+#[derive(Debug, Clone, Default)]
+pub struct VersionCatalog {
+    pub versions: Vec<CatalogVersion>,
+    pub libraries: Vec<CatalogEntry>,
+    pub plugins: Vec<CatalogEntry>,
+    pub bundles: Vec<CatalogBundle>,
+    /// Problems found while reading the catalog, e.g. unknown bundle members
+    pub warnings: Vec<String>,
+}
+
+impl VersionCatalog {
+    pub fn parse(content: &str) -> Result<Self, CatalogError> {
+        let table: Table = toml::from_str(content)?;
+        let lines = entry_lines(content);
+        let line = |section: &str, key: &str| {
+            lines
+                .get(&(section.to_string(), key.to_string()))
+                .copied()
+                .unwrap_or(0)
+        };
+        let section = |name: &str| table.get(name).and_then(Value::as_table);
+
+        let mut catalog = VersionCatalog::default();
+
+        let mut versions = HashMap::new();
+        for (name, value) in section("versions").into_iter().flatten() {
+            let version = required_version(value);
+            if let Some(v) = &version {
+                versions.insert(name.clone(), v.clone());
+            }
+            catalog.versions.push(CatalogVersion {
+                name: name.clone(),
+                version,
+                line: line("versions", name),
+            });
+        }
+
+        for (alias, value) in section("libraries").into_iter().flatten() {
+            let declaration = library(value, &versions, line("libraries", alias));
+            catalog.libraries.push(CatalogEntry {
+                alias: alias.clone(),
+                declaration,
+            });
+        }
+
+        for (alias, value) in section("plugins").into_iter().flatten() {
+            let declaration = plugin(value, &versions, line("plugins", alias));
+            catalog.plugins.push(CatalogEntry {
+                alias: alias.clone(),
+                declaration,
+            });
+        }
+
+        for (name, value) in section("bundles").into_iter().flatten() {
+            let libraries: Vec<String> = value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect();
+            for member in &libraries {
+                if !catalog.libraries.iter().any(|l| &l.alias == member) {
+                    catalog.warnings.push(format!(
+                        "Bundle '{}' references unknown library '{}'",
+                        name, member
+                    ));
+                }
+            }
+            catalog.bundles.push(CatalogBundle {
+                name: name.clone(),
+                libraries,
+                line: line("bundles", name),
+            });
+        }
+
+        catalog.libraries.sort_by_key(|e| e.declaration.line);
+        catalog.plugins.sort_by_key(|e| e.declaration.line);
+        Ok(catalog)
+    }
+
+    /// Every library and plugin declaration
+    pub fn declarations(&self) -> Vec<DeclaredDependency> {
+        self.libraries
+            .iter()
+            .chain(&self.plugins)
+            .map(|e| e.declaration.clone())
+            .collect()
+    }
+
+    /// Libraries and plugins grouped by the `version.ref` they use
+    pub fn version_refs(&self) -> BTreeMap<String, VersionRefMembers> {
+        let mut refs: BTreeMap<String, VersionRefMembers> = BTreeMap::new();
+        for entry in &self.libraries {
+            if let Some(name) = &entry.declaration.version_property {
+                refs.entry(name.clone())
+                    .or_default()
+                    .libraries
+                    .push(entry.alias.clone());
+            }
+        }
+        for entry in &self.plugins {
+            if let Some(name) = &entry.declaration.version_property {
+                refs.entry(name.clone())
+                    .or_default()
+                    .plugins
+                    .push(entry.alias.clone());
+            }
+        }
+        refs
+    }
+
+    /// Find a `[versions]` entry by name
+    pub fn version(&self, name: &str) -> Option<&CatalogVersion> {
+        self.versions.iter().find(|v| v.name == name)
+    }
+}
+
+/// The version a rich version declaration requires: `strictly`, then
+/// `require`, then `prefer`
+fn required_version(value: &Value) -> Option<String> {
+    match value {
+        Value::String(v) => Some(v.clone()),
+        Value::Table(t) => ["strictly", "require", "prefer"]
+            .iter()
+            .find_map(|k| t.get(*k).and_then(Value::as_str))
+            .map(String::from),
+        _ => None,
+    }
+}
+
+/// How an entry declares its version
+enum EntryVersion {
+    None,
+    Literal(String),
+    Ref(String),
+    Rich(Option<String>),
+}
+
+fn entry_version(table: &Table) -> EntryVersion {
+    match table.get("version") {
+        None => EntryVersion::None,
+        Some(Value::String(v)) => EntryVersion::Literal(v.clone()),
+        Some(Value::Table(t)) => match t.get("ref").and_then(Value::as_str) {
+            Some(name) => EntryVersion::Ref(name.to_string()),
+            None => EntryVersion::Rich(required_version(&Value::Table(t.clone()))),
+        },
+        Some(_) => EntryVersion::Rich(None),
+    }
+}
+
+fn library(value: &Value, versions: &HashMap<String, String>, line: usize) -> DeclaredDependency {
+    let (module, version) = match value {
+        Value::String(notation) => {
+            let mut parts = notation.splitn(3, ':');
+            let module = match (parts.next(), parts.next()) {
+                (Some(g), Some(a)) => Some((g.to_string(), a.to_string())),
+                _ => None,
+            };
+            let version = parts
+                .next()
+                .map_or(EntryVersion::None, |v| EntryVersion::Literal(v.to_string()));
+            (module, version)
+        }
+        Value::Table(t) => {
+            let module = match t.get("module").and_then(Value::as_str) {
+                Some(module) => module
+                    .split_once(':')
+                    .map(|(g, a)| (g.to_string(), a.to_string())),
+                None => match (
+                    t.get("group").and_then(Value::as_str),
+                    t.get("name").and_then(Value::as_str),
+                ) {
+                    (Some(g), Some(a)) => Some((g.to_string(), a.to_string())),
+                    _ => None,
+                },
+            };
+            (module, entry_version(t))
+        }
+        _ => (None, EntryVersion::None),
+    };
+
+    let (group_id, artifact_id) = module.unwrap_or_default();
+    let mut declaration = declaration("libraries", group_id, artifact_id, version, versions, line);
+    if declaration.artifact_id.is_empty() {
+        declaration.unresolved_reason = Some("Missing module coordinates".to_string());
+    }
+    declaration
+}
+
+fn plugin(value: &Value, versions: &HashMap<String, String>, line: usize) -> DeclaredDependency {
+    let (id, version) = match value {
+        Value::String(notation) => match notation.split_once(':') {
+            Some((id, v)) => (id.to_string(), EntryVersion::Literal(v.to_string())),
+            None => (notation.clone(), EntryVersion::None),
+        },
+        Value::Table(t) => (
+            t.get("id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            entry_version(t),
+        ),
+        _ => (String::new(), EntryVersion::None),
+    };
+
+    // Plugins resolve through their marker artifact on the Plugin Portal
+    let marker = plugin_marker(&id);
+    let mut declaration = declaration("plugins", id, marker, version, versions, line);
+    declaration.repository = Some(GRADLE_PLUGIN_PORTAL.to_string());
+    if declaration.group_id.is_empty() {
+        declaration.unresolved_reason = Some("Missing plugin id".to_string());
+    }
+    declaration
+}
+
+fn declaration(
+    section: &str,
+    group_id: String,
+    artifact_id: String,
+    version: EntryVersion,
+    versions: &HashMap<String, String>,
+    line: usize,
+) -> DeclaredDependency {
+    let (declared_version, version_property, resolved) = match version {
+        EntryVersion::None => (None, None, Err("No version declared".to_string())),
+        EntryVersion::Literal(v) => (Some(v.clone()), None, Ok(v)),
+        EntryVersion::Rich(v) => (
+            v.clone(),
+            None,
+            v.ok_or_else(|| "Rich version without a required version".to_string()),
+        ),
+        EntryVersion::Ref(name) => {
+            let resolved = versions
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| format!("Unknown version.ref '{}'", name));
+            (None, Some(name), resolved)
+        }
+    };
+
+    let (resolved_version, unresolved_reason) = match resolved {
+        Ok(v) if VersionRange::is_dynamic(&v) => (
+            Some(v),
+            Some("Dynamic versions are not checked; use resolve_version_range".to_string()),
+        ),
+        Ok(v) => (Some(v), None),
+        Err(reason) => (None, Some(reason)),
+    };
+
+    DeclaredDependency {
+        group_id,
+        artifact_id,
+        section: section.to_string(),
+        scope: None,
+        declared_version,
+        version_property,
+        resolved_version,
+        repository: None,
+        line,
+        unresolved_reason,
+    }
+}
+
+/// Map (table, key) to the 1-based line the key is defined on
+fn entry_lines(content: &str) -> HashMap<(String, String), usize> {
+    let mut lines = HashMap::new();
+    let mut section = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            section = header.trim_end_matches(']').trim().to_string();
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, _)) = line.split_once('=') {
+            let key = key.trim().trim_matches('"').to_string();
+            lines.entry((section.clone(), key)).or_insert(index + 1);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+[versions]
+jackson = "2.15.0"
+spring-boot = { strictly = "3.1.0" }
+
+[libraries]
+jackson-databind = { module = "com.fasterxml.jackson.core:jackson-databind", version.ref = "jackson" }
+jackson-core = { group = "com.fasterxml.jackson.core", name = "jackson-core", version.ref = "jackson" }
+guava = "com.google.guava:guava:32.0.0-jre"
+spring-web = { module = "org.springframework:spring-web" }
+broken = { module = "org.example:broken", version.ref = "missing" }
+
+[bundles]
+jackson = ["jackson-databind", "jackson-core", "jackson-annotations"]
+
+[plugins]
+spring-boot = { id = "org.springframework.boot", version.ref = "spring-boot" }
+kotlin-jvm = "org.jetbrains.kotlin.jvm:1.9.0"
+"#;
+
+    #[test]
+    fn test_parse_catalog() {
+        let catalog = VersionCatalog::parse(CATALOG).unwrap();
+        assert_eq!(catalog.version("jackson").unwrap().line, 3);
+        assert_eq!(
+            catalog.version("spring-boot").unwrap().version.as_deref(),
+            Some("3.1.0")
+        );
+
+        let databind = &catalog.libraries[0].declaration;
+        assert_eq!(
+            databind.checkable_coordinate().as_deref(),
+            Some("com.fasterxml.jackson.core:jackson-databind:2.15.0")
+        );
+        assert_eq!(databind.version_property.as_deref(), Some("jackson"));
+        assert_eq!(databind.line, 7);
+
+        let aliases: Vec<&str> = catalog.libraries.iter().map(|l| l.alias.as_str()).collect();
+        assert_eq!(
+            aliases,
+            vec![
+                "jackson-databind",
+                "jackson-core",
+                "guava",
+                "spring-web",
+                "broken"
+            ]
+        );
+        assert!(catalog.libraries[3].declaration.unresolved_reason.is_some());
+        assert_eq!(
+            catalog.libraries[4]
+                .declaration
+                .unresolved_reason
+                .as_deref(),
+            Some("Unknown version.ref 'missing'")
+        );
+        assert_eq!(catalog.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_plugins_use_marker_artifacts() {
+        let catalog = VersionCatalog::parse(CATALOG).unwrap();
+        let boot = &catalog.plugins[0].declaration;
+        assert_eq!(
+            boot.checkable_coordinate().as_deref(),
+            Some("org.springframework.boot:org.springframework.boot.gradle.plugin:3.1.0")
+        );
+        assert_eq!(boot.repository(), GRADLE_PLUGIN_PORTAL);
+        assert_eq!(
+            catalog.plugins[1].declaration.resolved_version.as_deref(),
+            Some("1.9.0")
+        );
+    }
+
+    #[test]
+    fn test_version_refs() {
+        let catalog = VersionCatalog::parse(CATALOG).unwrap();
+        let refs = catalog.version_refs();
+        assert_eq!(
+            refs["jackson"].libraries,
+            vec!["jackson-databind", "jackson-core"]
+        );
+        assert_eq!(refs["spring-boot"].plugins, vec!["spring-boot"]);
+    }
+}
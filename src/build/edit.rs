@@ -0,0 +1,389 @@
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::build::is_within;
+
+/// Number of unchanged lines shown around each change
+const DIFF_CONTEXT: usize = 3;
+
+/// How far below a declaration its `<version>` element is searched for
+const MAX_DECLARATION_LINES: usize = 20;
+
+/// Settings scripts at the root of a Gradle build
+const GRADLE_SETTINGS: &[&str] = &["settings.gradle", "settings.gradle.kts"];
+
+/// Closing tags that end the search for a declaration's version
+const DECLARATION_END: &[&str] = &["</dependency>", "</plugin>", "</parent>"];
+
+/// The kind of build file being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildFileKind {
+    Pom,
+    Gradle,
+    VersionCatalog,
+}
+
+impl BuildFileKind {
+    /// Detect the kind from the file name, falling back to the content
+    pub fn detect(path: Option<&str>, content: &str) -> Self {
+        match path {
+            Some(p) if p.ends_with(".toml") => BuildFileKind::VersionCatalog,
+            Some(p) if p.ends_with(".gradle") || p.ends_with(".gradle.kts") => {
+                BuildFileKind::Gradle
+            }
+            Some(p) if p.ends_with(".xml") => BuildFileKind::Pom,
+            _ => {
+                let content = content.trim_start();
+                if content.starts_with('<') {
+                    BuildFileKind::Pom
+                } else if content
+                    .lines()
+                    .any(|l| matches!(l.trim(), "[versions]" | "[libraries]" | "[plugins]"))
+                {
+                    BuildFileKind::VersionCatalog
+                } else {
+                    BuildFileKind::Gradle
+                }
+            }
+        }
+    }
+
+    /// Path used in diff headers, relative to the project directory so that
+    /// the diff applies there. The project directory is `base_dir` when
+    /// given and otherwise found above the file without leaving `within`.
+    /// Inline content gets the conventional file name.
+    pub async fn diff_path(
+        self,
+        path: Option<&Path>,
+        base_dir: Option<&Path>,
+        within: Option<&Path>,
+    ) -> String {
+        let Some(path) = path else {
+            return match self {
+                BuildFileKind::Pom => "pom.xml",
+                BuildFileKind::Gradle => "build.gradle",
+                BuildFileKind::VersionCatalog => "gradle/libs.versions.toml",
+            }
+            .to_string();
+        };
+        let file = canonical(path).await;
+        let base = match base_dir {
+            Some(dir) => Some(canonical(dir).await),
+            None => self.project_root(&file, within).await,
+        };
+        match base
+            .as_deref()
+            .and_then(|base| file.strip_prefix(base).ok())
+        {
+            Some(relative) => relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            None => file
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+
+    /// Directory a build file belongs to: the topmost directory of a chain
+    /// of pom.xml files for Maven, the nearest directory with a settings
+    /// script for Gradle, and otherwise the file's directory, or the one
+    /// above `gradle/` for a version catalog
+    async fn project_root(self, file: &Path, within: Option<&Path>) -> Option<PathBuf> {
+        let dir = file.parent()?;
+        if self == BuildFileKind::Pom {
+            // Aggregator POMs sit in the directories above their modules
+            let mut root = dir;
+            while let Some(parent) = root.parent() {
+                let pom = parent.join("pom.xml");
+                if !is_file(&pom).await || !is_within(&pom, within).await {
+                    break;
+                }
+                root = parent;
+            }
+            return Some(root.to_path_buf());
+        }
+
+        for ancestor in dir.ancestors() {
+            if !is_within(ancestor, within).await {
+                break;
+            }
+            for name in GRADLE_SETTINGS {
+                if is_file(&ancestor.join(name)).await {
+                    return Some(ancestor.to_path_buf());
+                }
+            }
+        }
+        let in_gradle_dir = dir.file_name().is_some_and(|name| name == "gradle");
+        match self {
+            BuildFileKind::VersionCatalog if in_gradle_dir => dir.parent().map(Path::to_path_buf),
+            _ => Some(dir.to_path_buf()),
+        }
+    }
+}
+
+async fn canonical(path: &Path) -> PathBuf {
+    tokio::fs::canonicalize(path)
+        .await
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+async fn is_file(path: &Path) -> bool {
+    tokio::fs::metadata(path).await.is_ok_and(|m| m.is_file())
+}
+
+/// A single in-place version replacement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionEdit {
+    /// 1-based line that is changed
+    pub line: usize,
+    pub from: String,
+    pub to: String,
+    /// Shared version property or `version.ref` updated by this edit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// groupId:artifactId of every declaration affected by this edit
+    pub dependencies: Vec<String>,
+}
+
+/// Replace a version on one line, only where it stands on its own as a
+/// quoted string, element text or the last part of a coordinate
+pub fn replace_version(line: &str, from: &str, to: &str) -> Option<String> {
+    if from.is_empty() {
+        return None;
+    }
+    let mut search = 0;
+    while let Some(found) = line[search..].find(from) {
+        let start = search + found;
+        let end = start + from.len();
+        let before = line[..start].chars().next_back();
+        let after = line[end..].chars().next();
+        let bounded = matches!(before, Some('"' | '\'' | '>' | ':'))
+            && matches!(after, Some('"' | '\'' | '<' | '@' | ':'));
+        if bounded {
+            return Some(format!("{}{}{}", &line[..start], to, &line[end..]));
+        }
+        search = end;
+    }
+    None
+}
+
+/// Find the line holding a declaration's version, starting at the
+/// declaration itself and stopping at its closing tag
+pub fn locate_version(content: &str, line: usize, version: &str) -> Option<usize> {
+    for (index, text) in content
+        .lines()
+        .enumerate()
+        .skip(line.saturating_sub(1))
+        .take(MAX_DECLARATION_LINES)
+    {
+        if replace_version(text, version, version).is_some() {
+            return Some(index + 1);
+        }
+        if DECLARATION_END.iter().any(|end| text.contains(end)) {
+            break;
+        }
+    }
+    None
+}
+
+/// Apply edits, keeping every other byte (including line endings) unchanged
+pub fn apply_edits(content: &str, edits: &[VersionEdit]) -> String {
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let edit = edits.iter().find(|e| e.line == index + 1);
+            match edit.and_then(|e| replace_version(line, &e.from, &e.to)) {
+                Some(updated) => updated,
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Unified diff between two versions of a file whose lines were changed in
+/// place (no lines added or removed)
+pub fn unified_diff(path: &str, original: &str, updated: &str) -> String {
+    let old: Vec<&str> = original.split_inclusive('\n').collect();
+    let new: Vec<&str> = updated.split_inclusive('\n').collect();
+    let changed: Vec<usize> = (0..old.len().min(new.len()))
+        .filter(|&i| old[i] != new[i])
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Merge changes whose context overlaps into one hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(old.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+    for (start, end) in hunks {
+        let len = end - start;
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start + 1,
+            len,
+            start + 1,
+            len
+        ));
+        for i in start..end {
+            if old[i] == new[i] {
+                push_line(&mut diff, ' ', old[i]);
+            } else {
+                push_line(&mut diff, '-', old[i]);
+                push_line(&mut diff, '+', new[i]);
+            }
+        }
+    }
+    diff
+}
+
+/// Push a diff line, keeping its original ending so CRLF files still apply
+fn push_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_kind() {
+        assert_eq!(
+            BuildFileKind::detect(Some("gradle/libs.versions.toml"), ""),
+            BuildFileKind::VersionCatalog
+        );
+        assert_eq!(
+            BuildFileKind::detect(Some("build.gradle.kts"), ""),
+            BuildFileKind::Gradle
+        );
+        assert_eq!(
+            BuildFileKind::detect(None, "<?xml version=\"1.0\"?><project/>"),
+            BuildFileKind::Pom
+        );
+        assert_eq!(
+            BuildFileKind::detect(None, "[versions]\nguava = \"33.0.0-jre\"\n"),
+            BuildFileKind::VersionCatalog
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_path_relative_to_project() {
+        let root = std::env::temp_dir().join(format!("maven-mcp-diff-{}", std::process::id()));
+        let write = |file: &str| {
+            let file = root.join(file);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, "").unwrap();
+        };
+        for file in [
+            "maven/pom.xml",
+            "maven/core/pom.xml",
+            "gradle-build/settings.gradle.kts",
+            "gradle-build/app/build.gradle.kts",
+            "gradle-build/gradle/libs.versions.toml",
+        ] {
+            write(file);
+        }
+
+        let diff_path = |kind: BuildFileKind, file: &str, base: Option<&str>| {
+            let file = root.join(file);
+            let base = base.map(|b| root.join(b));
+            async move { kind.diff_path(Some(&file), base.as_deref(), None).await }
+        };
+        let module = diff_path(BuildFileKind::Pom, "maven/core/pom.xml", None).await;
+        let module_alone =
+            diff_path(BuildFileKind::Pom, "maven/core/pom.xml", Some("maven/core")).await;
+        let subproject = diff_path(
+            BuildFileKind::Gradle,
+            "gradle-build/app/build.gradle.kts",
+            None,
+        )
+        .await;
+        let catalog = diff_path(
+            BuildFileKind::VersionCatalog,
+            "gradle-build/gradle/libs.versions.toml",
+            None,
+        )
+        .await;
+        let module_within = BuildFileKind::Pom
+            .diff_path(
+                Some(&root.join("maven/core/pom.xml")),
+                None,
+                Some(&root.join("maven/core").canonicalize().unwrap()),
+            )
+            .await;
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(module, "core/pom.xml");
+        assert_eq!(module_alone, "pom.xml");
+        assert_eq!(subproject, "app/build.gradle.kts");
+        assert_eq!(catalog, "gradle/libs.versions.toml");
+        assert_eq!(module_within, "pom.xml");
+        assert_eq!(
+            BuildFileKind::Gradle.diff_path(None, None, None).await,
+            "build.gradle"
+        );
+    }
+
+    #[test]
+    fn test_replace_version_requires_boundaries() {
+        assert_eq!(
+            replace_version("    <version>2.15.0</version>", "2.15.0", "2.16.1").as_deref(),
+            Some("    <version>2.16.1</version>")
+        );
+        assert_eq!(
+            replace_version(r#"implementation "g:a:1.0""#, "1.0", "1.1").as_deref(),
+            Some(r#"implementation "g:a:1.1""#)
+        );
+        // "1.0" inside "11.0" or "1.0.1" is not a match
+        assert_eq!(replace_version(r#"v = "11.0""#, "1.0", "2.0"), None);
+        assert_eq!(replace_version(r#"v = "1.0.1""#, "1.0", "2.0"), None);
+    }
+
+    #[test]
+    fn test_locate_version_within_declaration() {
+        let pom = "<dependency>\n  <groupId>g</groupId>\n  <version>1.0</version>\n</dependency>\n<version>1.0</version>\n";
+        assert_eq!(locate_version(pom, 1, "1.0"), Some(3));
+        let pom = "<dependency>\n  <groupId>g</groupId>\n</dependency>\n<version>1.0</version>\n";
+        assert_eq!(locate_version(pom, 1, "1.0"), None);
+    }
+
+    #[test]
+    fn test_apply_and_diff() {
+        let original = "[versions]\r\njackson = \"2.15.0\"\r\nguava = \"32.0.0-jre\"\r\n";
+        let edits = vec![VersionEdit {
+            line: 2,
+            from: "2.15.0".to_string(),
+            to: "2.16.1".to_string(),
+            property: Some("jackson".to_string()),
+            dependencies: vec![],
+        }];
+        let updated = apply_edits(original, &edits);
+        assert_eq!(
+            updated,
+            "[versions]\r\njackson = \"2.16.1\"\r\nguava = \"32.0.0-jre\"\r\n"
+        );
+        assert_eq!(
+            unified_diff("libs.versions.toml", original, &updated),
+            "--- a/libs.versions.toml\n+++ b/libs.versions.toml\n@@ -1,3 +1,3 @@\n [versions]\r\n-jackson = \"2.15.0\"\r\n+jackson = \"2.16.1\"\r\n guava = \"32.0.0-jre\"\r\n"
+        );
+    }
+}
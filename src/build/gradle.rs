@@ -0,0 +1,568 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::build::{DeclaredDependency, LineIndex};
+use crate::maven::GRADLE_PLUGIN_PORTAL;
+use crate::models::VersionRange;
+
+const MAX_INTERPOLATION_DEPTH: usize = 10;
+
+/// `implementation "g:a:v"`, `api("g:a:v")`, `implementation(platform("g:a:v"))`
+static STRING_NOTATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?P<conf>[A-Za-z_]\w*)[ \t]*\(?[ \t]*(?:(?P<platform>platform|enforcedPlatform)[ \t]*\(?[ \t]*)?["'](?P<notation>[^"'\n]+)["']"#,
+    )
+    .expect("valid regex")
+});
+
+/// `implementation group: 'g', name: 'a', version: 'v'` and the Kotlin
+/// `implementation(group = "g", name = "a", version = "v")`
+static MAP_NOTATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?P<conf>[A-Za-z_]\w*)[ \t]*\(?[ \t]*group[ \t]*[:=][ \t]*["'](?P<group>[^"'\n]+)["'][ \t]*,[ \t]*name[ \t]*[:=][ \t]*["'](?P<name>[^"'\n]+)["'](?:[ \t]*,[ \t]*version[ \t]*[:=][ \t]*(?:["'](?P<version>[^"'\n]+)["']|(?P<version_var>[A-Za-z_][\w.]*)))?"#,
+    )
+    .expect("valid regex")
+});
+
+/// `implementation(libs.jackson.databind)`, resolved through the version catalog
+static CATALOG_ACCESSOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?P<conf>[A-Za-z_]\w*)[ \t]*\(?[ \t]*(?:(?:platform|enforcedPlatform)[ \t]*\(?[ \t]*)?(?P<accessor>libs\.[\w.]+)"#,
+    )
+    .expect("valid regex")
+});
+
+/// `id("org.jetbrains.kotlin.jvm") version "1.9.0"`, `id 'x' version 'y'` and
+/// the `kotlin("jvm") version "1.9.0"` shorthand inside `plugins { }`
+static PLUGIN_REQUEST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?:id[ \t]*\(?[ \t]*["'](?P<id>[\w.\-]+)["'][ \t]*\)?|kotlin[ \t]*\([ \t]*["'](?P<kotlin>[\w.\-]+)["'][ \t]*\))[ \t]+version[ \t]*\(?[ \t]*(?:["'](?P<version>[^"'\n]+)["']|(?P<version_var>[A-Za-z_][\w.]*))"#,
+    )
+    .expect("valid regex")
+});
+
+/// `def v = '1.0'`, `val v = "1.0"`, `ext.v = '1.0'` and assignments inside `ext { }`
+static ASSIGNMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?:(?:def|val|var)[ \t]+|(?:(?:project|rootProject)\.)?ext\.)?(?P<name>[A-Za-z_]\w*)[ \t]*=[ \t]*["'](?P<value>[^"'\n]*)["']"#,
+    )
+    .expect("valid regex")
+});
+
+/// `val v by extra("1.0")`, `extra["v"] = "1.0"`, `ext.set("v", "1.0")`
+static EXTRA_PROPERTY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)(?:val[ \t]+(?P<delegated>\w+)[ \t]+by[ \t]+extra\([ \t]*|(?:extra|ext)\[[ \t]*["'](?P<indexed>[\w.]+)["'][ \t]*\][ \t]*=[ \t]*|(?:extra|ext)\.set\([ \t]*["'](?P<set>[\w.]+)["'][ \t]*,[ \t]*)["'](?P<value>[^"'\n]*)["']"#,
+    )
+    .expect("valid regex")
+});
+
+/// Accessors that may prefix a variable reference, e.g. `${project.ext.v}`
+static REFERENCE_WRAPPER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?:(?:project|rootProject|ext|extra)\.)*(?:(?:extra|ext|property|findProperty)[ \t]*[\[(][ \t]*["'](?P<quoted>[\w.]+)["'][ \t]*[\])]|(?P<plain>[\w.]+))$"#,
+    )
+    .expect("valid regex")
+});
+
+/// Keywords that look like a configuration followed by a string but are not
+const NON_CONFIGURATIONS: &[&str] = &[
+    "id",
+    "alias",
+    "url",
+    "uri",
+    "apply",
+    "from",
+    "include",
+    "includeBuild",
+    "plugin",
+    "println",
+    "mavenBomDependency",
+    "group",
+    "version",
+    "description",
+    "mainClass",
+    "name",
+];
+
+/// A version variable defined in a build script or gradle.properties
+#[derive(Debug, Clone)]
+pub struct GradleVariable {
+    pub name: String,
+    pub value: String,
+    /// 1-based line, or 0 for variables from gradle.properties
+    pub line: usize,
+}
+
+/// Dependencies statically extracted from a `build.gradle` or `build.gradle.kts`
+#[derive(Debug, Clone, Default)]
+pub struct GradleBuild {
+    pub variables: Vec<GradleVariable>,
+    pub dependencies: Vec<DeclaredDependency>,
+}
+
+impl GradleBuild {
+    /// Parse a build script. `properties` supplies values from gradle.properties.
+    pub fn parse(content: &str, properties: &HashMap<String, String>) -> Self {
+        let lines = LineIndex::new(content);
+        let mut variables: Vec<GradleVariable> = properties
+            .iter()
+            .map(|(name, value)| GradleVariable {
+                name: name.clone(),
+                value: value.clone(),
+                line: 0,
+            })
+            .collect();
+
+        for caps in ASSIGNMENT.captures_iter(content) {
+            variables.push(variable(&caps, "name", &lines));
+        }
+        for caps in EXTRA_PROPERTY.captures_iter(content) {
+            for name in ["delegated", "indexed", "set"] {
+                if caps.name(name).is_some() {
+                    variables.push(variable(&caps, name, &lines));
+                }
+            }
+        }
+
+        let mut build = GradleBuild {
+            variables,
+            dependencies: Vec::new(),
+        };
+        // Build script values override gradle.properties; later definitions win
+        let values: HashMap<String, String> = build
+            .variables
+            .iter()
+            .map(|v| (v.name.clone(), v.value.clone()))
+            .collect();
+
+        let mut found = Vec::new();
+        for caps in STRING_NOTATION.captures_iter(content) {
+            let conf = capture(&caps, "conf");
+            if NON_CONFIGURATIONS.contains(&conf) {
+                continue;
+            }
+            let mut parts = capture(&caps, "notation").splitn(4, ':');
+            let (Some(group), Some(artifact)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            // Interpolated coordinates are reported; other strings are not dependencies
+            let unresolved_reason = if is_identifier(group) && is_identifier(artifact) {
+                None
+            } else if group.contains('$') || artifact.contains('$') {
+                Some(format!(
+                    "Group or artifact is interpolated ('{}:{}'); only versions are resolved",
+                    group, artifact
+                ))
+            } else {
+                continue;
+            };
+            let version = parts.next().map(|v| v.split('@').next().unwrap_or(v));
+            let offset = caps.name("conf").map_or(0, |m| m.start());
+            found.push((
+                offset,
+                Declaration {
+                    configuration: conf,
+                    platform: caps.name("platform").map(|m| m.as_str()),
+                    group,
+                    artifact,
+                    version: version.map(String::from),
+                    version_var: None,
+                    line: lines.line_at(offset),
+                    unresolved_reason,
+                },
+            ));
+        }
+
+        for caps in MAP_NOTATION.captures_iter(content) {
+            let conf = capture(&caps, "conf");
+            if NON_CONFIGURATIONS.contains(&conf) {
+                continue;
+            }
+            let offset = caps.name("conf").map_or(0, |m| m.start());
+            found.push((
+                offset,
+                Declaration {
+                    configuration: conf,
+                    platform: None,
+                    group: capture(&caps, "group"),
+                    artifact: capture(&caps, "name"),
+                    version: caps.name("version").map(|m| m.as_str().to_string()),
+                    version_var: caps.name("version_var").map(|m| m.as_str().to_string()),
+                    line: lines.line_at(offset),
+                    unresolved_reason: None,
+                },
+            ));
+        }
+
+        for caps in CATALOG_ACCESSOR.captures_iter(content) {
+            if NON_CONFIGURATIONS.contains(&capture(&caps, "conf")) {
+                continue;
+            }
+            let offset = caps.name("conf").map_or(0, |m| m.start());
+            let accessor = &caps["accessor"];
+            build.dependencies.push(DeclaredDependency {
+                group_id: String::new(),
+                artifact_id: accessor.to_string(),
+                section: caps["conf"].to_string(),
+                scope: None,
+                declared_version: None,
+                version_property: None,
+                resolved_version: None,
+                repository: None,
+                line: lines.line_at(offset),
+                unresolved_reason: Some(
+                    "Version catalog accessor; analyze gradle/libs.versions.toml instead"
+                        .to_string(),
+                ),
+            });
+        }
+
+        for caps in PLUGIN_REQUEST.captures_iter(content) {
+            let id = match (caps.name("id"), caps.name("kotlin")) {
+                (Some(id), _) => id.as_str().to_string(),
+                (None, Some(module)) => format!("org.jetbrains.kotlin.{}", module.as_str()),
+                (None, None) => continue,
+            };
+            let offset = caps.get(0).map_or(0, |m| m.start());
+            let mut declaration = Declaration {
+                configuration: "plugins",
+                platform: None,
+                group: "",
+                artifact: "",
+                version: caps.name("version").map(|m| m.as_str().to_string()),
+                version_var: caps.name("version_var").map(|m| m.as_str().to_string()),
+                line: lines.line_at(offset),
+                unresolved_reason: None,
+            }
+            .resolve(&values);
+            // Plugins resolve through their marker artifact on the Plugin Portal
+            declaration.artifact_id = plugin_marker(&id);
+            declaration.group_id = id;
+            declaration.repository = Some(GRADLE_PLUGIN_PORTAL.to_string());
+            build.dependencies.push(declaration);
+        }
+
+        found.sort_by_key(|(offset, _)| *offset);
+        let resolved: Vec<DeclaredDependency> =
+            found.into_iter().map(|(_, d)| d.resolve(&values)).collect();
+        build.dependencies.extend(resolved);
+        build.dependencies.sort_by_key(|d| d.line);
+        build
+    }
+}
+
+/// artifactId of the marker a plugin id is published under, e.g.
+/// `org.jetbrains.kotlin.jvm.gradle.plugin`
+pub fn plugin_marker(id: &str) -> String {
+    format!("{}.gradle.plugin", id)
+}
+
+fn variable(caps: &Captures<'_>, name: &str, lines: &LineIndex) -> GradleVariable {
+    let m = caps.name(name).expect("matched group");
+    GradleVariable {
+        name: m.as_str().to_string(),
+        value: caps["value"].to_string(),
+        line: lines.line_at(m.start()),
+    }
+}
+
+fn capture<'h>(caps: &Captures<'h>, name: &str) -> &'h str {
+    caps.name(name).map_or("", |m| m.as_str())
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Parse `key=value` lines of a gradle.properties file
+pub fn parse_properties(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('!'))
+        .filter_map(|l| {
+            let (key, value) = l.split_once(['=', ':'])?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// A declaration as matched, before variable resolution
+struct Declaration<'a> {
+    configuration: &'a str,
+    platform: Option<&'a str>,
+    group: &'a str,
+    artifact: &'a str,
+    version: Option<String>,
+    version_var: Option<String>,
+    line: usize,
+    /// Why the coordinate itself cannot be checked
+    unresolved_reason: Option<String>,
+}
+
+impl Declaration<'_> {
+    fn resolve(self, values: &HashMap<String, String>) -> DeclaredDependency {
+        let declared_version = self
+            .version
+            .clone()
+            .or_else(|| self.version_var.as_ref().map(|v| format!("${{{}}}", v)));
+
+        let (resolved, version_property) = match (&self.version, &self.version_var) {
+            (Some(version), _) => (interpolate(version, values, 0), first_reference(version)),
+            (None, Some(var)) => {
+                let name = reference_name(var);
+                (values.get(&name).cloned().ok_or(name.clone()), Some(name))
+            }
+            (None, None) => (Err(String::new()), None),
+        };
+
+        let (resolved_version, unresolved_reason) = match resolved {
+            Ok(version) => {
+                let version = version.trim_end_matches("!!").to_string();
+                if VersionRange::is_dynamic(&version) {
+                    (
+                        Some(version),
+                        Some(
+                            "Dynamic versions are not checked; use resolve_version_range"
+                                .to_string(),
+                        ),
+                    )
+                } else {
+                    (Some(version), None)
+                }
+            }
+            Err(name) if name.is_empty() => (
+                None,
+                Some("No version declared; managed by a platform or plugin".to_string()),
+            ),
+            Err(name) => (None, Some(format!("Unresolved variable '{}'", name))),
+        };
+
+        DeclaredDependency {
+            group_id: self.group.to_string(),
+            artifact_id: self.artifact.to_string(),
+            section: self.configuration.to_string(),
+            scope: self.platform.map(String::from),
+            declared_version,
+            version_property,
+            resolved_version,
+            repository: None,
+            line: self.line,
+            unresolved_reason: self.unresolved_reason.or(unresolved_reason),
+        }
+    }
+}
+
+/// Normalize `project.ext.v`, `extra["v"]` or `property("v")` to `v`
+fn reference_name(reference: &str) -> String {
+    let reference = reference.trim();
+    match REFERENCE_WRAPPER.captures(reference) {
+        Some(caps) => caps
+            .name("quoted")
+            .or(caps.name("plain"))
+            .map_or(reference, |m| m.as_str())
+            .to_string(),
+        None => reference.to_string(),
+    }
+}
+
+/// Name of the first `$v` or `${v}` reference in a string
+fn first_reference(value: &str) -> Option<String> {
+    let start = value.find('$')? + 1;
+    let rest = &value[start..];
+    if let Some(inner) = rest.strip_prefix('{') {
+        let end = inner.find('}')?;
+        return Some(reference_name(&inner[..end]));
+    }
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    (end > 0).then(|| rest[..end].to_string())
+}
+
+/// Expand `$v` and `${v}` references, returning the first unknown name
+fn interpolate(
+    value: &str,
+    values: &HashMap<String, String>,
+    depth: usize,
+) -> Result<String, String> {
+    let mut output = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, remainder) = if let Some(inner) = after.strip_prefix('{') {
+            let Some(end) = inner.find('}') else {
+                return Err(inner.to_string());
+            };
+            (reference_name(&inner[..end]), &inner[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (after[..end].to_string(), &after[end..])
+        };
+
+        match values.get(&name) {
+            Some(v) if depth < MAX_INTERPOLATION_DEPTH => {
+                output.push_str(&interpolate(v, values, depth + 1)?)
+            }
+            _ => return Err(name),
+        }
+        rest = remainder;
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROOVY: &str = r#"
+ext {
+    jacksonVersion = '2.15.0'
+}
+def guavaVersion = "32.1.0-jre"
+
+dependencies {
+    implementation "com.fasterxml.jackson.core:jackson-databind:${jacksonVersion}"
+    implementation "com.google.guava:guava:$guavaVersion"
+    implementation platform('org.springframework.boot:spring-boot-dependencies:3.1.0')
+    implementation 'org.springframework.boot:spring-boot-starter-web'
+    testImplementation group: 'junit', name: 'junit', version: '4.13.1'
+    runtimeOnly group: 'org.postgresql', name: 'postgresql', version: postgresVersion
+    compileOnly libs.lombok
+    implementation "${springGroup}:spring-core:6.0.0"
+}
+
+repositories {
+    maven { url "https://repo.example.com/maven2" }
+}
+"#;
+
+    const KOTLIN: &str = r#"
+val kotlinxVersion = "1.7.3"
+val slf4jVersion by extra("2.0.9")
+
+dependencies {
+    implementation("org.jetbrains.kotlinx:kotlinx-coroutines-core:$kotlinxVersion")
+    implementation(enforcedPlatform("io.micronaut.platform:micronaut-platform:4.1.0"))
+    implementation(group = "org.slf4j", name = "slf4j-api", version = slf4jVersion)
+    testImplementation("org.junit.jupiter:junit-jupiter:5.+")
+}
+"#;
+
+    fn find<'a>(build: &'a GradleBuild, artifact: &str) -> &'a DeclaredDependency {
+        build
+            .dependencies
+            .iter()
+            .find(|d| d.artifact_id == artifact)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_groovy_dsl() {
+        let build = GradleBuild::parse(GROOVY, &HashMap::new());
+        assert_eq!(build.dependencies.len(), 8);
+
+        let jackson = find(&build, "jackson-databind");
+        assert_eq!(jackson.resolved_version.as_deref(), Some("2.15.0"));
+        assert_eq!(jackson.version_property.as_deref(), Some("jacksonVersion"));
+        assert_eq!(jackson.line, 8);
+
+        assert_eq!(
+            find(&build, "guava").resolved_version.as_deref(),
+            Some("32.1.0-jre")
+        );
+        let bom = find(&build, "spring-boot-dependencies");
+        assert_eq!(bom.scope.as_deref(), Some("platform"));
+        assert!(find(&build, "spring-boot-starter-web")
+            .unresolved_reason
+            .is_some());
+        assert_eq!(
+            find(&build, "junit").checkable_coordinate().as_deref(),
+            Some("junit:junit:4.13.1")
+        );
+        assert_eq!(
+            find(&build, "postgresql").unresolved_reason.as_deref(),
+            Some("Unresolved variable 'postgresVersion'")
+        );
+        assert!(find(&build, "libs.lombok").unresolved_reason.is_some());
+        let spring = find(&build, "spring-core");
+        assert_eq!(spring.group_id, "${springGroup}");
+        assert!(spring.checkable_coordinate().is_none());
+        assert!(spring
+            .unresolved_reason
+            .as_deref()
+            .unwrap()
+            .starts_with("Group or artifact is interpolated"));
+    }
+
+    #[test]
+    fn test_kotlin_dsl() {
+        let build = GradleBuild::parse(KOTLIN, &HashMap::new());
+        assert_eq!(
+            find(&build, "kotlinx-coroutines-core")
+                .resolved_version
+                .as_deref(),
+            Some("1.7.3")
+        );
+        assert_eq!(
+            find(&build, "micronaut-platform").scope.as_deref(),
+            Some("enforcedPlatform")
+        );
+        let slf4j = find(&build, "slf4j-api");
+        assert_eq!(slf4j.resolved_version.as_deref(), Some("2.0.9"));
+        assert_eq!(slf4j.version_property.as_deref(), Some("slf4jVersion"));
+        assert!(find(&build, "junit-jupiter")
+            .checkable_coordinate()
+            .is_none());
+    }
+
+    #[test]
+    fn test_plugin_requests() {
+        let build = GradleBuild::parse(
+            "val kotlinVersion = \"1.9.0\"\nplugins {\n    id(\"java\")\n    id(\"org.springframework.boot\") version \"3.1.0\"\n    kotlin(\"jvm\") version kotlinVersion\n    id 'com.diffplug.spotless' version '6.22.0' apply false\n}\n",
+            &HashMap::new(),
+        );
+        assert_eq!(build.dependencies.len(), 3);
+
+        let boot = &build.dependencies[0];
+        assert_eq!(
+            boot.checkable_coordinate().as_deref(),
+            Some("org.springframework.boot:org.springframework.boot.gradle.plugin:3.1.0")
+        );
+        assert_eq!(boot.repository(), GRADLE_PLUGIN_PORTAL);
+        assert_eq!(boot.line, 4);
+
+        let kotlin = &build.dependencies[1];
+        assert_eq!(kotlin.group_id, "org.jetbrains.kotlin.jvm");
+        assert_eq!(kotlin.resolved_version.as_deref(), Some("1.9.0"));
+        assert_eq!(kotlin.version_property.as_deref(), Some("kotlinVersion"));
+        assert_eq!(
+            build.dependencies[2].resolved_version.as_deref(),
+            Some("6.22.0")
+        );
+    }
+
+    #[test]
+    fn test_gradle_properties() {
+        let properties = parse_properties("# versions\njunitVersion=5.10.0\n");
+        let build = GradleBuild::parse(
+            "dependencies {\n  testImplementation(\"org.junit.jupiter:junit-jupiter:${project.junitVersion}\")\n}\n",
+            &properties,
+        );
+        assert_eq!(
+            build.dependencies[0].resolved_version.as_deref(),
+            Some("5.10.0")
+        );
+    }
+}
@@ -0,0 +1,131 @@
+pub mod bazel;
+pub mod catalog;
+pub mod edit;
+pub mod gradle;
+pub mod plugins;
+pub mod pom;
+pub mod reactor;
+pub mod resolved;
+pub mod sbom;
+pub mod wrapper;
+
+pub use bazel::{BazelError, MavenInstall};
+pub use catalog::{CatalogError, VersionCatalog};
+pub use edit::{BuildFileKind, VersionEdit};
+pub use gradle::GradleBuild;
+pub use pom::{EffectivePom, Pom, PomError};
+pub use reactor::{Reactor, ReactorModule, VersionConflict};
+pub use resolved::{ResolvedDependency, ResolvedFormat};
+pub use sbom::{Sbom, SbomError};
+pub use wrapper::{WrapperKind, WrapperProperties};
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::maven::MAVEN_CENTRAL_BASE;
+
+/// A dependency or plugin declared in a build file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeclaredDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    /// Where the declaration lives, e.g. "dependencies" or "pluginManagement"
+    pub section: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Version exactly as written in the build file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_version: Option<String>,
+    /// Property the version is taken from, e.g. "jackson.version"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_property: Option<String>,
+    /// Version after property interpolation and dependency management
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_version: Option<String>,
+    /// Repository the artifact is published to, when not Maven Central
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// 1-based line of the declaration
+    pub line: usize,
+    /// Why the declaration cannot be checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unresolved_reason: Option<String>,
+}
+
+impl DeclaredDependency {
+    pub fn to_ga(&self) -> String {
+        format!("{}:{}", self.group_id, self.artifact_id)
+    }
+
+    /// Repository to check the declaration against
+    pub fn repository(&self) -> &str {
+        self.repository.as_deref().unwrap_or(MAVEN_CENTRAL_BASE)
+    }
+
+    /// The coordinate to check, if the declaration resolved to a concrete version
+    pub fn checkable_coordinate(&self) -> Option<String> {
+        if self.unresolved_reason.is_some() {
+            return None;
+        }
+        self.resolved_version
+            .as_ref()
+            .map(|v| format!("{}:{}:{}", self.group_id, self.artifact_id, v))
+    }
+
+    /// The property holding the whole version, unless the version is composed
+    /// from it like `${guava.version}-jre`
+    pub fn shared_property(&self) -> Option<&str> {
+        let property = self.version_property.as_deref()?;
+        match &self.declared_version {
+            Some(declared) if !is_single_reference(declared) => None,
+            _ => Some(property),
+        }
+    }
+}
+
+/// Returns true if a value is exactly one `${name}` or `$name` reference
+pub fn is_single_reference(value: &str) -> bool {
+    let Some(rest) = value.trim().strip_prefix('$') else {
+        return false;
+    };
+    match rest.strip_prefix('{') {
+        Some(inner) => inner
+            .strip_suffix('}')
+            .is_some_and(|name| !name.is_empty() && !name.contains(['$', '{', '}'])),
+        None => !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+    }
+}
+
+/// Returns true if `path` exists and lies under the canonical `root` once
+/// symlinks are resolved. Every path is allowed without a root.
+pub async fn is_within(path: &Path, root: Option<&Path>) -> bool {
+    match root {
+        Some(root) => tokio::fs::canonicalize(path)
+            .await
+            .is_ok_and(|path| path.starts_with(root)),
+        None => true,
+    }
+}
+
+/// Maps byte offsets to 1-based line numbers
+#[derive(Debug, Clone)]
+pub(crate) struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(content: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { starts }
+    }
+
+    pub(crate) fn line_at(&self, offset: usize) -> usize {
+        match self.starts.binary_search(&offset) {
+            Ok(line) => line + 1,
+            Err(line) => line,
+        }
+    }
+}
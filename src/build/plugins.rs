@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use crate::build::pom::{PomDependency, DEFAULT_PLUGIN_GROUP};
+use crate::build::{is_within, DeclaredDependency};
+use crate::models::VersionRange;
+
+/// Plugin groups Maven searches for goal prefixes when none are configured
+pub const PLUGIN_GROUPS: &[&str] = &[DEFAULT_PLUGIN_GROUP, "org.codehaus.mojo"];
+
+/// Location of core extensions relative to the top-level project directory
+const EXTENSIONS_FILE: &str = ".mvn/extensions.xml";
+
+/// A goal as passed on the Maven command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoalReference {
+    /// `prefix:goal`, e.g. "surefire:test"
+    Prefix { prefix: String, goal: String },
+    /// `groupId:artifactId[:version]:goal`
+    Plugin {
+        group_id: String,
+        artifact_id: String,
+        version: Option<String>,
+        goal: String,
+    },
+}
+
+impl GoalReference {
+    /// Parse a goal; lifecycle phases like "verify" have no plugin and return `None`
+    pub fn parse(goal: &str) -> Option<Self> {
+        let parts: Vec<&str> = goal.trim().split(':').collect();
+        if parts.iter().any(|p| p.is_empty()) {
+            return None;
+        }
+        match parts.as_slice() {
+            [prefix, goal] => Some(GoalReference::Prefix {
+                prefix: prefix.to_string(),
+                goal: goal.to_string(),
+            }),
+            [group_id, artifact_id, goal] => Some(GoalReference::Plugin {
+                group_id: group_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                version: None,
+                goal: goal.to_string(),
+            }),
+            [group_id, artifact_id, version, goal] => Some(GoalReference::Plugin {
+                group_id: group_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                version: Some(version.to_string()),
+                goal: goal.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Goal prefix implied by the plugin naming convention, e.g. "surefire" for
+/// `maven-surefire-plugin` and "spotless" for `spotless-maven-plugin`
+pub fn conventional_prefix(artifact_id: &str) -> Option<&str> {
+    artifact_id
+        .strip_prefix("maven-")
+        .and_then(|a| a.strip_suffix("-plugin"))
+        .or_else(|| artifact_id.strip_suffix("-maven-plugin"))
+        .filter(|p| !p.is_empty())
+}
+
+/// Find `.mvn/extensions.xml` in the project directory or the nearest
+/// ancestor that has a `.mvn` directory, without leaving `root`
+pub async fn find_extensions_file(dir: &Path, root: Option<&Path>) -> Option<PathBuf> {
+    for ancestor in dir.ancestors() {
+        if !is_within(ancestor, root).await {
+            return None;
+        }
+        let mvn = tokio::fs::metadata(ancestor.join(".mvn")).await;
+        if mvn.is_ok_and(|m| m.is_dir()) {
+            let file = ancestor.join(EXTENSIONS_FILE);
+            let is_file = tokio::fs::metadata(&file).await.is_ok_and(|m| m.is_file());
+            return (is_file && is_within(&file, root).await).then_some(file);
+        }
+    }
+    None
+}
+
+/// Declarations for the entries of a `.mvn/extensions.xml` file
+pub fn extension_declarations(entries: &[PomDependency]) -> Vec<DeclaredDependency> {
+    entries
+        .iter()
+        .map(|entry| {
+            let unresolved_reason = match &entry.version {
+                None => Some("No version declared".to_string()),
+                Some(v) if v.contains("${") => {
+                    Some("Properties are not resolved in extensions.xml".to_string())
+                }
+                Some(v) if VersionRange::is_dynamic(v) => {
+                    Some("Version ranges are not checked; use resolve_version_range".to_string())
+                }
+                Some(_) => None,
+            };
+            DeclaredDependency {
+                group_id: entry.group_id.clone().unwrap_or_default(),
+                artifact_id: entry.artifact_id.clone(),
+                section: "extensions.xml".to_string(),
+                scope: None,
+                declared_version: entry.version.clone(),
+                version_property: None,
+                resolved_version: entry.version.clone(),
+                repository: None,
+                line: entry.line,
+                unresolved_reason,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::Pom;
+
+    #[test]
+    fn test_parse_goal() {
+        assert_eq!(GoalReference::parse("verify"), None);
+        assert_eq!(
+            GoalReference::parse("versions:display-dependency-updates"),
+            Some(GoalReference::Prefix {
+                prefix: "versions".to_string(),
+                goal: "display-dependency-updates".to_string(),
+            })
+        );
+        assert_eq!(
+            GoalReference::parse("org.jacoco:jacoco-maven-plugin:0.8.11:report"),
+            Some(GoalReference::Plugin {
+                group_id: "org.jacoco".to_string(),
+                artifact_id: "jacoco-maven-plugin".to_string(),
+                version: Some("0.8.11".to_string()),
+                goal: "report".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_conventional_prefix() {
+        assert_eq!(
+            conventional_prefix("maven-surefire-plugin"),
+            Some("surefire")
+        );
+        assert_eq!(
+            conventional_prefix("spotless-maven-plugin"),
+            Some("spotless")
+        );
+        assert_eq!(conventional_prefix("jib-core"), None);
+    }
+
+    #[test]
+    fn test_extension_declarations() {
+        let xml = r#"<extensions>
+  <extension>
+    <groupId>kr.motd.maven</groupId>
+    <artifactId>os-maven-plugin</artifactId>
+    <version>1.7.1</version>
+  </extension>
+  <extension>
+    <groupId>com.acme</groupId>
+    <artifactId>acme-extension</artifactId>
+  </extension>
+</extensions>"#;
+
+        let entries = Pom::parse_extensions(xml).unwrap();
+        let declarations = extension_declarations(&entries);
+        assert_eq!(declarations.len(), 2);
+        assert_eq!(
+            declarations[0].checkable_coordinate().as_deref(),
+            Some("kr.motd.maven:os-maven-plugin:1.7.1")
+        );
+        assert_eq!(declarations[0].line, 2);
+        assert!(declarations[1].unresolved_reason.is_some());
+        assert!(Pom::parse_extensions("<project/>").is_err());
+    }
+}
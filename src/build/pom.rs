@@ -0,0 +1,1103 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::build::reactor::{find_module, ReactorModule};
+use crate::build::{is_within, DeclaredDependency, LineIndex};
+use crate::maven::MavenClient;
+use crate::models::{MavenCoordinate, VersionRange};
+
+/// groupId Maven assumes for plugins declared without one
+pub const DEFAULT_PLUGIN_GROUP: &str = "org.apache.maven.plugins";
+
+const MAX_PARENT_DEPTH: usize = 10;
+const MAX_IMPORT_DEPTH: usize = 5;
+const MAX_INTERPOLATION_DEPTH: usize = 10;
+
+#[derive(Error, Debug)]
+pub enum PomError {
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid POM XML at line {line}: {message}")]
+    Xml { line: usize, message: String },
+    #[error("Not a POM: missing <project> root element")]
+    NotAPom,
+    #[error("Not an extensions.xml: missing <extensions> root element")]
+    NotAnExtensionsFile,
+}
+
+/// A `<parent>` reference
+#[derive(Debug, Clone, Default)]
+pub struct PomParent {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    pub relative_path: Option<String>,
+    pub line: usize,
+}
+
+/// A `<properties>` entry
+#[derive(Debug, Clone)]
+pub struct PomProperty {
+    pub name: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// A `<dependency>` or `<plugin>` entry exactly as written
+#[derive(Debug, Clone, Default)]
+pub struct PomDependency {
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub dependency_type: Option<String>,
+    pub optional: bool,
+    pub line: usize,
+    pub version_line: Option<usize>,
+}
+
+/// A `<license>` entry
+#[derive(Debug, Clone, Default)]
+pub struct PomLicense {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// The `<organization>` publishing the project
+#[derive(Debug, Clone, Default)]
+pub struct PomOrganization {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A `<distributionManagement><relocation>`: the artifact has moved to new
+/// coordinates, and elements that are left out keep their old value
+#[derive(Debug, Clone, Default)]
+pub struct PomRelocation {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub message: Option<String>,
+}
+
+/// The parts of a pom.xml relevant to dependency analysis
+#[derive(Debug, Clone, Default)]
+pub struct Pom {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub packaging: Option<String>,
+    pub parent: Option<PomParent>,
+    pub licenses: Vec<PomLicense>,
+    pub organization: Option<PomOrganization>,
+    pub relocation: Option<PomRelocation>,
+    pub properties: Vec<PomProperty>,
+    pub dependencies: Vec<PomDependency>,
+    pub dependency_management: Vec<PomDependency>,
+    pub plugins: Vec<PomDependency>,
+    pub plugin_management: Vec<PomDependency>,
+    /// Build extensions from `<build><extensions>`
+    pub extensions: Vec<PomDependency>,
+    pub modules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Section {
+    Dependencies,
+    DependencyManagement,
+    Plugins,
+    PluginManagement,
+    Extensions,
+}
+
+impl Pom {
+    /// Read and parse a pom.xml file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PomError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| PomError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::parse(&content)
+    }
+
+    /// Parse pom.xml content, recording the line of every declaration
+    pub fn parse(content: &str) -> Result<Self, PomError> {
+        let parser = read_xml(content)?;
+        if !parser.seen_project {
+            return Err(PomError::NotAPom);
+        }
+        Ok(parser.pom)
+    }
+
+    /// Parse a `.mvn/extensions.xml` core extensions file
+    pub fn parse_extensions(content: &str) -> Result<Vec<PomDependency>, PomError> {
+        let parser = read_xml(content)?;
+        if !parser.seen_extensions {
+            return Err(PomError::NotAnExtensionsFile);
+        }
+        Ok(parser.pom.extensions)
+    }
+
+    /// groupId of the project, inherited from the parent if not declared
+    pub fn effective_group_id(&self) -> Option<&str> {
+        self.group_id
+            .as_deref()
+            .or(self.parent.as_ref().map(|p| p.group_id.as_str()))
+    }
+
+    /// Version of the project, inherited from the parent if not declared
+    pub fn effective_version(&self) -> Option<&str> {
+        self.version
+            .as_deref()
+            .or(self.parent.as_ref().map(|p| p.version.as_str()))
+    }
+
+    /// groupId:artifactId:version of the project, with inherited values
+    pub fn to_gav(&self) -> Option<String> {
+        Some(format!(
+            "{}:{}:{}",
+            self.effective_group_id()?,
+            self.artifact_id.as_deref()?,
+            self.effective_version()?
+        ))
+    }
+
+    /// groupId:artifactId:version the project was relocated to, if any
+    pub fn relocation_target(&self) -> Option<String> {
+        let relocation = self.relocation.as_ref()?;
+        Some(format!(
+            "{}:{}:{}",
+            relocation
+                .group_id
+                .as_deref()
+                .or(self.effective_group_id())?,
+            relocation
+                .artifact_id
+                .as_deref()
+                .or(self.artifact_id.as_deref())?,
+            relocation.version.as_deref().or(self.effective_version())?
+        ))
+    }
+
+    /// Line of a property definition
+    pub fn property_line(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.line)
+    }
+}
+
+/// Walk the XML events of a POM or extensions.xml
+fn read_xml(content: &str) -> Result<PomParser, PomError> {
+    let lines = LineIndex::new(content);
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut parser = PomParser::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| PomError::Xml {
+            line: lines.line_at(reader.error_position() as usize),
+            message: e.to_string(),
+        })?;
+        let line = lines.line_at(reader.buffer_position() as usize);
+
+        match event {
+            Event::Start(e) => {
+                path.push(element_name(&e));
+                text.clear();
+                parser.start(&path, line);
+            }
+            Event::Empty(e) => {
+                path.push(element_name(&e));
+                text.clear();
+                parser.start(&path, line);
+                parser.end(&path, "", line);
+                path.pop();
+            }
+            Event::Text(e) => {
+                let unescaped = e.unescape().map_err(|e| PomError::Xml {
+                    line,
+                    message: e.to_string(),
+                })?;
+                text.push_str(&unescaped);
+            }
+            Event::CData(e) => text.push_str(&String::from_utf8_lossy(&e.into_inner())),
+            Event::End(_) => {
+                parser.end(&path, text.trim(), line);
+                path.pop();
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(parser)
+}
+
+fn element_name(e: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+/// Tracks the entry currently being read while walking the XML events
+#[derive(Default)]
+struct PomParser {
+    pom: Pom,
+    seen_project: bool,
+    seen_extensions: bool,
+    current: Option<(Section, usize, PomDependency)>,
+}
+
+impl PomParser {
+    fn start(&mut self, path: &[String], line: usize) {
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        let section = match path.as_slice() {
+            ["project"] => {
+                self.seen_project = true;
+                None
+            }
+            ["extensions"] => {
+                self.seen_extensions = true;
+                None
+            }
+            ["project", "parent"] => {
+                self.pom.parent = Some(PomParent {
+                    line,
+                    ..Default::default()
+                });
+                None
+            }
+            ["project", "licenses", "license"] => {
+                self.pom.licenses.push(PomLicense::default());
+                None
+            }
+            ["project", "organization"] => {
+                self.pom.organization = Some(PomOrganization::default());
+                None
+            }
+            ["project", "distributionManagement", "relocation"] => {
+                self.pom.relocation = Some(PomRelocation::default());
+                None
+            }
+            ["project", "dependencies", "dependency"] => Some(Section::Dependencies),
+            ["project", "dependencyManagement", "dependencies", "dependency"] => {
+                Some(Section::DependencyManagement)
+            }
+            ["project", "build", "plugins", "plugin"] => Some(Section::Plugins),
+            ["project", "build", "pluginManagement", "plugins", "plugin"] => {
+                Some(Section::PluginManagement)
+            }
+            ["project", "build", "extensions", "extension"] | ["extensions", "extension"] => {
+                Some(Section::Extensions)
+            }
+            _ => None,
+        };
+
+        if let Some(section) = section {
+            let entry = PomDependency {
+                line,
+                ..Default::default()
+            };
+            self.current = Some((section, path.len(), entry));
+        }
+    }
+
+    fn end(&mut self, path: &[String], text: &str, line: usize) {
+        if let Some((section, depth, entry)) = &mut self.current {
+            if path.len() == *depth + 1 {
+                let value = Some(text.to_string());
+                match path[*depth].as_str() {
+                    "groupId" => entry.group_id = value,
+                    "artifactId" => entry.artifact_id = text.to_string(),
+                    "version" => {
+                        entry.version = value;
+                        entry.version_line = Some(line);
+                    }
+                    "scope" => entry.scope = value,
+                    "type" => entry.dependency_type = value,
+                    "optional" => entry.optional = text == "true",
+                    _ => {}
+                }
+                return;
+            }
+            if path.len() == *depth {
+                let section = *section;
+                let (_, _, entry) = self.current.take().expect("current entry");
+                match section {
+                    Section::Dependencies => self.pom.dependencies.push(entry),
+                    Section::DependencyManagement => self.pom.dependency_management.push(entry),
+                    Section::Plugins => self.pom.plugins.push(entry),
+                    Section::PluginManagement => self.pom.plugin_management.push(entry),
+                    Section::Extensions => self.pom.extensions.push(entry),
+                }
+                return;
+            }
+            if path.len() > *depth {
+                return;
+            }
+        }
+
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        let value = Some(text.to_string());
+        match path.as_slice() {
+            ["project", "groupId"] => self.pom.group_id = value,
+            ["project", "artifactId"] => self.pom.artifact_id = value,
+            ["project", "version"] => self.pom.version = value,
+            ["project", "packaging"] => self.pom.packaging = value,
+            ["project", "parent", field] => {
+                if let Some(parent) = &mut self.pom.parent {
+                    match *field {
+                        "groupId" => parent.group_id = text.to_string(),
+                        "artifactId" => parent.artifact_id = text.to_string(),
+                        "version" => parent.version = text.to_string(),
+                        "relativePath" => parent.relative_path = value,
+                        _ => {}
+                    }
+                }
+            }
+            ["project", "properties", name] => self.pom.properties.push(PomProperty {
+                name: name.to_string(),
+                value: text.to_string(),
+                line,
+            }),
+            ["project", "modules", "module"] => self.pom.modules.push(text.to_string()),
+            ["project", "licenses", "license", field] => {
+                if let Some(license) = self.pom.licenses.last_mut() {
+                    match *field {
+                        "name" => license.name = value,
+                        "url" => license.url = value,
+                        _ => {}
+                    }
+                }
+            }
+            ["project", "organization", field] => {
+                if let Some(organization) = &mut self.pom.organization {
+                    match *field {
+                        "name" => organization.name = value,
+                        "url" => organization.url = value,
+                        _ => {}
+                    }
+                }
+            }
+            ["project", "distributionManagement", "relocation", field] => {
+                if let Some(relocation) = &mut self.pom.relocation {
+                    match *field {
+                        "groupId" => relocation.group_id = value,
+                        "artifactId" => relocation.artifact_id = value,
+                        "version" => relocation.version = value,
+                        "message" => relocation.message = value,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A POM together with its parent chain, merged properties and managed versions
+#[derive(Debug, Clone)]
+pub struct EffectivePom {
+    pub pom: Pom,
+    /// Parent POMs, nearest first
+    pub parents: Vec<Pom>,
+    /// Problems encountered while loading parents or imported BOMs
+    pub warnings: Vec<String>,
+    properties: HashMap<String, String>,
+    managed_dependencies: HashMap<String, String>,
+    managed_plugins: HashMap<String, String>,
+}
+
+impl EffectivePom {
+    /// Resolve parents, properties and dependency management for a POM.
+    ///
+    /// Parents are looked up via `relativePath` next to `path` first and
+    /// fetched from Maven Central otherwise. Local parents outside `root`,
+    /// when one is given, are not read. BOMs imported through
+    /// `<scope>import</scope>` are always fetched from Maven Central.
+    pub async fn resolve(
+        pom: Pom,
+        path: Option<&Path>,
+        root: Option<&Path>,
+        client: &MavenClient,
+    ) -> Self {
+        Self::resolve_nested(pom, path.map(Path::to_path_buf), root, &[], client, 0).await
+    }
+
+    /// Resolve a POM that is part of a reactor build, so that parents and
+    /// imported BOMs which are modules of the same build are used as-is
+    pub async fn resolve_in_reactor(
+        pom: Pom,
+        path: &Path,
+        root: Option<&Path>,
+        reactor: &[ReactorModule],
+        client: &MavenClient,
+    ) -> Self {
+        Self::resolve_nested(pom, Some(path.to_path_buf()), root, reactor, client, 0).await
+    }
+
+    fn resolve_nested<'a>(
+        pom: Pom,
+        path: Option<PathBuf>,
+        root: Option<&'a Path>,
+        reactor: &'a [ReactorModule],
+        client: &'a MavenClient,
+        import_depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Self> + Send + 'a>> {
+        Box::pin(async move {
+            let mut warnings = Vec::new();
+            let parents = load_parents(&pom, path, root, reactor, client, &mut warnings).await;
+
+            let mut effective = EffectivePom {
+                pom,
+                parents,
+                warnings,
+                properties: HashMap::new(),
+                managed_dependencies: HashMap::new(),
+                managed_plugins: HashMap::new(),
+            };
+            effective.collect_properties();
+            effective.collect_plugin_management();
+            let imports = effective.collect_dependency_management();
+
+            for import in imports {
+                if import_depth >= MAX_IMPORT_DEPTH {
+                    effective
+                        .warnings
+                        .push(format!("Skipped BOM {}: imports nested too deep", import));
+                    continue;
+                }
+                let (bom, bom_path) =
+                    match find_module(reactor, &import.group_id, &import.artifact_id) {
+                        Some(module) => (module.pom.clone(), Some(module.path.clone())),
+                        None => match fetch_pom(client, &import).await {
+                            Ok(bom) => (bom, None),
+                            Err(e) => {
+                                effective
+                                    .warnings
+                                    .push(format!("Could not load BOM {}: {}", import, e));
+                                continue;
+                            }
+                        },
+                    };
+                let bom =
+                    Self::resolve_nested(bom, bom_path, root, reactor, client, import_depth + 1)
+                        .await;
+                effective.warnings.extend(bom.warnings);
+                for (ga, version) in bom.managed_dependencies {
+                    effective.managed_dependencies.entry(ga).or_insert(version);
+                }
+            }
+
+            effective
+        })
+    }
+
+    /// The POM followed by its parents, nearest first
+    fn chain(&self) -> impl Iterator<Item = &Pom> {
+        std::iter::once(&self.pom).chain(self.parents.iter())
+    }
+
+    fn collect_properties(&mut self) {
+        let mut properties = HashMap::new();
+        // Ancestors first so that nearer POMs override them
+        for pom in self.chain().collect::<Vec<_>>().into_iter().rev() {
+            for property in &pom.properties {
+                properties.insert(property.name.clone(), property.value.clone());
+            }
+        }
+
+        let pom = &self.pom;
+        let mut builtin = Vec::new();
+        if let Some(group_id) = pom.effective_group_id() {
+            builtin.push(("project.groupId", group_id.to_string()));
+        }
+        if let Some(artifact_id) = &pom.artifact_id {
+            builtin.push(("project.artifactId", artifact_id.clone()));
+        }
+        if let Some(version) = pom.effective_version() {
+            builtin.push(("project.version", version.to_string()));
+            builtin.push(("pom.version", version.to_string()));
+            builtin.push(("version", version.to_string()));
+        }
+        if let Some(parent) = &pom.parent {
+            builtin.push(("project.parent.groupId", parent.group_id.clone()));
+            builtin.push(("project.parent.artifactId", parent.artifact_id.clone()));
+            builtin.push(("project.parent.version", parent.version.clone()));
+        }
+        for (name, value) in builtin {
+            properties.insert(name.to_string(), value);
+        }
+
+        self.properties = properties;
+    }
+
+    /// Collect managed plugin versions, including plugins configured in parents
+    fn collect_plugin_management(&mut self) {
+        let mut managed = HashMap::new();
+        let entries: Vec<&PomDependency> = std::iter::once(&self.pom)
+            .flat_map(|p| p.plugin_management.iter())
+            .chain(
+                self.parents
+                    .iter()
+                    .flat_map(|p| p.plugin_management.iter().chain(p.plugins.iter())),
+            )
+            .collect();
+
+        for entry in entries {
+            let group_id = entry.group_id.as_deref().unwrap_or(DEFAULT_PLUGIN_GROUP);
+            let (Some(version), Ok(ga)) = (&entry.version, self.interpolate_ga(group_id, entry))
+            else {
+                continue;
+            };
+            if let Ok(version) = self.interpolate(version) {
+                managed.entry(ga).or_insert(version);
+            }
+        }
+
+        self.managed_plugins = managed;
+    }
+
+    /// Collect managed dependency versions, returning the BOMs to import
+    fn collect_dependency_management(&mut self) -> Vec<MavenCoordinate> {
+        let mut managed = HashMap::new();
+        let mut imports = Vec::new();
+        let mut warnings = Vec::new();
+
+        for entry in self.chain().flat_map(|p| p.dependency_management.iter()) {
+            let Some(group_id) = entry.group_id.as_deref() else {
+                continue;
+            };
+            let ga = match self.interpolate_ga(group_id, entry) {
+                Ok(ga) => ga,
+                Err(property) => {
+                    warnings.push(format!(
+                        "Unresolved property ${{{}}} in managed dependency {}:{}",
+                        property, group_id, entry.artifact_id
+                    ));
+                    continue;
+                }
+            };
+            let version = match entry.version.as_deref().map(|v| self.interpolate(v)) {
+                Some(Ok(version)) => version,
+                Some(Err(property)) => {
+                    warnings.push(format!(
+                        "Unresolved property ${{{}}} in managed dependency {}",
+                        property, ga
+                    ));
+                    continue;
+                }
+                None => continue,
+            };
+
+            if entry.scope.as_deref() == Some("import") {
+                if let Ok(mut coordinate) = MavenCoordinate::parse(&ga) {
+                    coordinate.version = Some(version);
+                    imports.push(coordinate);
+                }
+            } else {
+                managed.entry(ga).or_insert(version);
+            }
+        }
+
+        self.managed_dependencies = managed;
+        self.warnings.extend(warnings);
+        imports
+    }
+
+    fn interpolate_ga(&self, group_id: &str, entry: &PomDependency) -> Result<String, String> {
+        Ok(format!(
+            "{}:{}",
+            self.interpolate(group_id)?,
+            self.interpolate(&entry.artifact_id)?
+        ))
+    }
+
+    /// Replace `${property}` references, returning the name of the first
+    /// property that cannot be resolved
+    pub fn interpolate(&self, value: &str) -> Result<String, String> {
+        self.interpolate_depth(value, 0)
+    }
+
+    fn interpolate_depth(&self, value: &str, depth: usize) -> Result<String, String> {
+        let mut output = String::new();
+        let mut rest = value;
+
+        while let Some(start) = rest.find("${") {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find('}') else {
+                output.push_str(&rest[start..]);
+                return Ok(output);
+            };
+            let name = &after[..end];
+            let resolved = match self.properties.get(name) {
+                Some(v) if depth < MAX_INTERPOLATION_DEPTH => {
+                    self.interpolate_depth(v, depth + 1)?
+                }
+                _ => return Err(name.to_string()),
+            };
+            output.push_str(&resolved);
+            rest = &after[end + 1..];
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    /// Version managed for a dependency by this POM, its parents or imported BOMs
+    pub fn managed_version(&self, ga: &str) -> Option<&str> {
+        self.managed_dependencies.get(ga).map(String::as_str)
+    }
+
+    /// Version managed for a plugin by this POM or its parents
+    pub fn managed_plugin_version(&self, ga: &str) -> Option<&str> {
+        self.managed_plugins.get(ga).map(String::as_str)
+    }
+
+    /// Licenses of the project, inherited from the nearest parent declaring any
+    pub fn licenses(&self) -> &[PomLicense] {
+        self.chain()
+            .map(|p| p.licenses.as_slice())
+            .find(|l| !l.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Organization of the project, inherited from the nearest parent declaring one
+    pub fn organization(&self) -> Option<&PomOrganization> {
+        self.chain().find_map(|p| p.organization.as_ref())
+    }
+
+    /// Dependencies a consumer of this artifact receives transitively:
+    /// non-optional compile and runtime dependencies, including inherited ones
+    pub fn runtime_dependencies(&self) -> Vec<DeclaredDependency> {
+        let mut seen = HashSet::new();
+        self.chain()
+            .flat_map(|p| p.dependencies.iter())
+            .filter(|entry| {
+                !entry.optional
+                    && matches!(entry.scope.as_deref(), None | Some("compile" | "runtime"))
+            })
+            .map(|entry| self.declaration("dependencies", entry, false))
+            .filter(|d| seen.insert(d.to_ga()))
+            .collect()
+    }
+
+    /// Every declaration in the POM itself, with resolved versions
+    pub fn declarations(&self) -> Vec<DeclaredDependency> {
+        let mut declarations = Vec::new();
+
+        if let Some(parent) = &self.pom.parent {
+            declarations.push(DeclaredDependency {
+                group_id: parent.group_id.clone(),
+                artifact_id: parent.artifact_id.clone(),
+                section: "parent".to_string(),
+                scope: None,
+                declared_version: Some(parent.version.clone()),
+                version_property: property_reference(&parent.version),
+                resolved_version: self.interpolate(&parent.version).ok(),
+                repository: None,
+                line: parent.line,
+                unresolved_reason: None,
+            });
+        }
+
+        let sections = [
+            ("dependencies", &self.pom.dependencies, false),
+            (
+                "dependencyManagement",
+                &self.pom.dependency_management,
+                false,
+            ),
+            ("plugins", &self.pom.plugins, true),
+            ("pluginManagement", &self.pom.plugin_management, true),
+            ("extensions", &self.pom.extensions, false),
+        ];
+
+        for (section, entries, is_plugin) in sections {
+            for entry in entries {
+                declarations.push(self.declaration(section, entry, is_plugin));
+            }
+        }
+
+        declarations
+    }
+
+    fn declaration(
+        &self,
+        section: &str,
+        entry: &PomDependency,
+        is_plugin: bool,
+    ) -> DeclaredDependency {
+        let raw_group = match (&entry.group_id, is_plugin) {
+            (Some(group_id), _) => group_id.as_str(),
+            (None, true) => DEFAULT_PLUGIN_GROUP,
+            (None, false) => "",
+        };
+        let group_id = self
+            .interpolate(raw_group)
+            .unwrap_or_else(|_| raw_group.to_string());
+        let artifact_id = self
+            .interpolate(&entry.artifact_id)
+            .unwrap_or_else(|_| entry.artifact_id.clone());
+        let ga = format!("{}:{}", group_id, artifact_id);
+
+        let resolved = match &entry.version {
+            Some(version) => self
+                .interpolate(version)
+                .map_err(|property| format!("Unresolved property ${{{}}}", property)),
+            None => {
+                let managed = if is_plugin {
+                    self.managed_plugin_version(&ga)
+                } else {
+                    self.managed_version(&ga)
+                };
+                match managed {
+                    Some(version) => Ok(version.to_string()),
+                    None if is_plugin => Err(
+                        "No plugin version declared; Maven falls back to its default binding"
+                            .to_string(),
+                    ),
+                    None => Err("No version declared or managed".to_string()),
+                }
+            }
+        };
+
+        let (resolved_version, unresolved_reason) = match resolved {
+            Ok(version) if VersionRange::is_dynamic(&version) => (
+                Some(version),
+                Some("Version ranges are not checked; use resolve_version_range".to_string()),
+            ),
+            Ok(version) => (Some(version), None),
+            Err(reason) => (None, Some(reason)),
+        };
+
+        DeclaredDependency {
+            group_id,
+            artifact_id,
+            section: section.to_string(),
+            scope: entry.scope.clone(),
+            declared_version: entry.version.clone(),
+            version_property: entry.version.as_deref().and_then(property_reference),
+            resolved_version,
+            repository: None,
+            line: entry.line,
+            unresolved_reason,
+        }
+    }
+}
+
+/// Name of the first `${property}` referenced by a value
+fn property_reference(value: &str) -> Option<String> {
+    let start = value.find("${")? + 2;
+    let end = value[start..].find('}')? + start;
+    Some(value[start..end].to_string())
+}
+
+/// Walk the parent chain, preferring local parents and reactor modules over
+/// Maven Central
+async fn load_parents(
+    pom: &Pom,
+    mut path: Option<PathBuf>,
+    root: Option<&Path>,
+    reactor: &[ReactorModule],
+    client: &MavenClient,
+    warnings: &mut Vec<String>,
+) -> Vec<Pom> {
+    let mut parents: Vec<Pom> = Vec::new();
+
+    while parents.len() < MAX_PARENT_DEPTH {
+        let child = parents.last().unwrap_or(pom);
+        let Some(parent) = child.parent.clone() else {
+            break;
+        };
+
+        let local = match path.as_deref() {
+            Some(p) => load_local_parent(p, &parent, root).await,
+            None => None,
+        };
+        if let Some((local, local_path)) = local {
+            debug!("Using local parent {}", local_path.display());
+            parents.push(local);
+            path = Some(local_path);
+            continue;
+        }
+
+        if let Some(module) = find_module(reactor, &parent.group_id, &parent.artifact_id) {
+            debug!("Using reactor parent {}", module.path.display());
+            parents.push(module.pom.clone());
+            path = Some(module.path.clone());
+            continue;
+        }
+
+        let coordinate = MavenCoordinate {
+            group_id: parent.group_id.clone(),
+            artifact_id: parent.artifact_id.clone(),
+            version: Some(parent.version.clone()),
+        };
+        match fetch_pom(client, &coordinate).await {
+            Ok(remote) => {
+                parents.push(remote);
+                path = None;
+            }
+            Err(e) => {
+                warnings.push(format!("Could not load parent {}: {}", coordinate, e));
+                break;
+            }
+        }
+    }
+
+    parents
+}
+
+async fn load_local_parent(
+    child_path: &Path,
+    parent: &PomParent,
+    root: Option<&Path>,
+) -> Option<(Pom, PathBuf)> {
+    let relative = parent.relative_path.as_deref().unwrap_or("../pom.xml");
+    if relative.is_empty() {
+        return None;
+    }
+
+    let mut candidate = child_path.parent()?.join(relative);
+    if tokio::fs::metadata(&candidate)
+        .await
+        .is_ok_and(|m| m.is_dir())
+    {
+        candidate = candidate.join("pom.xml");
+    }
+    if !is_within(&candidate, root).await {
+        debug!("Skipping parent {} outside the root", candidate.display());
+        return None;
+    }
+    let content = tokio::fs::read_to_string(&candidate).await.ok()?;
+    let pom = Pom::parse(&content).ok()?;
+
+    let matches = pom.artifact_id.as_deref() == Some(parent.artifact_id.as_str())
+        && pom.effective_group_id() == Some(parent.group_id.as_str());
+    matches.then_some((pom, candidate))
+}
+
+async fn fetch_pom(client: &MavenClient, coordinate: &MavenCoordinate) -> anyhow::Result<Pom> {
+    let content = client.get_pom(coordinate).await?;
+    Ok(Pom::parse(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>demo</artifactId>
+  <version>1.0.0</version>
+  <properties>
+    <jackson.version>2.15.0</jackson.version>
+    <jackson.bom>${jackson.version}</jackson.bom>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.google.guava</groupId>
+        <artifactId>guava</artifactId>
+        <version>32.0.0-jre</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+      <version>${jackson.bom}</version>
+    </dependency>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>${project.groupId}</groupId>
+      <artifactId>demo-core</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>missing</artifactId>
+      <version>${missing.version}</version>
+    </dependency>
+  </dependencies>
+  <build>
+    <plugins>
+      <plugin>
+        <artifactId>maven-compiler-plugin</artifactId>
+        <version>3.11.0</version>
+        <dependencies>
+          <dependency>
+            <groupId>org.ow2.asm</groupId>
+            <artifactId>asm</artifactId>
+            <version>9.5</version>
+          </dependency>
+        </dependencies>
+      </plugin>
+    </plugins>
+  </build>
+</project>
+"#;
+
+    fn effective(pom: Pom) -> EffectivePom {
+        let mut effective = EffectivePom {
+            pom,
+            parents: Vec::new(),
+            warnings: Vec::new(),
+            properties: HashMap::new(),
+            managed_dependencies: HashMap::new(),
+            managed_plugins: HashMap::new(),
+        };
+        effective.collect_properties();
+        effective.collect_plugin_management();
+        effective.collect_dependency_management();
+        effective
+    }
+
+    #[test]
+    fn test_parse_sections_and_lines() {
+        let pom = Pom::parse(POM).unwrap();
+        assert_eq!(pom.group_id.as_deref(), Some("com.example"));
+        assert_eq!(pom.dependency_management.len(), 1);
+        assert_eq!(pom.dependencies.len(), 4);
+        assert_eq!(pom.dependencies[0].line, 21);
+        assert_eq!(pom.dependencies[0].version_line, Some(24));
+        // Plugin dependencies are not project dependencies
+        assert_eq!(pom.plugins.len(), 1);
+        assert_eq!(pom.plugins[0].artifact_id, "maven-compiler-plugin");
+        assert_eq!(pom.property_line("jackson.version"), Some(8));
+    }
+
+    #[test]
+    fn test_declarations_resolve_properties_and_management() {
+        let declarations = effective(Pom::parse(POM).unwrap()).declarations();
+        let find = |a: &str| declarations.iter().find(|d| d.artifact_id == a).unwrap();
+
+        let jackson = find("jackson-databind");
+        assert_eq!(jackson.resolved_version.as_deref(), Some("2.15.0"));
+        assert_eq!(jackson.version_property.as_deref(), Some("jackson.bom"));
+
+        let guava = declarations
+            .iter()
+            .find(|d| d.artifact_id == "guava" && d.section == "dependencies")
+            .unwrap();
+        assert_eq!(guava.resolved_version.as_deref(), Some("32.0.0-jre"));
+
+        let core = find("demo-core");
+        assert_eq!(core.group_id, "com.example");
+        assert_eq!(core.resolved_version.as_deref(), Some("1.0.0"));
+
+        let missing = find("missing");
+        assert!(missing.checkable_coordinate().is_none());
+        assert!(missing
+            .unresolved_reason
+            .as_deref()
+            .unwrap()
+            .contains("missing.version"));
+
+        let compiler = find("maven-compiler-plugin");
+        assert_eq!(compiler.group_id, DEFAULT_PLUGIN_GROUP);
+        assert_eq!(compiler.section, "plugins");
+    }
+
+    #[test]
+    fn test_licenses_and_runtime_dependencies() {
+        let pom = Pom::parse(
+            r#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>lib</artifactId>
+  <version>1.0</version>
+  <organization><name>Example Corp</name><url>https://example.com</url></organization>
+  <licenses>
+    <license><name>Apache License, Version 2.0</name><url>https://www.apache.org/licenses/LICENSE-2.0.txt</url></license>
+  </licenses>
+  <dependencies>
+    <dependency><groupId>g</groupId><artifactId>compile</artifactId><version>1</version></dependency>
+    <dependency><groupId>g</groupId><artifactId>runtime</artifactId><version>1</version><scope>runtime</scope></dependency>
+    <dependency><groupId>g</groupId><artifactId>test</artifactId><version>1</version><scope>test</scope></dependency>
+    <dependency><groupId>g</groupId><artifactId>optional</artifactId><version>1</version><optional>true</optional></dependency>
+  </dependencies>
+</project>"#,
+        )
+        .unwrap();
+        let effective = effective(pom);
+        assert_eq!(
+            effective.licenses()[0].name.as_deref(),
+            Some("Apache License, Version 2.0")
+        );
+        assert_eq!(
+            effective.organization().and_then(|o| o.name.as_deref()),
+            Some("Example Corp")
+        );
+        let runtime: Vec<String> = effective
+            .runtime_dependencies()
+            .iter()
+            .map(|d| d.artifact_id.clone())
+            .collect();
+        assert_eq!(runtime, vec!["compile", "runtime"]);
+    }
+
+    #[test]
+    fn test_relocation_target() {
+        let pom = Pom::parse(
+            r#"<project>
+  <groupId>mysql</groupId>
+  <artifactId>mysql-connector-java</artifactId>
+  <version>8.0.33</version>
+  <packaging>pom</packaging>
+  <distributionManagement>
+    <relocation>
+      <groupId>com.mysql</groupId>
+      <artifactId>mysql-connector-j</artifactId>
+      <message>MySQL Connector/J artifacts moved to reverse-DNS compliant Maven 2+ coordinates.</message>
+    </relocation>
+  </distributionManagement>
+</project>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pom.relocation_target().as_deref(),
+            Some("com.mysql:mysql-connector-j:8.0.33")
+        );
+        assert!(Pom::parse(POM).unwrap().relocation_target().is_none());
+    }
+
+    #[test]
+    fn test_interpolation_cycle_is_unresolved() {
+        let pom = Pom::parse("<project><properties><a>${b}</a><b>${a}</b></properties></project>")
+            .unwrap();
+        assert!(effective(pom).interpolate("${a}").is_err());
+    }
+
+    #[test]
+    fn test_invalid_xml() {
+        assert!(matches!(
+            Pom::parse("<project>\n<dependencies>\n</project>"),
+            Err(PomError::Xml { .. })
+        ));
+        assert!(matches!(Pom::parse("<settings/>"), Err(PomError::NotAPom)));
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::build::{DeclaredDependency, Pom, PomError};
+
+/// A module of a multi-module Maven build
+#[derive(Debug, Clone)]
+pub struct ReactorModule {
+    pub path: PathBuf,
+    pub pom: Pom,
+}
+
+impl ReactorModule {
+    /// groupId:artifactId of the module
+    pub fn to_ga(&self) -> Option<String> {
+        Some(format!(
+            "{}:{}",
+            self.pom.effective_group_id()?,
+            self.pom.artifact_id.as_deref()?
+        ))
+    }
+}
+
+/// All modules reachable from a root pom.xml through `<modules>`
+#[derive(Debug, Clone)]
+pub struct Reactor {
+    pub root: PathBuf,
+    /// Modules in declaration order, the root first
+    pub modules: Vec<ReactorModule>,
+    /// Modules that could not be read
+    pub warnings: Vec<String>,
+}
+
+impl Reactor {
+    /// Walk `<modules>` recursively starting at the root pom.xml. Modules
+    /// outside `within`, when given, are skipped.
+    pub fn scan(root: impl AsRef<Path>, within: Option<&Path>) -> Result<Self, PomError> {
+        let root = pom_file(root.as_ref());
+        let root_pom = Pom::load(&root)?;
+
+        let mut reactor = Reactor {
+            root: root.clone(),
+            modules: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let mut visited = HashSet::new();
+        visited.insert(canonical(&root));
+        reactor.visit(root, root_pom, within, &mut visited);
+
+        Ok(reactor)
+    }
+
+    fn visit(
+        &mut self,
+        path: PathBuf,
+        pom: Pom,
+        within: Option<&Path>,
+        visited: &mut HashSet<PathBuf>,
+    ) {
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let children = pom.modules.clone();
+        self.modules.push(ReactorModule { path, pom });
+
+        for module in children {
+            let child = pom_file(&dir.join(&module));
+            let canonical_child = canonical(&child);
+            if !visited.insert(canonical_child.clone()) {
+                continue;
+            }
+            if within.is_some_and(|within| !canonical_child.starts_with(within)) {
+                self.warnings.push(format!(
+                    "Skipped module '{}': outside the readable directory",
+                    module
+                ));
+                continue;
+            }
+            match Pom::load(&child) {
+                Ok(pom) => self.visit(child, pom, within, visited),
+                Err(e) => self
+                    .warnings
+                    .push(format!("Skipped module '{}': {}", module, e)),
+            }
+        }
+    }
+
+    /// groupId:artifactId of every module, used to tell internal
+    /// dependencies from external ones
+    pub fn module_coordinates(&self) -> HashSet<String> {
+        self.modules
+            .iter()
+            .filter_map(ReactorModule::to_ga)
+            .collect()
+    }
+
+    /// Path of a module's POM relative to the root directory
+    pub fn relative_path(&self, path: &Path) -> String {
+        let base = self.root.parent().unwrap_or(Path::new(""));
+        path.strip_prefix(base)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Find a reactor module by its coordinates
+pub fn find_module<'a>(
+    reactor: &'a [ReactorModule],
+    group_id: &str,
+    artifact_id: &str,
+) -> Option<&'a ReactorModule> {
+    reactor.iter().find(|m| {
+        m.pom.artifact_id.as_deref() == Some(artifact_id)
+            && m.pom.effective_group_id() == Some(group_id)
+    })
+}
+
+/// A module pom.xml, given either the file or its directory
+fn pom_file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join("pom.xml")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The same external dependency declared at different versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionConflict {
+    pub dependency: String,
+    pub versions: Vec<String>,
+    pub declarations: Vec<ModuleDeclaration>,
+}
+
+/// Where one of the conflicting versions is declared
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleDeclaration {
+    pub module: String,
+    pub version: String,
+    pub section: String,
+    pub line: usize,
+}
+
+/// Find external dependencies declared at more than one version across modules
+pub fn find_version_conflicts(
+    modules: &[(String, Vec<DeclaredDependency>)],
+    internal: &HashSet<String>,
+) -> Vec<VersionConflict> {
+    let mut by_dependency: BTreeMap<String, Vec<ModuleDeclaration>> = BTreeMap::new();
+    for (module, declarations) in modules {
+        for declaration in declarations {
+            let ga = declaration.to_ga();
+            let Some(version) = &declaration.resolved_version else {
+                continue;
+            };
+            if internal.contains(&ga) {
+                continue;
+            }
+            by_dependency
+                .entry(ga)
+                .or_default()
+                .push(ModuleDeclaration {
+                    module: module.clone(),
+                    version: version.clone(),
+                    section: declaration.section.clone(),
+                    line: declaration.line,
+                });
+        }
+    }
+
+    by_dependency
+        .into_iter()
+        .filter_map(|(dependency, declarations)| {
+            let mut versions: Vec<String> = Vec::new();
+            for declaration in &declarations {
+                if !versions.contains(&declaration.version) {
+                    versions.push(declaration.version.clone());
+                }
+            }
+            (versions.len() > 1).then_some(VersionConflict {
+                dependency,
+                versions,
+                declarations,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared(ga: &str, version: &str) -> DeclaredDependency {
+        let (group_id, artifact_id) = ga.split_once(':').unwrap();
+        DeclaredDependency {
+            group_id: group_id.to_string(),
+            artifact_id: artifact_id.to_string(),
+            section: "dependencies".to_string(),
+            scope: None,
+            declared_version: Some(version.to_string()),
+            version_property: None,
+            resolved_version: Some(version.to_string()),
+            repository: None,
+            line: 1,
+            unresolved_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_scan_nested_modules() {
+        let root = std::env::temp_dir().join(format!("maven-mcp-reactor-{}", std::process::id()));
+        let write = |dir: &Path, body: &str| {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("pom.xml"), format!("<project>{}</project>", body)).unwrap();
+        };
+        write(
+            &root,
+            "<groupId>com.acme</groupId><artifactId>root</artifactId>\
+             <modules><module>api</module><module>missing</module></modules>",
+        );
+        write(
+            &root.join("api"),
+            "<artifactId>api</artifactId><modules><module>..</module><module>impl</module></modules>",
+        );
+        write(&root.join("api/impl"), "<artifactId>impl</artifactId>");
+
+        let reactor = Reactor::scan(&root, None).unwrap();
+        let api = canonical(&root.join("api"));
+        let within_api = Reactor::scan(root.join("api"), Some(&api)).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(within_api.modules.len(), 2);
+        assert_eq!(
+            within_api.warnings,
+            vec!["Skipped module '..': outside the readable directory"]
+        );
+
+        let modules: Vec<String> = reactor
+            .modules
+            .iter()
+            .map(|m| reactor.relative_path(&m.path))
+            .collect();
+        assert_eq!(modules, vec!["pom.xml", "api/pom.xml", "api/impl/pom.xml"]);
+        assert_eq!(reactor.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_version_conflicts_ignore_internal_modules() {
+        let modules = vec![
+            (
+                "a/pom.xml".to_string(),
+                vec![
+                    declared("com.google.guava:guava", "32.0.0-jre"),
+                    declared("com.acme:core", "1.0"),
+                ],
+            ),
+            (
+                "b/pom.xml".to_string(),
+                vec![
+                    declared("com.google.guava:guava", "33.0.0-jre"),
+                    declared("com.acme:core", "2.0"),
+                    declared("org.slf4j:slf4j-api", "2.0.9"),
+                ],
+            ),
+        ];
+        let internal = HashSet::from(["com.acme:core".to_string()]);
+
+        let conflicts = find_version_conflicts(&modules, &internal);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].dependency, "com.google.guava:guava");
+        assert_eq!(conflicts[0].versions, vec!["32.0.0-jre", "33.0.0-jre"]);
+    }
+}
@@ -0,0 +1,297 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Scopes Maven prints after a resolved coordinate
+const MAVEN_SCOPES: &[&str] = &["compile", "provided", "runtime", "test", "system", "import"];
+
+/// Characters drawing the `mvn dependency:tree` hierarchy
+const TREE_CHARS: &[char] = &['|', '+', '\\', '-', ' '];
+
+/// Width of one nesting level in `mvn dependency:tree` output
+const TREE_INDENT: usize = 3;
+
+/// Format of a file listing resolved (rather than declared) dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolvedFormat {
+    /// `gradle.lockfile` or `<configuration>.lockfile`
+    GradleLockfile,
+    /// Text output of `mvn dependency:list`
+    DependencyList,
+    /// Text output of `mvn dependency:tree`
+    DependencyTree,
+}
+
+impl ResolvedFormat {
+    /// Detect the format from the file name, falling back to the content
+    pub fn detect(path: Option<&str>, content: &str) -> Self {
+        if path.is_some_and(|p| p.ends_with(".lockfile")) {
+            return ResolvedFormat::GradleLockfile;
+        }
+        let lines = content.lines().map(strip_log_level);
+        let mut lockfile = false;
+        for line in lines {
+            if line.starts_with("+- ") || line.starts_with("\\- ") {
+                return ResolvedFormat::DependencyTree;
+            }
+            if line.starts_with("# This is a Gradle generated file") {
+                lockfile = true;
+            }
+        }
+        if lockfile {
+            ResolvedFormat::GradleLockfile
+        } else {
+            ResolvedFormat::DependencyList
+        }
+    }
+}
+
+/// A dependency at the version it was actually resolved to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Gradle configurations the version is locked for
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub configurations: Vec<String>,
+    /// groupId:artifactId:version of the direct dependency this one is
+    /// pulled in through, `None` for direct dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_by: Option<String>,
+    /// 1-based line in the input
+    pub line: usize,
+}
+
+impl ResolvedDependency {
+    /// groupId:artifactId:version
+    pub fn to_coordinate(&self) -> String {
+        format!("{}:{}:{}", self.group_id, self.artifact_id, self.version)
+    }
+}
+
+/// Parse resolved dependencies in the given format
+pub fn parse_resolved(format: ResolvedFormat, content: &str) -> Vec<ResolvedDependency> {
+    match format {
+        ResolvedFormat::GradleLockfile => parse_gradle_lockfile(content),
+        ResolvedFormat::DependencyList => parse_dependency_list(content),
+        ResolvedFormat::DependencyTree => parse_dependency_tree(content),
+    }
+}
+
+/// `group:artifact:version=configuration,configuration` lines of a Gradle lockfile
+pub fn parse_gradle_lockfile(content: &str) -> Vec<ResolvedDependency> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.trim();
+            if line.starts_with('#') {
+                return None;
+            }
+            let (coordinate, configurations) = line.split_once('=')?;
+            let mut parts = coordinate.split(':');
+            let (Some(group_id), Some(artifact_id), Some(version), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                // Also skips the `empty=...` entry
+                return None;
+            };
+            Some(ResolvedDependency {
+                group_id: group_id.to_string(),
+                artifact_id: artifact_id.to_string(),
+                version: version.to_string(),
+                scope: None,
+                configurations: configurations
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .map(String::from)
+                    .collect(),
+                introduced_by: None,
+                line: index + 1,
+            })
+        })
+        .collect()
+}
+
+/// Coordinates printed by `mvn dependency:list`
+pub fn parse_dependency_list(content: &str) -> Vec<ResolvedDependency> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let token = strip_log_level(line).split_whitespace().next()?;
+            parse_maven_output(token, index + 1)
+        })
+        .collect()
+}
+
+/// Coordinates printed by `mvn dependency:tree`, keeping the direct
+/// dependency each transitive one comes from. Entries omitted by the verbose tree are skipped.
+pub fn parse_dependency_tree(content: &str) -> Vec<ResolvedDependency> {
+    let mut resolved = Vec::new();
+    // Coordinates of the current path from a root to the previous entry
+    let mut ancestors: Vec<String> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = strip_log_level(line);
+        let entry = line.trim_start_matches(TREE_CHARS);
+        let indent = line.len() - entry.len();
+        let Some(token) = entry.split_whitespace().next() else {
+            continue;
+        };
+
+        if indent == 0 {
+            // A module root like `com.acme:app:jar:1.0`
+            if token.split(':').count() >= 4 && !token.starts_with('[') {
+                ancestors = vec![token.to_string()];
+            }
+            continue;
+        }
+        if ancestors.is_empty() || token.starts_with('(') {
+            continue;
+        }
+        let Some(mut dependency) = parse_maven_output(token, index + 1) else {
+            continue;
+        };
+
+        let depth = indent / TREE_INDENT;
+        ancestors.truncate(depth);
+        if depth > 1 {
+            dependency.introduced_by = ancestors.get(1).map(|a| maven_coordinate(a));
+        }
+        ancestors.push(token.to_string());
+        resolved.push(dependency);
+    }
+
+    resolved
+}
+
+/// Drop the `[INFO] ` prefix Maven puts in front of plugin output
+fn strip_log_level(line: &str) -> &str {
+    let line = line.trim_end();
+    match line.strip_prefix('[') {
+        Some(rest) => match rest.split_once("] ") {
+            Some((level, rest)) if level.chars().all(|c| c.is_ascii_uppercase()) => rest,
+            _ => line,
+        },
+        None => line,
+    }
+}
+
+/// `group:artifact:type[:classifier]:version:scope`
+fn parse_maven_output(token: &str, line: usize) -> Option<ResolvedDependency> {
+    let parts: Vec<&str> = token.split(':').collect();
+    let (group_id, artifact_id, version, scope) = match parts.as_slice() {
+        [g, a, _, v, s] | [g, a, _, _, v, s] if MAVEN_SCOPES.contains(s) => (g, a, v, s),
+        _ => return None,
+    };
+    Some(ResolvedDependency {
+        group_id: group_id.to_string(),
+        artifact_id: artifact_id.to_string(),
+        version: version.to_string(),
+        scope: Some(scope.to_string()),
+        configurations: Vec::new(),
+        introduced_by: None,
+        line,
+    })
+}
+
+/// groupId:artifactId:version of a `group:artifact:type[:classifier]:version[:scope]` token
+fn maven_coordinate(token: &str) -> String {
+    let parts: Vec<&str> = token.split(':').collect();
+    let version = match parts.as_slice() {
+        [_, _, _, v] | [_, _, _, v, _] => v,
+        [_, _, _, _, v, _] => v,
+        _ => return token.to_string(),
+    };
+    format!("{}:{}:{}", parts[0], parts[1], version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradle_lockfile() {
+        let content = "# This is a Gradle generated file for dependency locking.\n\
+                       # Manual edits can break the build and are not advised.\n\
+                       # This file is expected to be part of source control.\n\
+                       com.google.guava:guava:32.1.2-jre=compileClasspath,runtimeClasspath\n\
+                       org.slf4j:slf4j-api:2.0.9=runtimeClasspath\n\
+                       empty=annotationProcessor\n";
+        assert_eq!(
+            ResolvedFormat::detect(None, content),
+            ResolvedFormat::GradleLockfile
+        );
+
+        let resolved = parse_gradle_lockfile(content);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(
+            resolved[0].to_coordinate(),
+            "com.google.guava:guava:32.1.2-jre"
+        );
+        assert_eq!(
+            resolved[0].configurations,
+            vec!["compileClasspath", "runtimeClasspath"]
+        );
+        assert_eq!(resolved[1].line, 5);
+    }
+
+    #[test]
+    fn test_dependency_list() {
+        let content = "[INFO] --- maven-dependency-plugin:3.6.0:list (default-cli) @ app ---\n\
+                       [INFO]\n\
+                       [INFO] The following files have been resolved:\n\
+                       [INFO]    org.slf4j:slf4j-api:jar:2.0.9:compile -- module org.slf4j [auto]\n\
+                       [INFO]    io.netty:netty-transport-native-epoll:jar:linux-x86_64:4.1.100.Final:runtime\n\
+                       [INFO]    junit:junit:jar:4.13.2:test (optional)\n";
+        assert_eq!(
+            ResolvedFormat::detect(None, content),
+            ResolvedFormat::DependencyList
+        );
+
+        let resolved = parse_dependency_list(content);
+        let coordinates: Vec<String> = resolved.iter().map(|d| d.to_coordinate()).collect();
+        assert_eq!(
+            coordinates,
+            vec![
+                "org.slf4j:slf4j-api:2.0.9",
+                "io.netty:netty-transport-native-epoll:4.1.100.Final",
+                "junit:junit:4.13.2"
+            ]
+        );
+        assert_eq!(resolved[2].scope.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_dependency_tree() {
+        let content = "[INFO] com.acme:app:jar:1.0\n\
+                       [INFO] +- org.springframework:spring-context:jar:6.0.0:compile\n\
+                       [INFO] |  +- org.springframework:spring-core:jar:6.0.0:compile\n\
+                       [INFO] |  |  \\- org.springframework:spring-jcl:jar:6.0.0:compile\n\
+                       [INFO] |  \\- (org.springframework:spring-beans:jar:6.0.0:compile - omitted for duplicate)\n\
+                       [INFO] \\- junit:junit:jar:4.13.2:test\n";
+        assert_eq!(
+            ResolvedFormat::detect(None, content),
+            ResolvedFormat::DependencyTree
+        );
+
+        let resolved = parse_dependency_tree(content);
+        assert_eq!(resolved.len(), 4);
+        assert_eq!(resolved[0].introduced_by, None);
+        assert_eq!(
+            resolved[1].introduced_by.as_deref(),
+            Some("org.springframework:spring-context:6.0.0")
+        );
+        assert_eq!(
+            resolved[2].introduced_by.as_deref(),
+            Some("org.springframework:spring-context:6.0.0")
+        );
+        assert_eq!(resolved[3].artifact_id, "junit");
+        assert_eq!(resolved[3].introduced_by, None);
+    }
+}
@@ -0,0 +1,577 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::build::{LineIndex, ResolvedDependency};
+
+#[derive(Error, Debug)]
+pub enum SbomError {
+    #[error("Invalid CycloneDX JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid CycloneDX XML at line {line}: {message}")]
+    Xml { line: usize, message: String },
+    #[error("Not a CycloneDX SBOM")]
+    NotCycloneDx,
+}
+
+/// A package URL split into the parts used here
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageUrl {
+    pub package_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl PackageUrl {
+    /// Parse `pkg:type/namespace/name@version?qualifiers#subpath`
+    pub fn parse(purl: &str) -> Option<Self> {
+        let rest = purl.trim().strip_prefix("pkg:")?;
+        let rest = rest.split(['?', '#']).next()?;
+        let (rest, version) = match rest.rsplit_once('@') {
+            Some((rest, version)) => (rest, Some(percent_decode(version))),
+            None => (rest, None),
+        };
+        let mut segments = rest.trim_matches('/').split('/');
+        let package_type = segments.next()?.to_ascii_lowercase();
+        let segments: Vec<String> = segments.map(percent_decode).collect();
+        let (name, namespace) = segments.split_last()?;
+        if name.is_empty() {
+            return None;
+        }
+        Some(PackageUrl {
+            package_type,
+            namespace: (!namespace.is_empty()).then(|| namespace.join("/")),
+            name: name.clone(),
+            version,
+        })
+    }
+}
+
+/// A component as listed in the SBOM, before it is mapped to Maven
+#[derive(Debug, Clone, Default)]
+struct Component {
+    bom_ref: Option<String>,
+    group: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    purl: Option<String>,
+    scope: Option<String>,
+}
+
+impl Component {
+    fn display(&self) -> String {
+        if let Some(purl) = &self.purl {
+            return purl.clone();
+        }
+        let name = [self.group.as_deref(), self.name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(":");
+        match &self.version {
+            Some(version) => format!("{}@{}", name, version),
+            None => name,
+        }
+    }
+}
+
+/// The parts of a CycloneDX document shared by its JSON and XML forms
+#[derive(Debug, Default)]
+struct BomDocument {
+    spec_version: Option<String>,
+    root: Option<String>,
+    components: Vec<Component>,
+    /// bom-ref to the bom-refs it depends on, in document order
+    graph: Vec<(String, Vec<String>)>,
+}
+
+/// Maven components of a CycloneDX SBOM, keeping the dependency graph
+#[derive(Debug, Clone, Default)]
+pub struct Sbom {
+    pub spec_version: Option<String>,
+    /// Maven components; `introduced_by` names the direct dependency each
+    /// transitive component is reached through
+    pub dependencies: Vec<ResolvedDependency>,
+    /// Components that cannot be checked, with the reason
+    pub skipped: Vec<String>,
+}
+
+impl Sbom {
+    /// Parse a CycloneDX SBOM in JSON or XML form
+    pub fn parse(content: &str) -> Result<Self, SbomError> {
+        let document = if content.trim_start().starts_with('<') {
+            read_xml(content)?
+        } else {
+            read_json(content)?
+        };
+        Ok(Self::from_document(document, content))
+    }
+
+    fn from_document(document: BomDocument, content: &str) -> Self {
+        let lines = LineIndex::new(content);
+        let line_of = |component: &Component| {
+            component
+                .purl
+                .as_deref()
+                .or(component.bom_ref.as_deref())
+                .and_then(|needle| content.find(needle))
+                .map(|offset| lines.line_at(offset))
+                .unwrap_or(0)
+        };
+
+        let by_ref: HashMap<&str, &Component> = document
+            .components
+            .iter()
+            .filter_map(|c| Some((c.bom_ref.as_deref()?, c)))
+            .collect();
+        let introduced_by: HashMap<String, String> = direct_dependencies(&document)
+            .map(|direct| {
+                reached_through(&document, &direct)
+                    .into_iter()
+                    .filter_map(|(reached, through)| {
+                        let through = by_ref.get(through.as_str())?;
+                        Some((
+                            reached,
+                            maven_coordinate(through).unwrap_or_else(|| through.display()),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut sbom = Sbom {
+            spec_version: document.spec_version.clone(),
+            ..Default::default()
+        };
+        for component in &document.components {
+            if component.scope.as_deref() == Some("excluded") {
+                sbom.skipped
+                    .push(format!("{}: excluded from the build", component.display()));
+                continue;
+            }
+            let Some(purl) = &component.purl else {
+                sbom.skipped
+                    .push(format!("{}: no purl", component.display()));
+                continue;
+            };
+            let Some(parsed) = PackageUrl::parse(purl) else {
+                sbom.skipped.push(format!("{}: invalid purl", purl));
+                continue;
+            };
+            if parsed.package_type != "maven" {
+                sbom.skipped.push(format!(
+                    "{}: not a Maven package ({})",
+                    purl, parsed.package_type
+                ));
+                continue;
+            }
+            let (Some(group_id), Some(version)) = (parsed.namespace, parsed.version) else {
+                sbom.skipped
+                    .push(format!("{}: groupId or version missing", purl));
+                continue;
+            };
+            sbom.dependencies.push(ResolvedDependency {
+                group_id,
+                artifact_id: parsed.name,
+                version,
+                scope: component.scope.clone(),
+                configurations: Vec::new(),
+                introduced_by: component
+                    .bom_ref
+                    .as_ref()
+                    .and_then(|r| introduced_by.get(r).cloned()),
+                line: line_of(component),
+            });
+        }
+        sbom
+    }
+}
+
+/// bom-refs of the direct dependencies: those of the described component,
+/// or else every component nothing depends on. `None` without a graph.
+fn direct_dependencies(document: &BomDocument) -> Option<Vec<String>> {
+    if document.graph.is_empty() {
+        return None;
+    }
+    let root = document
+        .root
+        .as_ref()
+        .and_then(|root| document.graph.iter().find(|(r, _)| r == root));
+    if let Some((_, direct)) = root {
+        return Some(direct.clone());
+    }
+    let depended_on: HashSet<&String> = document.graph.iter().flat_map(|(_, d)| d).collect();
+    Some(
+        document
+            .components
+            .iter()
+            .filter_map(|c| c.bom_ref.clone())
+            .filter(|r| !depended_on.contains(r))
+            .collect(),
+    )
+}
+
+/// For every component only reachable through a direct dependency, the
+/// first direct dependency (in document order) it is reached through
+fn reached_through(document: &BomDocument, direct: &[String]) -> HashMap<String, String> {
+    let edges: HashMap<&str, &Vec<String>> = document
+        .graph
+        .iter()
+        .map(|(r, d)| (r.as_str(), d))
+        .collect();
+    let direct_set: HashSet<&String> = direct.iter().collect();
+    let mut through = HashMap::new();
+    for start in direct {
+        let mut queue = VecDeque::from([start.as_str()]);
+        let mut visited = HashSet::from([start.as_str()]);
+        while let Some(current) = queue.pop_front() {
+            for next in edges.get(current).into_iter().copied().flatten() {
+                if !visited.insert(next.as_str()) {
+                    continue;
+                }
+                if !direct_set.contains(next) {
+                    through.entry(next.clone()).or_insert_with(|| start.clone());
+                }
+                queue.push_back(next);
+            }
+        }
+    }
+    through
+}
+
+/// groupId:artifactId:version of a component with a Maven purl
+fn maven_coordinate(component: &Component) -> Option<String> {
+    let purl = PackageUrl::parse(component.purl.as_deref()?)?;
+    if purl.package_type != "maven" {
+        return None;
+    }
+    Some(format!(
+        "{}:{}:{}",
+        purl.namespace?, purl.name, purl.version?
+    ))
+}
+
+fn read_json(content: &str) -> Result<BomDocument, SbomError> {
+    let bom: Value = serde_json::from_str(content)?;
+    if bom.get("bomFormat").and_then(Value::as_str) != Some("CycloneDX") {
+        return Err(SbomError::NotCycloneDx);
+    }
+    let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+
+    let mut document = BomDocument {
+        spec_version: text(&bom, "specVersion"),
+        root: bom
+            .pointer("/metadata/component")
+            .and_then(|c| text(c, "bom-ref")),
+        ..Default::default()
+    };
+
+    // Components may nest other components
+    let mut pending: Vec<&Value> = bom
+        .get("components")
+        .and_then(Value::as_array)
+        .map(|c| c.iter().rev().collect())
+        .unwrap_or_default();
+    while let Some(value) = pending.pop() {
+        document.components.push(Component {
+            bom_ref: text(value, "bom-ref"),
+            group: text(value, "group"),
+            name: text(value, "name"),
+            version: text(value, "version"),
+            purl: text(value, "purl"),
+            scope: text(value, "scope"),
+        });
+        if let Some(nested) = value.get("components").and_then(Value::as_array) {
+            pending.extend(nested.iter().rev());
+        }
+    }
+
+    for dependency in bom
+        .get("dependencies")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(from) = text(dependency, "ref") else {
+            continue;
+        };
+        let depends_on = dependency
+            .get("dependsOn")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.as_str().map(String::from))
+            .collect();
+        document.graph.push((from, depends_on));
+    }
+
+    Ok(document)
+}
+
+fn read_xml(content: &str) -> Result<BomDocument, SbomError> {
+    let lines = LineIndex::new(content);
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut document = BomDocument::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    // Components being read; nested components stack up
+    let mut open: Vec<(bool, Component)> = Vec::new();
+    let mut seen_bom = false;
+
+    loop {
+        let event = reader.read_event().map_err(|e| SbomError::Xml {
+            line: lines.line_at(reader.error_position() as usize),
+            message: e.to_string(),
+        })?;
+
+        match event {
+            Event::Start(e) => {
+                path.push(element_name(&e));
+                text.clear();
+                start_element(&e, &path, &mut document, &mut open, &mut seen_bom);
+            }
+            Event::Empty(e) => {
+                path.push(element_name(&e));
+                start_element(&e, &path, &mut document, &mut open, &mut seen_bom);
+                end_element(&path, "", &mut document, &mut open);
+                path.pop();
+            }
+            Event::Text(e) => {
+                let unescaped = e.unescape().map_err(|e| SbomError::Xml {
+                    line: lines.line_at(reader.buffer_position() as usize),
+                    message: e.to_string(),
+                })?;
+                text.push_str(&unescaped);
+            }
+            Event::End(_) => {
+                end_element(&path, text.trim(), &mut document, &mut open);
+                path.pop();
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !seen_bom {
+        return Err(SbomError::NotCycloneDx);
+    }
+    Ok(document)
+}
+
+fn start_element(
+    e: &BytesStart<'_>,
+    path: &[String],
+    document: &mut BomDocument,
+    open: &mut Vec<(bool, Component)>,
+    seen_bom: &mut bool,
+) {
+    let attribute = |name: &str| {
+        e.attributes()
+            .flatten()
+            .find(|a| a.key.local_name().as_ref() == name.as_bytes())
+            .and_then(|a| a.unescape_value().ok())
+            .map(|v| v.into_owned())
+    };
+    let names: Vec<&str> = path.iter().map(String::as_str).collect();
+    match names.as_slice() {
+        ["bom"] => {
+            *seen_bom = true;
+            // The namespace ends in the spec version, e.g. .../bom/1.5
+            document.spec_version = e
+                .attributes()
+                .flatten()
+                .find(|a| a.key.as_ref() == b"xmlns")
+                .and_then(|a| a.unescape_value().ok())
+                .and_then(|ns| ns.rsplit('/').next().map(String::from));
+        }
+        [.., "component"] => {
+            let is_root = names.as_slice() == ["bom", "metadata", "component"];
+            open.push((
+                is_root,
+                Component {
+                    bom_ref: attribute("bom-ref"),
+                    ..Default::default()
+                },
+            ));
+        }
+        ["bom", "dependencies", "dependency"] => {
+            if let Some(from) = attribute("ref") {
+                document.graph.push((from, Vec::new()));
+            }
+        }
+        ["bom", "dependencies", "dependency", "dependency"] => {
+            if let (Some(to), Some((_, depends_on))) = (attribute("ref"), document.graph.last_mut())
+            {
+                depends_on.push(to);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn end_element(
+    path: &[String],
+    text: &str,
+    document: &mut BomDocument,
+    open: &mut Vec<(bool, Component)>,
+) {
+    let names: Vec<&str> = path.iter().map(String::as_str).collect();
+    match names.as_slice() {
+        [.., "component"] => {
+            if let Some((is_root, component)) = open.pop() {
+                if is_root {
+                    document.root = component.bom_ref;
+                } else {
+                    document.components.push(component);
+                }
+            }
+        }
+        [.., "component", field] => {
+            if let Some((_, component)) = open.last_mut() {
+                let value = Some(text.to_string());
+                match *field {
+                    "group" => component.group = value,
+                    "name" => component.name = value,
+                    "version" => component.version = value,
+                    "purl" => component.purl = value,
+                    "scope" => component.scope = value,
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn element_name(e: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_purl() {
+        assert_eq!(
+            PackageUrl::parse("pkg:maven/org.apache.commons/commons-lang3@3.12.0?type=jar"),
+            Some(PackageUrl {
+                package_type: "maven".to_string(),
+                namespace: Some("org.apache.commons".to_string()),
+                name: "commons-lang3".to_string(),
+                version: Some("3.12.0".to_string()),
+            })
+        );
+        let npm = PackageUrl::parse("pkg:npm/%40angular/core@16.0.0").unwrap();
+        assert_eq!(npm.namespace.as_deref(), Some("@angular"));
+        assert_eq!(PackageUrl::parse("maven/g/a@1"), None);
+    }
+
+    #[test]
+    fn test_json_bom() {
+        let bom = r#"{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "metadata": { "component": { "bom-ref": "app", "name": "app" } },
+  "components": [
+    { "bom-ref": "spring", "group": "org.springframework", "name": "spring-context", "version": "6.0.0",
+      "purl": "pkg:maven/org.springframework/spring-context@6.0.0?type=jar" },
+    { "bom-ref": "jcl", "purl": "pkg:maven/org.springframework/spring-jcl@6.0.0?type=jar" },
+    { "bom-ref": "left-pad", "name": "left-pad", "purl": "pkg:npm/left-pad@1.3.0" },
+    { "bom-ref": "junit", "purl": "pkg:maven/junit/junit@4.13.2", "scope": "excluded" }
+  ],
+  "dependencies": [
+    { "ref": "app", "dependsOn": ["spring", "left-pad"] },
+    { "ref": "spring", "dependsOn": ["jcl"] }
+  ]
+}"#;
+        let sbom = Sbom::parse(bom).unwrap();
+        assert_eq!(sbom.spec_version.as_deref(), Some("1.5"));
+        let coordinates: Vec<String> = sbom
+            .dependencies
+            .iter()
+            .map(|d| d.to_coordinate())
+            .collect();
+        assert_eq!(
+            coordinates,
+            vec![
+                "org.springframework:spring-context:6.0.0",
+                "org.springframework:spring-jcl:6.0.0"
+            ]
+        );
+        assert_eq!(sbom.dependencies[0].introduced_by, None);
+        assert_eq!(
+            sbom.dependencies[1].introduced_by.as_deref(),
+            Some("org.springframework:spring-context:6.0.0")
+        );
+        assert_eq!(sbom.dependencies[1].line, 8);
+        assert_eq!(sbom.skipped.len(), 2);
+        assert!(sbom.skipped[0].contains("not a Maven package (npm)"));
+    }
+
+    #[test]
+    fn test_xml_bom() {
+        let bom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1">
+  <metadata>
+    <component type="application" bom-ref="app"><name>app</name></component>
+  </metadata>
+  <components>
+    <component type="library" bom-ref="guava">
+      <group>com.google.guava</group>
+      <name>guava</name>
+      <version>32.1.2-jre</version>
+      <purl>pkg:maven/com.google.guava/guava@32.1.2-jre?type=jar</purl>
+    </component>
+    <component type="library" bom-ref="failureaccess">
+      <purl>pkg:maven/com.google.guava/failureaccess@1.0.1?type=jar</purl>
+    </component>
+  </components>
+  <dependencies>
+    <dependency ref="app"><dependency ref="guava"/></dependency>
+    <dependency ref="guava"><dependency ref="failureaccess"/></dependency>
+  </dependencies>
+</bom>"#;
+        let sbom = Sbom::parse(bom).unwrap();
+        assert_eq!(sbom.spec_version.as_deref(), Some("1.4"));
+        assert_eq!(sbom.dependencies.len(), 2);
+        assert_eq!(
+            sbom.dependencies[1].introduced_by.as_deref(),
+            Some("com.google.guava:guava:32.1.2-jre")
+        );
+        assert!(matches!(
+            Sbom::parse("<project/>"),
+            Err(SbomError::NotCycloneDx)
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::build::DeclaredDependency;
+
+/// Location of the Maven Wrapper configuration in a project
+pub const MAVEN_WRAPPER_PROPERTIES: &str = ".mvn/wrapper/maven-wrapper.properties";
+/// Location of the Gradle Wrapper configuration in a project
+pub const GRADLE_WRAPPER_PROPERTIES: &str = "gradle/wrapper/gradle-wrapper.properties";
+
+/// groupIds of artifacts commonly referenced from wrapper URLs, used when the
+/// repository root cannot be told from the URL
+const KNOWN_GROUPS: &[(&str, &str)] = &[
+    ("apache-maven", "org.apache.maven"),
+    ("maven-wrapper", "org.apache.maven.wrapper"),
+    ("maven-wrapper-distribution", "org.apache.maven.wrapper"),
+];
+
+/// Build tool a wrapper belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WrapperKind {
+    Maven,
+    Gradle,
+}
+
+impl WrapperKind {
+    /// Detect the wrapper from a properties file name
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        match name {
+            "maven-wrapper.properties" => Some(WrapperKind::Maven),
+            "gradle-wrapper.properties" => Some(WrapperKind::Gradle),
+            _ => None,
+        }
+    }
+
+    /// Where the wrapper's properties file lives relative to the project root
+    pub fn default_location(self) -> &'static str {
+        match self {
+            WrapperKind::Maven => MAVEN_WRAPPER_PROPERTIES,
+            WrapperKind::Gradle => GRADLE_WRAPPER_PROPERTIES,
+        }
+    }
+}
+
+/// Versions pinned by a `maven-wrapper.properties` or `gradle-wrapper.properties`
+#[derive(Debug, Clone)]
+pub struct WrapperProperties {
+    pub kind: WrapperKind,
+    pub distribution_url: Option<String>,
+    /// The distribution and, for Maven, the wrapper itself. Gradle
+    /// distributions are reported as `org.gradle:gradle`.
+    pub components: Vec<DeclaredDependency>,
+}
+
+impl WrapperProperties {
+    pub fn parse(kind: WrapperKind, content: &str) -> Self {
+        let entries = parse_entries(content);
+        let get = |key: &str| entries.iter().find(|(k, _, _)| k == key);
+
+        let mut components = Vec::new();
+        let distribution_url = get("distributionUrl").map(|(_, v, _)| v.clone());
+
+        match kind {
+            WrapperKind::Maven => {
+                for key in ["distributionUrl", "wrapperUrl"] {
+                    if let Some((_, url, line)) = get(key) {
+                        components.push(from_maven_url(key, url, *line));
+                    }
+                }
+                // Wrapper 3.3+ records its own version instead of a wrapperUrl
+                if let Some((_, version, line)) = get("wrapperVersion") {
+                    if !components.iter().any(|c| c.section == "wrapperUrl") {
+                        components.push(component(
+                            "org.apache.maven.wrapper",
+                            "maven-wrapper",
+                            "wrapperVersion",
+                            Some(version.clone()),
+                            *line,
+                        ));
+                    }
+                }
+            }
+            WrapperKind::Gradle => {
+                if let Some((_, url, line)) = get("distributionUrl") {
+                    let version = gradle_distribution_version(url);
+                    let mut gradle =
+                        component("org.gradle", "gradle", "distributionUrl", version, *line);
+                    if gradle.resolved_version.is_none() {
+                        gradle.unresolved_reason =
+                            Some(format!("Not a Gradle distribution URL: {}", url));
+                    }
+                    components.push(gradle);
+                }
+            }
+        }
+
+        WrapperProperties {
+            kind,
+            distribution_url,
+            components,
+        }
+    }
+}
+
+/// Parse `key=value` lines, keeping line numbers and undoing `\:` escapes
+fn parse_entries(content: &str) -> Vec<(String, String, usize)> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line_text = line.trim();
+            if line_text.is_empty() || line_text.starts_with('#') || line_text.starts_with('!') {
+                return None;
+            }
+            let split = line_text.find(['=', ':'])?;
+            let key = line_text[..split].trim().to_string();
+            let value = line_text[split + 1..]
+                .trim()
+                .replace("\\:", ":")
+                .replace("\\=", "=");
+            Some((key, value, index + 1))
+        })
+        .collect()
+}
+
+/// Coordinate of an artifact URL in Maven repository layout, e.g.
+/// `.../org/apache/maven/apache-maven/3.9.6/apache-maven-3.9.6-bin.zip`
+fn from_maven_url(key: &str, url: &str, line: usize) -> DeclaredDependency {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let parsed = segments.len().checked_sub(3).and_then(|artifact_index| {
+        let artifact = segments[artifact_index];
+        let version = segments[artifact_index + 1];
+        let file = segments[artifact_index + 2];
+        if !file.starts_with(&format!("{}-{}", artifact, version)) {
+            return None;
+        }
+        let group = KNOWN_GROUPS
+            .iter()
+            .find(|(a, _)| *a == artifact)
+            .map(|(_, g)| g.to_string())
+            .or_else(|| {
+                let root = segments.iter().position(|s| *s == "maven2")?;
+                (root + 1 < artifact_index).then(|| segments[root + 1..artifact_index].join("."))
+            })?;
+        Some((group, artifact, version))
+    });
+
+    match parsed {
+        Some((group, artifact, version)) => {
+            component(&group, artifact, key, Some(version.to_string()), line)
+        }
+        None => {
+            let mut unknown = component("", "", key, None, line);
+            unknown.unresolved_reason =
+                Some(format!("Not a Maven repository artifact URL: {}", url));
+            unknown
+        }
+    }
+}
+
+/// Version of a `gradle-<version>-bin.zip` or `gradle-<version>-all.zip` URL
+fn gradle_distribution_version(url: &str) -> Option<String> {
+    let file = url.rsplit('/').next()?;
+    let name = file.strip_prefix("gradle-")?.strip_suffix(".zip")?;
+    let version = name
+        .strip_suffix("-bin")
+        .or_else(|| name.strip_suffix("-all"))?;
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+fn component(
+    group_id: &str,
+    artifact_id: &str,
+    section: &str,
+    version: Option<String>,
+    line: usize,
+) -> DeclaredDependency {
+    DeclaredDependency {
+        group_id: group_id.to_string(),
+        artifact_id: artifact_id.to_string(),
+        section: section.to_string(),
+        scope: None,
+        declared_version: version.clone(),
+        version_property: None,
+        resolved_version: version,
+        repository: None,
+        line,
+        unresolved_reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maven_wrapper() {
+        let wrapper = WrapperProperties::parse(
+            WrapperKind::Maven,
+            "# Licensed to the Apache Software Foundation\n\
+             distributionUrl=https://repo.maven.apache.org/maven2/org/apache/maven/apache-maven/3.8.6/apache-maven-3.8.6-bin.zip\n\
+             wrapperUrl=https://repo.maven.apache.org/maven2/org/apache/maven/wrapper/maven-wrapper/3.1.1/maven-wrapper-3.1.1.jar\n",
+        );
+        let coordinates: Vec<String> = wrapper
+            .components
+            .iter()
+            .filter_map(DeclaredDependency::checkable_coordinate)
+            .collect();
+        assert_eq!(
+            coordinates,
+            vec![
+                "org.apache.maven:apache-maven:3.8.6",
+                "org.apache.maven.wrapper:maven-wrapper:3.1.1"
+            ]
+        );
+        assert_eq!(wrapper.components[0].line, 2);
+    }
+
+    #[test]
+    fn test_maven_wrapper_version_and_mirror() {
+        let wrapper = WrapperProperties::parse(
+            WrapperKind::Maven,
+            "wrapperVersion=3.3.2\n\
+             distributionType=only-script\n\
+             distributionUrl=https://nexus.example.com/repository/central/org/apache/maven/apache-maven/3.9.6/apache-maven-3.9.6-bin.zip\n",
+        );
+        assert_eq!(
+            wrapper.components[0].checkable_coordinate().as_deref(),
+            Some("org.apache.maven:apache-maven:3.9.6")
+        );
+        assert_eq!(
+            wrapper.components[1].checkable_coordinate().as_deref(),
+            Some("org.apache.maven.wrapper:maven-wrapper:3.3.2")
+        );
+    }
+
+    #[test]
+    fn test_gradle_wrapper() {
+        let wrapper = WrapperProperties::parse(
+            WrapperKind::Gradle,
+            "distributionBase=GRADLE_USER_HOME\n\
+             distributionUrl=https\\://services.gradle.org/distributions/gradle-8.5-rc-1-all.zip\n",
+        );
+        assert_eq!(
+            wrapper.distribution_url.as_deref(),
+            Some("https://services.gradle.org/distributions/gradle-8.5-rc-1-all.zip")
+        );
+        assert_eq!(
+            wrapper.components[0].checkable_coordinate().as_deref(),
+            Some("org.gradle:gradle:8.5-rc-1")
+        );
+    }
+}
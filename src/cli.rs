@@ -0,0 +1,586 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::de::DeserializeOwned;
+
+use crate::config::TransportMode;
+use crate::models::{UpdatePolicy, UpdateType};
+use crate::report::ReportFormat;
+use crate::tools::responses::*;
+use crate::tools::service::{
+    AnalyzePomParams, AnalyzeProjectHealthParams, CheckMultipleDependenciesParams,
+    GetLatestVersionParams,
+};
+use crate::tools::MavenToolsService;
+
+/// Exit code when a CI gate such as `--min-grade` is violated
+const EXIT_GATE_FAILED: u8 = 1;
+
+/// Exit code when the analysis could not be run
+pub const EXIT_ERROR: u8 = 2;
+
+/// MCP server for Maven Central. Without a subcommand it serves MCP over
+/// stdio or HTTP; the subcommands run the same checks from the command line.
+#[derive(Debug, Parser)]
+#[command(name = "maven-mcp", version)]
+pub struct Cli {
+    /// Server config file [env: MAVEN_MCP_CONFIG]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// How MCP clients connect when no subcommand is given [default: the
+    /// configured mode, else stdio]
+    #[arg(long, value_enum)]
+    pub transport: Option<TransportMode>,
+
+    /// Address the HTTP transport listens on [default: the configured
+    /// address, else 127.0.0.1:8080]
+    #[arg(long, value_name = "ADDR")]
+    pub bind: Option<SocketAddr>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Show the latest versions of a dependency
+    Latest {
+        /// Coordinate like org.springframework:spring-core
+        coordinate: String,
+        /// Prefer pre-release versions over stable ones
+        #[arg(long)]
+        include_prereleases: bool,
+        /// Print the JSON response instead of a summary
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check dependencies for available updates
+    Check(CheckArgs),
+    /// Grade the health of dependencies
+    Health(HealthArgs),
+    /// Check and grade the declarations of a pom.xml
+    AnalyzePom(AnalyzePomArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Coordinates like com.google.guava:guava:32.1.2-jre
+    pub dependencies: Vec<String>,
+    /// CycloneDX SBOM to take Maven components from
+    #[arg(long)]
+    pub sbom: Option<String>,
+    /// Which line decides whether a dependency is outdated: patch, minor or
+    /// major [default: the configured policy]
+    #[arg(long, value_parser = parse_policy)]
+    pub policy: Option<UpdatePolicy>,
+    /// Also suggest pre-release versions
+    #[arg(long)]
+    pub include_prereleases: bool,
+    /// Fail when any dependency is a major version behind
+    #[arg(long)]
+    pub no_major_behind: bool,
+    /// Print the JSON response instead of a summary
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct HealthArgs {
+    /// Coordinates like com.google.guava:guava:32.1.2-jre
+    pub dependencies: Vec<String>,
+    /// Gradle lockfile or saved `mvn dependency:list` / `dependency:tree` output
+    #[arg(long)]
+    pub resolved: Option<String>,
+    /// CycloneDX SBOM to take Maven components from
+    #[arg(long)]
+    pub sbom: Option<String>,
+    #[command(flatten)]
+    pub gates: Gates,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct AnalyzePomArgs {
+    /// Path to the pom.xml
+    #[arg(default_value = "pom.xml")]
+    pub path: String,
+    /// Which line decides whether a dependency is outdated: patch, minor or
+    /// major [default: the configured policy]
+    #[arg(long, value_parser = parse_policy)]
+    pub policy: Option<UpdatePolicy>,
+    /// Also suggest pre-release versions
+    #[arg(long)]
+    pub include_prereleases: bool,
+    #[command(flatten)]
+    pub gates: Gates,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    pub format: OutputFormat,
+}
+
+/// Thresholds that make the command exit with a failure
+#[derive(Debug, Args)]
+pub struct Gates {
+    /// Fail when the health grade is worse than this grade (A-F)
+    #[arg(long, value_parser = parse_grade)]
+    pub min_grade: Option<HealthGrade>,
+    /// Fail when any dependency is a major version behind
+    #[arg(long)]
+    pub no_major_behind: bool,
+}
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// A short summary for the terminal
+    Human,
+    Json,
+    Markdown,
+    Html,
+    Sarif,
+}
+
+impl OutputFormat {
+    /// Format to request from the tool, `None` when the summary is
+    /// printed from the JSON response
+    fn report_format(self) -> Option<ReportFormat> {
+        match self {
+            OutputFormat::Human => None,
+            OutputFormat::Json => Some(ReportFormat::Json),
+            OutputFormat::Markdown => Some(ReportFormat::Markdown),
+            OutputFormat::Html => Some(ReportFormat::Html),
+            OutputFormat::Sarif => Some(ReportFormat::Sarif),
+        }
+    }
+}
+
+/// Run a subcommand, printing its output and returning the exit code
+pub async fn run(service: &MavenToolsService, command: Command) -> ExitCode {
+    match execute(service, command).await {
+        Ok(violations) if violations.is_empty() => ExitCode::SUCCESS,
+        Ok(violations) => {
+            for violation in violations {
+                eprintln!("gate failed: {}", violation);
+            }
+            ExitCode::from(EXIT_GATE_FAILED)
+        }
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Run a subcommand and return the gates it violated
+async fn execute(service: &MavenToolsService, command: Command) -> Result<Vec<String>> {
+    match command {
+        Command::Latest {
+            coordinate,
+            include_prereleases,
+            json,
+        } => {
+            let text = tool_text(
+                service
+                    .get_latest_version(Parameters(GetLatestVersionParams {
+                        dependency: coordinate,
+                        prefer_stable: !include_prereleases,
+                    }))
+                    .await,
+            )?;
+            if json {
+                println!("{}", text);
+            } else {
+                print_latest(&parse(&text)?);
+            }
+            Ok(Vec::new())
+        }
+        Command::Check(args) => {
+            let text = tool_text(
+                service
+                    .check_multiple_dependencies(Parameters(CheckMultipleDependenciesParams {
+                        dependencies: args.dependencies,
+                        sbom_path: args.sbom,
+                        sbom_content: None,
+                        stable_only: !args.include_prereleases,
+                        same_stream: true,
+                        update_policy: args.policy,
+                    }))
+                    .await,
+            )?;
+            let response: BulkCheckResponse = parse(&text)?;
+            if args.json {
+                println!("{}", text);
+            } else {
+                print_updates(&response);
+            }
+            let mut violations = Vec::new();
+            if args.no_major_behind {
+                violations.extend(
+                    response
+                        .dependencies
+                        .iter()
+                        .filter(|d| d.update_type == Some(UpdateType::Major))
+                        .map(|d| major_behind(&d.dependency, d.latest_version.as_deref())),
+                );
+            }
+            Ok(violations)
+        }
+        Command::Health(args) => {
+            let params = |output_format| AnalyzeProjectHealthParams {
+                dependencies: args.dependencies.clone(),
+                resolved_path: args.resolved.clone(),
+                resolved_content: None,
+                resolved_format: None,
+                sbom_path: args.sbom.clone(),
+                sbom_content: None,
+                output_format,
+            };
+            let text = tool_text(
+                service
+                    .analyze_project_health(Parameters(params(ReportFormat::Json)))
+                    .await,
+            )?;
+            let health: ProjectHealthResponse = parse(&text)?;
+            match args.format.report_format() {
+                None => print_health(&health),
+                Some(ReportFormat::Json) => println!("{}", text),
+                Some(format) => println!(
+                    "{}",
+                    tool_text(
+                        service
+                            .analyze_project_health(Parameters(params(format)))
+                            .await
+                    )?
+                ),
+            }
+            Ok(args.gates.violations(&health))
+        }
+        Command::AnalyzePom(args) => {
+            let params = |output_format| AnalyzePomParams {
+                path: Some(args.path.clone()),
+                content: None,
+                stable_only: !args.include_prereleases,
+                update_policy: args.policy,
+                output_format,
+            };
+            let text = tool_text(
+                service
+                    .analyze_pom(Parameters(params(ReportFormat::Json)))
+                    .await,
+            )?;
+            let response: PomAnalysisResponse = parse(&text)?;
+            match args.format.report_format() {
+                None => {
+                    println!(
+                        "{}{}",
+                        response.pom,
+                        response
+                            .project
+                            .as_ref()
+                            .map(|p| format!(" ({})", p))
+                            .unwrap_or_default()
+                    );
+                    if response.unresolved_count > 0 {
+                        println!(
+                            "{} declarations could not be resolved",
+                            response.unresolved_count
+                        );
+                    }
+                    println!();
+                    print_health(&response.health);
+                }
+                Some(ReportFormat::Json) => println!("{}", text),
+                Some(format) => println!(
+                    "{}",
+                    tool_text(service.analyze_pom(Parameters(params(format))).await)?
+                ),
+            }
+            Ok(args.gates.violations(&response.health))
+        }
+    }
+}
+
+impl Gates {
+    /// Descriptions of the thresholds the health results violate
+    fn violations(&self, health: &ProjectHealthResponse) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(min_grade) = self.min_grade {
+            if health.health_grade > min_grade {
+                violations.push(format!(
+                    "health grade {} ({:.1}) is worse than the required {}",
+                    health.health_grade, health.health_score, min_grade
+                ));
+            }
+        }
+        if self.no_major_behind {
+            violations.extend(
+                health
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.update_type == Some(UpdateType::Major))
+                    .map(|d| major_behind(&d.dependency, d.latest_version.as_deref())),
+            );
+        }
+        violations
+    }
+}
+
+fn major_behind(dependency: &str, latest: Option<&str>) -> String {
+    format!(
+        "{} is a major version behind {}",
+        dependency,
+        latest.unwrap_or("the latest release")
+    )
+}
+
+/// The text content of a tool result
+fn tool_text(result: Result<CallToolResult, McpError>) -> Result<String> {
+    let result = result.map_err(|e| anyhow!("{}", e.message))?;
+    result
+        .content
+        .first()
+        .and_then(|c| c.as_text())
+        .map(|t| t.text.clone())
+        .context("Tool returned no text content")
+}
+
+fn parse<T: DeserializeOwned>(text: &str) -> Result<T> {
+    serde_json::from_str(text).context("Unexpected tool response")
+}
+
+fn parse_policy(value: &str) -> Result<UpdatePolicy, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "patch" => Ok(UpdatePolicy::Patch),
+        "minor" => Ok(UpdatePolicy::Minor),
+        "major" => Ok(UpdatePolicy::Major),
+        _ => Err(format!("expected patch, minor or major, got '{}'", value)),
+    }
+}
+
+fn parse_grade(value: &str) -> Result<HealthGrade, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "A" => Ok(HealthGrade::A),
+        "B" => Ok(HealthGrade::B),
+        "C" => Ok(HealthGrade::C),
+        "D" => Ok(HealthGrade::D),
+        "F" => Ok(HealthGrade::F),
+        _ => Err(format!("expected a grade from A to F, got '{}'", value)),
+    }
+}
+
+fn print_latest(response: &LatestVersionResponse) {
+    println!("{}", response.dependency);
+    let latest = &response.latest;
+    for (label, version) in [
+        ("stable", &latest.stable),
+        ("rc", &latest.rc),
+        ("beta", &latest.beta),
+        ("alpha", &latest.alpha),
+        ("milestone", &latest.milestone),
+    ] {
+        if let Some(version) = version {
+            println!("  {:<10} {}", label, version);
+        }
+    }
+    println!(
+        "  {} versions, {} stable{}",
+        response.total_versions,
+        response.stable_versions,
+        response
+            .last_updated
+            .as_ref()
+            .map(|u| format!(", last updated {}", u))
+            .unwrap_or_default()
+    );
+}
+
+fn print_updates(response: &BulkCheckResponse) {
+    let width = response
+        .dependencies
+        .iter()
+        .map(|d| d.dependency.len())
+        .max()
+        .unwrap_or(0);
+    for d in &response.dependencies {
+        let current = d.current_version.as_deref().unwrap_or("-");
+        let status = match (&d.error, d.is_outdated) {
+            (Some(error), _) => format!("error: {}", error),
+            (None, true) => format!(
+                "{} -> {}{}",
+                current,
+                d.latest_version.as_deref().unwrap_or("?"),
+                d.update_type
+                    .map(|u| format!(" ({})", u))
+                    .unwrap_or_default()
+            ),
+            (None, false) => format!("{} up to date", current),
+        };
+        println!("{:<width$}  {}", d.dependency, status, width = width);
+    }
+    println!(
+        "\n{} checked: {} outdated, {} up to date, {} errors",
+        response.total_checked,
+        response.outdated_count,
+        response.up_to_date_count,
+        response.error_count
+    );
+    for warning in &response.warnings {
+        println!("warning: {}", warning);
+    }
+}
+
+fn print_health(health: &ProjectHealthResponse) {
+    let summary = &health.summary;
+    println!(
+        "Health: {} ({:.1}/100) across {} dependencies",
+        health.health_grade, health.health_score, health.total_dependencies
+    );
+    println!(
+        "  current {}, fresh {}, aging {}, stale {}, outdated {}, errors {}",
+        summary.current,
+        summary.fresh,
+        summary.aging,
+        summary.stale,
+        summary.outdated,
+        summary.errors
+    );
+
+    let attention: Vec<&DependencyHealthResult> = health
+        .dependencies
+        .iter()
+        .filter(|d| {
+            d.error.is_some()
+                || !matches!(
+                    d.age_classification,
+                    Some(AgeClassification::Current | AgeClassification::Fresh)
+                )
+        })
+        .collect();
+    if !attention.is_empty() {
+        println!("\nNeeds attention:");
+        for d in attention {
+            let status = match (&d.error, d.age_classification) {
+                (Some(error), _) => format!("error     {}: {}", d.dependency, error),
+                (None, age) => format!(
+                    "{:<9} {} {} -> {}",
+                    age.map(|a| a.to_string()).unwrap_or_default(),
+                    d.dependency,
+                    d.current_version.as_deref().unwrap_or("-"),
+                    d.latest_version.as_deref().unwrap_or("?")
+                ),
+            };
+            let source = crate::report::dependency_source(d);
+            if source.is_empty() {
+                println!("  {}", status);
+            } else {
+                println!("  {} ({})", status, source);
+            }
+        }
+    }
+
+    if !health.recommendations.is_empty() {
+        println!("\nRecommendations:");
+        for recommendation in &health.recommendations {
+            println!("  - {}", recommendation);
+        }
+    }
+    for warning in &health.warnings {
+        println!("warning: {}", warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(grade: HealthGrade, update_type: Option<UpdateType>) -> ProjectHealthResponse {
+        ProjectHealthResponse {
+            total_dependencies: 1,
+            health_score: 75.0,
+            health_grade: grade,
+            summary: HealthSummary {
+                current: 0,
+                fresh: 0,
+                aging: 1,
+                stale: 0,
+                outdated: 0,
+                errors: 0,
+            },
+            dependencies: vec![DependencyHealthResult {
+                dependency: "com.acme:lib".to_string(),
+                current_version: Some("1.0".to_string()),
+                latest_version: Some("2.0".to_string()),
+                age_classification: Some(AgeClassification::Aging),
+                health_score: 75.0,
+                update_type,
+                error: None,
+                location: None,
+                introduced_by: None,
+            }],
+            recommendations: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from([
+            "maven-mcp",
+            "analyze-pom",
+            "app/pom.xml",
+            "--min-grade",
+            "b",
+            "--no-major-behind",
+            "--format",
+            "sarif",
+        ])
+        .unwrap();
+        let Some(Command::AnalyzePom(args)) = cli.command else {
+            panic!("expected analyze-pom");
+        };
+        assert_eq!(args.path, "app/pom.xml");
+        assert_eq!(args.gates.min_grade, Some(HealthGrade::B));
+        assert!(args.gates.no_major_behind);
+        assert_eq!(args.format, OutputFormat::Sarif);
+
+        assert!(Cli::try_parse_from(["maven-mcp"])
+            .unwrap()
+            .command
+            .is_none());
+        assert!(Cli::try_parse_from(["maven-mcp", "health", "--min-grade", "E"]).is_err());
+        assert!(Cli::try_parse_from(["maven-mcp", "check", "--policy", "weekly"]).is_err());
+    }
+
+    #[test]
+    fn test_gates() {
+        let gates = Gates {
+            min_grade: Some(HealthGrade::B),
+            no_major_behind: false,
+        };
+        assert!(gates.violations(&health(HealthGrade::A, None)).is_empty());
+        assert!(gates.violations(&health(HealthGrade::B, None)).is_empty());
+        assert_eq!(
+            gates.violations(&health(HealthGrade::C, None)),
+            vec!["health grade C (75.0) is worse than the required B"]
+        );
+
+        let gates = Gates {
+            min_grade: None,
+            no_major_behind: true,
+        };
+        assert!(gates
+            .violations(&health(HealthGrade::C, Some(UpdateType::Minor)))
+            .is_empty());
+        assert_eq!(
+            gates.violations(&health(HealthGrade::C, Some(UpdateType::Major))),
+            vec!["com.acme:lib is a major version behind 2.0"]
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use futures::FutureExt;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::{StreamableHttpServerConfig, StreamableHttpService};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use crate::config::{TransportConfig, AUTH_TOKEN_ENV};
+use crate::tools::MavenToolsService;
+
+/// Path MCP clients connect to
+pub const MCP_PATH: &str = "/mcp";
+
+/// How long open connections may take to finish after a shutdown signal
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// Serve MCP over streamable HTTP until `shutdown` completes. Clients may
+/// only read files under `config.root` and cannot write files.
+pub async fn serve(
+    service: MavenToolsService,
+    config: &TransportConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    if config.auth_token.is_none() && !config.bind.ip().is_loopback() {
+        bail!(
+            "Refusing to listen on {} without a bearer token; set transport.auth_token or {}, or bind a loopback address",
+            config.bind,
+            AUTH_TOKEN_ENV
+        );
+    }
+    let root = match &config.root {
+        Some(root) => Some(
+            tokio::fs::canonicalize(root)
+                .await
+                .with_context(|| format!("Invalid transport.root {}", root.display()))?,
+        ),
+        None => None,
+    };
+
+    let listener = TcpListener::bind(config.bind)
+        .await
+        .with_context(|| format!("Failed to bind {}", config.bind))?;
+
+    info!("Serving MCP at http://{}{}", config.bind, MCP_PATH);
+    match &root {
+        Some(root) => info!("HTTP clients may read files under {}", root.display()),
+        None => info!("HTTP clients must send file content inline"),
+    }
+
+    serve_listener(
+        listener,
+        router(service.for_remote_clients(root), config.auth_token.clone()),
+        shutdown,
+    )
+    .await
+}
+
+/// Router serving the tools at [`MCP_PATH`]. Every session shares the
+/// service's caches, so cached metadata is reused across clients, while
+/// `resources/list` only shows a session the artifacts it queried.
+pub fn router(service: MavenToolsService, auth_token: Option<String>) -> Router {
+    let mcp = StreamableHttpService::new(
+        move || Ok(service.new_session()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = Router::new().nest_service(MCP_PATH, mcp);
+    match auth_token {
+        Some(token) => router.layer(middleware::from_fn_with_state(
+            Arc::new(token),
+            require_token,
+        )),
+        None => router,
+    }
+}
+
+/// Stop accepting connections once `shutdown` completes, then give open
+/// connections and SSE streams a grace period to finish
+async fn serve_listener(
+    listener: TcpListener,
+    router: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let shutdown = shutdown.shared();
+    let server = axum::serve(listener, router).with_graceful_shutdown(shutdown.clone());
+    tokio::select! {
+        result = server => result.context("HTTP server failed"),
+        _ = async {
+            shutdown.await;
+            tokio::time::sleep(SHUTDOWN_GRACE).await;
+        } => {
+            warn!(
+                "Closing connections still open {}s after shutdown",
+                SHUTDOWN_GRACE.as_secs()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Reject requests without the configured bearer token
+async fn require_token(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|sent| constant_time_eq(sent.as_bytes(), token.as_bytes()));
+    if authorized {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid bearer token",
+        )
+            .into_response()
+    }
+}
+
+/// Compare secrets without returning early at the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Completes on Ctrl-C or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("Shutdown signal received, finishing open requests");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::service::AnalyzePomParams;
+    use reqwest::header::ACCEPT;
+    use rmcp::handler::server::wrapper::Parameters;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_refuses_public_bind_without_token() {
+        let config = TransportConfig {
+            bind: "0.0.0.0:0".parse().unwrap(),
+            ..TransportConfig::default()
+        };
+        let err = serve(MavenToolsService::new(), &config, async {})
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("without a bearer token"));
+    }
+
+    #[tokio::test]
+    async fn test_remote_clients_read_only_under_root() {
+        let dir = std::env::temp_dir().join(format!("maven-mcp-http-{}", std::process::id()));
+        let root = dir.join("projects");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("pom.xml"),
+            "<project><groupId>com.acme</groupId><artifactId>app</artifactId></project>",
+        )
+        .unwrap();
+        std::fs::write(dir.join("secret.xml"), "<project/>").unwrap();
+
+        let analyze = |service: MavenToolsService, path: &str| {
+            let params = AnalyzePomParams {
+                path: Some(path.to_string()),
+                content: None,
+                stable_only: true,
+                update_policy: None,
+                output_format: Default::default(),
+            };
+            async move { service.analyze_pom(Parameters(params)).await }
+        };
+        let canonical_root = root.canonicalize().unwrap();
+        let scoped = MavenToolsService::new().for_remote_clients(Some(canonical_root));
+        let inline_only = MavenToolsService::new().for_remote_clients(None);
+
+        let within = analyze(scoped.clone(), "pom.xml").await;
+        let outside = analyze(scoped, "../secret.xml").await.unwrap_err();
+        let no_root = analyze(inline_only, "pom.xml").await.unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(within.is_ok());
+        assert!(outside.message.contains("outside the directory"));
+        assert!(no_root.message.contains("must send file content inline"));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_and_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), MCP_PATH);
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve_listener(
+            listener,
+            router(MavenToolsService::new(), Some("s3cret".to_string())),
+            async {
+                let _ = stopped.await;
+            },
+        ));
+
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0" }
+            }
+        });
+        let client = reqwest::Client::new();
+        let request = |token: Option<&str>| {
+            let request = client
+                .post(&url)
+                .header(ACCEPT, "application/json, text/event-stream")
+                .json(&initialize);
+            match token {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            }
+        };
+
+        for token in [None, Some("wrong")] {
+            let response = request(token).send().await.unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        }
+
+        let response = request(Some("s3cret")).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert!(response.headers().contains_key("mcp-session-id"));
+        let body = response.text().await.unwrap();
+        assert!(body.contains("\"serverInfo\""));
+        drop(client);
+
+        stop.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server did not shut down")
+            .unwrap()
+            .unwrap();
+    }
+}
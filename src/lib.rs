@@ -1,7 +1,16 @@
+pub mod build;
+pub mod cli;
+pub mod config;
+pub mod http;
 pub mod maven;
 pub mod models;
+pub mod report;
 pub mod tools;
 
+pub use config::ServerConfig;
 pub use maven::MavenClient;
-pub use models::{MavenCoordinate, MavenVersion, UpdateType, VersionStability};
+pub use models::{
+    MavenCoordinate, MavenVersion, StabilityRules, UpdatePolicy, UpdateType, VersionRange,
+    VersionScheme, VersionStability,
+};
 pub use tools::MavenToolsService;
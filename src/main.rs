@@ -1,34 +1,87 @@
+use std::process::ExitCode;
+
 use anyhow::Result;
+use clap::Parser;
 use rmcp::transport::stdio;
 use rmcp::ServiceExt;
 use tracing::info;
 use tracing_subscriber::{self, EnvFilter};
 
-use maven_mcp::MavenToolsService;
+use maven_mcp::cli::{self, Cli};
+use maven_mcp::config::{self, ConfigError, TransportMode};
+use maven_mcp::{http, MavenToolsService, ServerConfig};
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging (to stderr, not stdout which is used for MCP communication)
+async fn main() -> Result<ExitCode> {
+    let args = Cli::parse();
+
+    // Initialize logging (to stderr, not stdout which is used for MCP communication).
+    // Subcommands print their results to stdout, so only warnings are logged.
+    let level = if args.command.is_some() {
+        "maven_mcp=warn"
+    } else {
+        "maven_mcp=info"
+    };
     tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive("maven_mcp=info".parse()?))
+        .with_env_filter(EnvFilter::from_default_env().add_directive(level.parse()?))
         .with_writer(std::io::stderr)
         .with_ansi(false)
         .init();
 
-    info!("Starting maven-mcp v{}", env!("CARGO_PKG_VERSION"));
+    let (service, config) = match load_service(&args) {
+        Ok(loaded) => loaded,
+        Err(e) if args.command.is_some() => {
+            eprintln!("error: {:#}", e);
+            return Ok(ExitCode::from(cli::EXIT_ERROR));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(command) = args.command {
+        return Ok(cli::run(&service, command).await);
+    }
 
-    // Create the service
-    let service = MavenToolsService::new();
+    info!("Starting maven-mcp v{}", env!("CARGO_PKG_VERSION"));
 
-    // Start the server with stdio transport
-    let server = service.serve(stdio()).await?;
+    match config.transport.mode {
+        TransportMode::Stdio => {
+            // Start the server with stdio transport
+            let server = service.serve(stdio()).await?;
 
-    info!("Server initialized, waiting for requests...");
+            info!("Server initialized, waiting for requests...");
 
-    // Wait for the server to complete
-    let _result = server.waiting().await?;
+            // Wait for the server to complete
+            let _result = server.waiting().await?;
+        }
+        TransportMode::Http => {
+            http::serve(service, &config.transport, http::shutdown_signal()).await?;
+        }
+    }
 
     info!("Server shutting down");
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Create the service from the config file (--config or MAVEN_MCP_CONFIG)
+/// and the command line overrides. Stability rules fall back to the file
+/// named by MAVEN_MCP_STABILITY_RULES.
+fn load_service(args: &Cli) -> Result<(MavenToolsService, ServerConfig), ConfigError> {
+    let config_path = config::config_path(args.config.clone());
+    let mut config = ServerConfig::load_or_default(config_path.as_deref())?;
+    if let Some(path) = &config_path {
+        info!("Loaded config from {}", path.display());
+    }
+    if !config.stability.is_empty() {
+        info!("Loaded {} custom stability rules", config.stability.len());
+    }
+    if let Some(mode) = args.transport {
+        config.transport.mode = mode;
+    }
+    if let Some(bind) = args.bind {
+        config.transport.bind = bind;
+    }
+
+    let service = MavenToolsService::with_config(config.clone(), config_path)?;
+    Ok((service, config))
 }
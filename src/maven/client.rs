@@ -1,23 +1,43 @@
-use std::sync::Arc;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 use moka::future::Cache;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
+use tokio::sync::Semaphore;
 use tracing::{debug, instrument};
 
-use crate::maven::metadata::MavenMetadata;
-use crate::models::{MavenCoordinate, MavenVersion, VersionStability};
+use crate::config::{ConfigError, RepositoryConfig, ServerConfig, MAX_CACHE_TTL_HOURS};
 
-const MAVEN_CENTRAL_BASE: &str = "https://repo1.maven.org/maven2";
-const CACHE_TTL_HOURS: u64 = 24;
-const CACHE_MAX_ENTRIES: u64 = 1000;
+use crate::maven::metadata::{GradleRelease, MavenMetadata, PluginPrefix};
+use crate::models::{
+    MavenCoordinate, MavenVersion, StabilityRules, UpdateType, VersionRange, VersionScheme,
+    VersionStability,
+};
+
+/// Base URL of Maven Central
+pub const MAVEN_CENTRAL_BASE: &str = "https://repo1.maven.org/maven2";
+/// Maven repository of the Gradle Plugin Portal, hosting plugin markers
+pub const GRADLE_PLUGIN_PORTAL: &str = "https://plugins.gradle.org/m2";
+/// Endpoint listing every Gradle distribution
+pub const GRADLE_VERSIONS_ENDPOINT: &str = "https://services.gradle.org/versions/all";
+/// How many recently queried Maven Central artifacts are remembered
+const RECENT_ARTIFACTS: usize = 50;
 
 /// Client for fetching data from Maven Central
 #[derive(Clone)]
 pub struct MavenClient {
     http: Client,
     cache: Cache<String, Arc<CachedMetadata>>,
+    pom_cache: Cache<String, Arc<String>>,
+    prefix_cache: Cache<String, Arc<Vec<PluginPrefix>>>,
+    rules: Arc<StabilityRules>,
+    config: Arc<ServerConfig>,
+    /// Limits the repository requests in flight
+    permits: Arc<Semaphore>,
+    /// Maven Central artifacts queried most recently, newest first
+    recent: Arc<Mutex<VecDeque<MavenCoordinate>>>,
 }
 
 /// Cached metadata with processed version information
@@ -32,59 +52,521 @@ pub struct CachedMetadata {
     pub latest_alpha: Option<String>,
     pub latest_milestone: Option<String>,
     pub last_updated: Option<String>,
+    /// Latest versions per suffix stream (`None` is the default stream)
+    pub streams: BTreeMap<Option<String>, StreamVersions>,
+    /// All versions grouped by major line, newest first. Calendar and
+    /// timestamp versions are not grouped.
+    pub major_lines: BTreeMap<u64, Vec<String>>,
+    /// All versions grouped by major.minor line, newest first
+    pub minor_lines: BTreeMap<(u64, u64), Vec<String>>,
+}
+
+/// Latest versions within a single version stream
+#[derive(Debug, Clone, Default)]
+pub struct StreamVersions {
+    pub latest_stable: Option<String>,
+    pub latest_any: Option<String>,
+    pub total_versions: usize,
+}
+
+impl CachedMetadata {
+    /// Latest version within a stream, falling back to the overall latest if
+    /// the stream is unknown
+    pub fn latest_in_stream(&self, stream: Option<&str>, stable_only: bool) -> Option<String> {
+        match self.streams.get(&stream.map(String::from)) {
+            Some(s) if stable_only => s.latest_stable.clone(),
+            Some(s) => s.latest_any.clone(),
+            None if stable_only => self.latest_stable.clone(),
+            None => self.latest_any.clone(),
+        }
+    }
+
+    /// Number of versions in the stream of `current` that are newer than it
+    pub fn newer_in_stream(&self, current: &MavenVersion, stable_only: bool) -> usize {
+        let versions = if stable_only {
+            &self.stable_versions
+        } else {
+            &self.all_versions
+        };
+        versions
+            .iter()
+            .filter(|v| MavenVersion::detect_stream(v) == current.stream)
+            .filter(|v| MavenVersion::parse(v) > *current)
+            .count()
+    }
+
+    /// Latest eligible version within a major line (e.g. "latest 5.x")
+    pub fn latest_in_major_line(
+        &self,
+        major: u64,
+        eligible: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        self.major_lines
+            .get(&major)?
+            .iter()
+            .find(|v| eligible(v))
+            .cloned()
+    }
+
+    /// Latest eligible version within a major.minor line (e.g. "latest 5.3.x")
+    pub fn latest_in_minor_line(
+        &self,
+        major: u64,
+        minor: u64,
+        eligible: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        self.minor_lines
+            .get(&(major, minor))?
+            .iter()
+            .find(|v| eligible(v))
+            .cloned()
+    }
+
+    /// Latest eligible version of a calendar-versioned artifact that is
+    /// `current` itself or one of the `within` updates from it
+    pub fn latest_calendar_update(
+        &self,
+        current: &str,
+        within: &[UpdateType],
+        eligible: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        self.all_versions
+            .iter()
+            .filter(|v| eligible(v))
+            .find(|v| {
+                let update = UpdateType::between(current, v);
+                update == UpdateType::None || within.contains(&update)
+            })
+            .cloned()
+    }
+
+    /// Returns true if the version was classified as stable
+    pub fn is_stable(&self, version: &str) -> bool {
+        self.stable_versions.iter().any(|v| v == version)
+    }
+
+    /// Latest versions of every stream other than the given one
+    pub fn stream_alternatives(
+        &self,
+        stream: Option<&str>,
+        stable_only: bool,
+    ) -> Vec<(Option<String>, String)> {
+        self.streams
+            .iter()
+            .filter(|(name, _)| name.as_deref() != stream)
+            .filter_map(|(name, s)| {
+                let latest = if stable_only {
+                    s.latest_stable.clone()
+                } else {
+                    s.latest_any.clone()
+                };
+                latest.map(|v| (name.clone(), v))
+            })
+            .collect()
+    }
 }
 
 impl MavenClient {
     pub fn new() -> Self {
+        Self::with_stability_rules(StabilityRules::builtin())
+    }
+
+    /// Create a client that classifies versions with the given rules
+    pub fn with_stability_rules(rules: StabilityRules) -> Self {
+        Self::build(ServerConfig::default(), rules)
+    }
+
+    /// Create a client from a validated server config
+    pub fn with_config(config: ServerConfig) -> Result<Self, ConfigError> {
+        let rules = StabilityRules::from_file(config.stability.clone())?;
+        Ok(Self::build(config, rules))
+    }
+
+    fn build(config: ServerConfig, rules: StabilityRules) -> Self {
         let http = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent("maven-central-mcp/0.1.0")
+            .timeout(Duration::from_secs(config.http.timeout_secs))
+            .user_agent(&config.http.user_agent)
             .build()
             .expect("Failed to create HTTP client");
 
+        // Validation bounds ttl_hours; clamp anyway so that moka never panics
+        let max_ttl = Duration::from_secs(MAX_CACHE_TTL_HOURS * 3600);
+        let ttl = config
+            .cache
+            .ttl_hours
+            .checked_mul(3600)
+            .map_or(max_ttl, |secs| Duration::from_secs(secs).min(max_ttl));
         let cache = Cache::builder()
-            .max_capacity(CACHE_MAX_ENTRIES)
-            .time_to_live(Duration::from_secs(CACHE_TTL_HOURS * 3600))
+            .max_capacity(config.cache.max_entries)
+            .time_to_live(ttl)
             .build();
 
-        MavenClient { http, cache }
+        let pom_cache = Cache::builder()
+            .max_capacity(config.cache.max_entries)
+            .time_to_live(ttl)
+            .build();
+
+        let prefix_cache = Cache::builder()
+            .max_capacity(config.cache.max_entries)
+            .time_to_live(ttl)
+            .build();
+
+        MavenClient {
+            http,
+            cache,
+            pom_cache,
+            prefix_cache,
+            rules: Arc::new(rules),
+            permits: Arc::new(Semaphore::new(config.http.max_concurrent_requests)),
+            config: Arc::new(config),
+            recent: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// The stability rules used by this client
+    pub fn stability_rules(&self) -> &StabilityRules {
+        &self.rules
+    }
+
+    /// The config this client was created from
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Base URL requests for a repository are sent to, after applying the
+    /// configured mirrors
+    pub fn repository_url<'a>(&'a self, repository: &'a str) -> &'a str {
+        self.config
+            .repositories
+            .iter()
+            .find(|r| r.mirrors(repository))
+            .map_or(repository, |r| r.url.trim_end_matches('/'))
+    }
+
+    /// Start a GET request, with the credentials configured for its repository
+    fn get(&self, url: &str) -> RequestBuilder {
+        let request = self.http.get(url);
+        match self.config.repositories.iter().find(|r| r.serves(url)) {
+            Some(RepositoryConfig {
+                token: Some(token), ..
+            }) => request.bearer_auth(token),
+            Some(RepositoryConfig {
+                username: Some(username),
+                password,
+                ..
+            }) => request.basic_auth(username, password.as_ref()),
+            _ => request,
+        }
+    }
+
+    /// A client sharing this one's caches, connections and request limit,
+    /// with its own list of recent artifacts
+    pub fn with_new_history(&self) -> Self {
+        MavenClient {
+            recent: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Maven Central artifacts whose metadata (without a version) or POM
+    /// (with a version) was fetched most recently, newest first
+    pub fn recent_artifacts(&self) -> Vec<MavenCoordinate> {
+        self.recent
+            .lock()
+            .map(|recent| recent.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn remember(&self, coordinate: &MavenCoordinate, version: Option<&str>) {
+        let coordinate = MavenCoordinate {
+            group_id: coordinate.group_id.clone(),
+            artifact_id: coordinate.artifact_id.clone(),
+            version: version.map(String::from),
+        };
+        if let Ok(mut recent) = self.recent.lock() {
+            recent.retain(|c| c != &coordinate);
+            recent.push_front(coordinate);
+            recent.truncate(RECENT_ARTIFACTS);
+        }
+    }
+
+    /// Wait for a free request slot
+    async fn permit(&self) -> Result<tokio::sync::SemaphorePermit<'_>> {
+        self.permits
+            .acquire()
+            .await
+            .context("HTTP client is shutting down")
+    }
+
+    /// Classify a version of the given artifact using the configured rules
+    pub fn classify(&self, coordinate: &MavenCoordinate, version: &str) -> VersionStability {
+        self.rules.classify(&coordinate.group_id, version)
+    }
+
+    /// Update type between two versions of an artifact, classifying
+    /// pre-releases with the configured rules
+    pub fn update_type(&self, group_id: &str, current: &str, target: &str) -> UpdateType {
+        UpdateType::classified_between(current, target, |v| self.rules.classify(group_id, v))
     }
 
     /// Fetch and process metadata for a Maven coordinate
-    #[instrument(skip(self), fields(coordinate = %coordinate))]
     pub async fn get_metadata(&self, coordinate: &MavenCoordinate) -> Result<Arc<CachedMetadata>> {
-        let cache_key = coordinate.to_ga();
+        self.get_metadata_in(MAVEN_CENTRAL_BASE, coordinate).await
+    }
 
-        // Check cache first
-        if let Some(cached) = self.cache.get(&cache_key).await {
-            debug!("Cache hit for {}", cache_key);
-            return Ok(cached);
+    /// Fetch and process metadata for a coordinate from the given repository
+    #[instrument(skip(self), fields(coordinate = %coordinate))]
+    pub async fn get_metadata_in(
+        &self,
+        repository: &str,
+        coordinate: &MavenCoordinate,
+    ) -> Result<Arc<CachedMetadata>> {
+        let repository = repository.trim_end_matches('/');
+        let central = repository == MAVEN_CENTRAL_BASE;
+        let cache_key = if central {
+            coordinate.to_ga()
+        } else {
+            format!("{} {}", repository, coordinate.to_ga())
+        };
+
+        // Concurrent misses for the same key share a single fetch
+        let processed = self
+            .cache
+            .try_get_with(cache_key.clone(), async {
+                debug!("Cache miss for {}, fetching from {}", cache_key, repository);
+                let metadata = self
+                    .fetch_metadata(self.repository_url(repository), coordinate)
+                    .await?;
+                Ok(Arc::new(self.process_metadata(coordinate, &metadata)))
+            })
+            .await
+            .map_err(shared_error)?;
+        if central {
+            self.remember(coordinate, None);
         }
 
-        debug!("Cache miss for {}, fetching from Maven Central", cache_key);
+        Ok(processed)
+    }
 
-        // Fetch from Maven Central
-        let metadata = self.fetch_metadata(coordinate).await?;
-        let processed = Arc::new(self.process_metadata(&metadata));
+    /// Fetch the POM of a released artifact version
+    pub async fn get_pom(&self, coordinate: &MavenCoordinate) -> Result<Arc<String>> {
+        self.get_pom_in(MAVEN_CENTRAL_BASE, coordinate).await
+    }
 
-        // Store in cache
-        self.cache.insert(cache_key, processed.clone()).await;
+    /// Fetch the POM of a released artifact version from the given repository
+    #[instrument(skip(self), fields(coordinate = %coordinate))]
+    pub async fn get_pom_in(
+        &self,
+        repository: &str,
+        coordinate: &MavenCoordinate,
+    ) -> Result<Arc<String>> {
+        let repository = repository.trim_end_matches('/');
+        let version = coordinate
+            .version
+            .as_deref()
+            .context("A version is required to fetch a POM")?;
+        let central = repository == MAVEN_CENTRAL_BASE;
+        let cache_key = if central {
+            format!("{}:{}", coordinate.to_ga(), version)
+        } else {
+            format!("{} {}:{}", repository, coordinate.to_ga(), version)
+        };
 
-        Ok(processed)
+        let url = format!(
+            "{}/{}/{}/{}-{}.pom",
+            self.repository_url(repository),
+            coordinate.metadata_path(),
+            version,
+            coordinate.artifact_id,
+            version
+        );
+
+        let pom = self
+            .pom_cache
+            .try_get_with(cache_key.clone(), async {
+                debug!("Fetching POM from {}", url);
+
+                let _permit = self.permit().await?;
+                let response = self.get(&url).send().await.context("Failed to fetch POM")?;
+
+                if !response.status().is_success() {
+                    anyhow::bail!(
+                        "Failed to fetch POM for {}: HTTP {}",
+                        cache_key,
+                        response.status()
+                    );
+                }
+
+                let pom = response
+                    .text()
+                    .await
+                    .context("Failed to read response body")?;
+                Ok(Arc::new(pom))
+            })
+            .await
+            .map_err(shared_error)?;
+        if central {
+            self.remember(coordinate, Some(version));
+        }
+
+        Ok(pom)
     }
 
-    /// Fetch raw metadata from Maven Central
-    async fn fetch_metadata(&self, coordinate: &MavenCoordinate) -> Result<MavenMetadata> {
+    /// Fetch the checksum a repository publishes next to an artifact file,
+    /// e.g. extension "jar" with algorithm "sha256" for `a-1.0.jar.sha256`
+    #[instrument(skip(self), fields(coordinate = %coordinate))]
+    pub async fn get_checksum(
+        &self,
+        repository: &str,
+        coordinate: &MavenCoordinate,
+        extension: &str,
+        algorithm: &str,
+    ) -> Result<String> {
+        let repository = repository.trim_end_matches('/');
+        let version = coordinate
+            .version
+            .as_deref()
+            .context("A version is required to fetch a checksum")?;
+        let url = format!(
+            "{}/{}/{}/{}-{}.{}.{}",
+            self.repository_url(repository),
+            coordinate.metadata_path(),
+            version,
+            coordinate.artifact_id,
+            version,
+            extension,
+            algorithm
+        );
+
+        debug!("Fetching checksum from {}", url);
+
+        let _permit = self.permit().await?;
+        let response = self
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch checksum")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch {}: HTTP {}", url, response.status());
+        }
+
+        // Checksum files hold the hex digest, sometimes followed by a file name
+        let body = response
+            .text()
+            .await
+            .context("Failed to read response body")?;
+        let digest = body.split_whitespace().next().unwrap_or_default();
+        if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("Invalid checksum file {}", url);
+        }
+        Ok(digest.to_ascii_lowercase())
+    }
+
+    /// Fetch the published Gradle distributions from a versions endpoint,
+    /// classified like the versions of an `org.gradle:gradle` artifact
+    #[instrument(skip(self))]
+    pub async fn get_gradle_versions(&self, endpoint: &str) -> Result<Arc<CachedMetadata>> {
+        let cache_key = format!("gradle-distributions {}", endpoint);
+
+        // Concurrent misses for the same endpoint share a single fetch
+        self.cache
+            .try_get_with(cache_key, async {
+                debug!("Fetching Gradle versions from {}", endpoint);
+
+                let _permit = self.permit().await?;
+                let response = self
+                    .get(endpoint)
+                    .send()
+                    .await
+                    .context("Failed to fetch Gradle versions")?;
+
+                if !response.status().is_success() {
+                    anyhow::bail!(
+                        "Failed to fetch Gradle versions from {}: HTTP {}",
+                        endpoint,
+                        response.status()
+                    );
+                }
+
+                let json = response
+                    .text()
+                    .await
+                    .context("Failed to read response body")?;
+                let versions = GradleRelease::parse_all(&json)
+                    .context("Failed to parse Gradle versions")?
+                    .into_iter()
+                    .filter(GradleRelease::is_published)
+                    .map(|r| r.version)
+                    .collect();
+
+                let coordinate = MavenCoordinate::parse("org.gradle:gradle")?;
+                Ok(Arc::new(self.process_metadata(
+                    &coordinate,
+                    &MavenMetadata::from_versions(versions),
+                )))
+            })
+            .await
+            .map_err(shared_error)
+    }
+
+    /// Fetch the plugin prefixes published in a group's maven-metadata.xml
+    #[instrument(skip(self))]
+    pub async fn get_plugin_prefixes(&self, group_id: &str) -> Result<Arc<Vec<PluginPrefix>>> {
         let url = format!(
             "{}/{}/maven-metadata.xml",
-            MAVEN_CENTRAL_BASE,
+            self.repository_url(MAVEN_CENTRAL_BASE),
+            group_id.replace('.', "/")
+        );
+
+        // Concurrent misses for the same group share a single fetch
+        self.prefix_cache
+            .try_get_with(group_id.to_string(), async {
+                debug!("Fetching group metadata from {}", url);
+
+                let _permit = self.permit().await?;
+                let response = self
+                    .get(&url)
+                    .send()
+                    .await
+                    .context("Failed to fetch group maven-metadata.xml")?;
+
+                if !response.status().is_success() {
+                    anyhow::bail!(
+                        "Failed to fetch group metadata for {}: HTTP {}",
+                        group_id,
+                        response.status()
+                    );
+                }
+
+                let xml = response
+                    .text()
+                    .await
+                    .context("Failed to read response body")?;
+                let metadata = MavenMetadata::parse(&xml)
+                    .context("Failed to parse group maven-metadata.xml")?;
+                Ok(Arc::new(metadata.get_plugins()))
+            })
+            .await
+            .map_err(shared_error)
+    }
+
+    /// Fetch raw metadata from a Maven repository
+    async fn fetch_metadata(
+        &self,
+        repository: &str,
+        coordinate: &MavenCoordinate,
+    ) -> Result<MavenMetadata> {
+        let url = format!(
+            "{}/{}/maven-metadata.xml",
+            repository,
             coordinate.metadata_path()
         );
 
         debug!("Fetching metadata from {}", url);
 
+        let _permit = self.permit().await?;
         let response = self
-            .http
             .get(&url)
             .send()
             .await
@@ -107,7 +589,11 @@ impl MavenClient {
     }
 
     /// Process raw metadata into categorized version information
-    fn process_metadata(&self, metadata: &MavenMetadata) -> CachedMetadata {
+    pub(crate) fn process_metadata(
+        &self,
+        coordinate: &MavenCoordinate,
+        metadata: &MavenMetadata,
+    ) -> CachedMetadata {
         let all_versions = metadata.get_versions();
 
         // Sort versions by Maven version ordering (newest first)
@@ -125,13 +611,42 @@ impl MavenClient {
         let mut latest_beta = None;
         let mut latest_alpha = None;
         let mut latest_milestone = None;
+        let mut streams: BTreeMap<Option<String>, StreamVersions> = BTreeMap::new();
+        let mut major_lines: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        let mut minor_lines: BTreeMap<(u64, u64), Vec<String>> = BTreeMap::new();
 
         for (version_str, parsed) in &sorted_versions {
             if latest_any.is_none() {
                 latest_any = Some(version_str.clone());
             }
 
-            match parsed.stability {
+            let stability = self.classify(coordinate, version_str);
+
+            // Calendar and timestamp versions have no maintenance lines
+            let numbers = parsed.release_numbers();
+            let calendar = VersionScheme::detect(version_str).is_calendar();
+            if let Some(&major) = numbers.first().filter(|_| !calendar) {
+                let minor = numbers.get(1).copied().unwrap_or(0);
+                major_lines
+                    .entry(major)
+                    .or_default()
+                    .push(version_str.clone());
+                minor_lines
+                    .entry((major, minor))
+                    .or_default()
+                    .push(version_str.clone());
+            }
+
+            let stream = streams.entry(parsed.stream.clone()).or_default();
+            stream.total_versions += 1;
+            if stream.latest_any.is_none() {
+                stream.latest_any = Some(version_str.clone());
+            }
+            if stability.is_stable() && stream.latest_stable.is_none() {
+                stream.latest_stable = Some(version_str.clone());
+            }
+
+            match stability {
                 VersionStability::Stable => {
                     stable_versions.push(version_str.clone());
                     if latest_stable.is_none() {
@@ -174,6 +689,9 @@ impl MavenClient {
             latest_alpha,
             latest_milestone,
             last_updated: metadata.get_last_updated().map(String::from),
+            streams,
+            major_lines,
+            minor_lines,
         }
     }
 
@@ -187,6 +705,28 @@ impl MavenClient {
         Ok(metadata.all_versions.iter().any(|v| v == version))
     }
 
+    /// All published versions satisfying a range, newest first
+    pub async fn resolve_range(
+        &self,
+        coordinate: &MavenCoordinate,
+        range: &VersionRange,
+        stable_only: bool,
+    ) -> Result<Vec<String>> {
+        let metadata = self.get_metadata(coordinate).await?;
+
+        let versions = metadata
+            .all_versions
+            .iter()
+            .filter(|v| {
+                let stability = self.classify(coordinate, v);
+                (!stable_only || stability.is_stable()) && range.matches(v, stability)
+            })
+            .cloned()
+            .collect();
+
+        Ok(versions)
+    }
+
     /// Get versions filtered by stability
     pub async fn get_versions_by_stability(
         &self,
@@ -198,7 +738,7 @@ impl MavenClient {
         let versions: Vec<String> = metadata
             .all_versions
             .iter()
-            .filter(|v| VersionStability::classify(v) == stability)
+            .filter(|v| self.classify(coordinate, v) == stability)
             .cloned()
             .collect();
 
@@ -212,6 +752,11 @@ impl Default for MavenClient {
     }
 }
 
+/// An error shared by every caller waiting on the same cache fill
+fn shared_error(e: Arc<anyhow::Error>) -> anyhow::Error {
+    anyhow::anyhow!("{:#}", e)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,12 +785,184 @@ mod tests {
 </metadata>"#;
 
         let metadata = MavenMetadata::parse(xml).unwrap();
-        let processed = client.process_metadata(&metadata);
+        let coordinate = MavenCoordinate::parse("org.example:test").unwrap();
+        let processed = client.process_metadata(&coordinate, &metadata);
 
         assert_eq!(processed.latest_stable, Some("1.5.0".to_string()));
         assert_eq!(processed.latest_rc, Some("2.0.0-RC1".to_string()));
         assert_eq!(processed.latest_beta, Some("2.0.0-beta".to_string()));
         assert_eq!(processed.latest_alpha, Some("2.0.0-alpha".to_string()));
         assert_eq!(processed.stable_versions.len(), 3);
+        let stable = |v: &str| processed.is_stable(v);
+        assert_eq!(
+            processed.latest_in_minor_line(1, 0, stable),
+            Some("1.0.1".to_string())
+        );
+        assert_eq!(
+            processed.latest_in_major_line(1, stable),
+            Some("1.5.0".to_string())
+        );
+        assert_eq!(
+            processed.latest_in_major_line(2, |_| true),
+            Some("2.0.0-RC1".to_string())
+        );
+        assert_eq!(processed.latest_in_major_line(2, stable), None);
+    }
+
+    #[tokio::test]
+    async fn test_process_metadata_streams() {
+        let client = MavenClient::new();
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.google.guava</groupId>
+  <artifactId>guava</artifactId>
+  <versioning>
+    <versions>
+      <version>31.0-android</version>
+      <version>31.0-jre</version>
+      <version>32.1.0-android</version>
+      <version>32.1.0-jre</version>
+      <version>33.0.0-android</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+
+        let metadata = MavenMetadata::parse(xml).unwrap();
+        let coordinate = MavenCoordinate::parse("com.google.guava:guava").unwrap();
+        let processed = client.process_metadata(&coordinate, &metadata);
+
+        assert_eq!(processed.streams.len(), 2);
+        assert_eq!(
+            processed.latest_in_stream(Some("jre"), true),
+            Some("32.1.0-jre".to_string())
+        );
+        assert_eq!(
+            processed.stream_alternatives(Some("jre"), true),
+            vec![(Some("android".to_string()), "33.0.0-android".to_string())]
+        );
+        // 31.0-jre is one release behind in its stream, not three overall
+        let current = MavenVersion::parse("31.0-jre");
+        assert_eq!(processed.newer_in_stream(&current, true), 1);
+        assert_eq!(processed.newer_in_stream(&current, false), 1);
+    }
+
+    #[test]
+    fn test_repository_mirrors() {
+        let config = ServerConfig::from_toml(
+            r#"
+[[repositories]]
+url = "https://nexus.example.com/maven-public/"
+mirror_of = "central"
+"#,
+        )
+        .unwrap();
+        let client = MavenClient::with_config(config).unwrap();
+        assert_eq!(
+            client.repository_url(MAVEN_CENTRAL_BASE),
+            "https://nexus.example.com/maven-public"
+        );
+        assert_eq!(
+            client.repository_url(GRADLE_PLUGIN_PORTAL),
+            GRADLE_PLUGIN_PORTAL
+        );
+    }
+
+    #[test]
+    fn test_recent_artifacts() {
+        let client = MavenClient::new();
+        let lib = MavenCoordinate::parse("com.acme:lib").unwrap();
+        let app = MavenCoordinate::parse("com.acme:app").unwrap();
+        client.remember(&lib, None);
+        client.remember(&lib, Some("1.0"));
+        client.remember(&app, None);
+        client.remember(&lib, None);
+
+        let recent: Vec<String> = client
+            .recent_artifacts()
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            recent,
+            vec!["com.acme:lib", "com.acme:app", "com.acme:lib:1.0"]
+        );
+        assert!(client.with_new_history().recent_artifacts().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_share_fetch() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let counter = fetches.clone();
+        let prefix_fetches = Arc::new(AtomicUsize::new(0));
+        let prefix_counter = prefix_fetches.clone();
+        let gradle_fetches = Arc::new(AtomicUsize::new(0));
+        let gradle_counter = gradle_fetches.clone();
+        let router = axum::Router::new()
+            .route(
+                "/com/acme/lib/maven-metadata.xml",
+                axum::routing::get(move || async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    "<metadata><groupId>com.acme</groupId><artifactId>lib</artifactId>\
+                     <versioning><versions><version>1.0</version></versions></versioning></metadata>"
+                }),
+            )
+            .route(
+                "/com/acme/maven-metadata.xml",
+                axum::routing::get(move || async move {
+                    prefix_counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    "<metadata><plugins><plugin><prefix>acme</prefix>\
+                     <artifactId>acme-maven-plugin</artifactId></plugin></plugins></metadata>"
+                }),
+            )
+            .route(
+                "/gradle/versions",
+                axum::routing::get(move || async move {
+                    gradle_counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    r#"[{"version":"8.10"}]"#
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = ServerConfig::from_toml(&format!(
+            "[[repositories]]\nurl = \"http://{}\"\nmirror_of = \"central\"\n",
+            address
+        ))
+        .unwrap();
+        let client = MavenClient::with_config(config).unwrap();
+        let lib = MavenCoordinate::parse("com.acme:lib").unwrap();
+        let missing = MavenCoordinate::parse("com.acme:missing").unwrap();
+
+        let (first, second) = tokio::join!(client.get_metadata(&lib), client.get_metadata(&lib));
+        assert_eq!(first.unwrap().latest_any.as_deref(), Some("1.0"));
+        assert!(second.is_ok());
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        assert!(client.get_metadata(&missing).await.is_err());
+        assert_eq!(client.recent_artifacts(), vec![lib]);
+
+        let (first, second) = tokio::join!(
+            client.get_plugin_prefixes("com.acme"),
+            client.get_plugin_prefixes("com.acme")
+        );
+        assert_eq!(first.unwrap()[0].prefix, "acme");
+        assert!(second.is_ok());
+        assert_eq!(prefix_fetches.load(Ordering::SeqCst), 1);
+
+        let endpoint = format!("http://{}/gradle/versions", address);
+        let (first, second) = tokio::join!(
+            client.get_gradle_versions(&endpoint),
+            client.get_gradle_versions(&endpoint)
+        );
+        assert_eq!(first.unwrap().latest_stable.as_deref(), Some("8.10"));
+        assert!(second.is_ok());
+        assert_eq!(gradle_fetches.load(Ordering::SeqCst), 1);
     }
 }
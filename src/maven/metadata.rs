@@ -7,6 +7,8 @@ pub struct MavenMetadata {
     pub group_id: Option<String>,
     pub artifact_id: Option<String>,
     pub versioning: Option<Versioning>,
+    /// Plugin prefixes, present in group-level metadata of plugin groups
+    pub plugins: Option<Plugins>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -24,7 +26,65 @@ pub struct Versions {
     pub versions: Vec<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct Plugins {
+    #[serde(rename = "plugin", default)]
+    pub plugins: Vec<PluginPrefix>,
+}
+
+/// Maps a goal prefix like "surefire" to the plugin's artifactId
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPrefix {
+    pub name: Option<String>,
+    pub prefix: String,
+    pub artifact_id: String,
+}
+
+/// A release listed by the Gradle distributions endpoint
+/// (`https://services.gradle.org/versions/all`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradleRelease {
+    pub version: String,
+    #[serde(default)]
+    pub snapshot: bool,
+    #[serde(default)]
+    pub nightly: bool,
+    #[serde(default)]
+    pub release_nightly: bool,
+    #[serde(default)]
+    pub broken: bool,
+}
+
+impl GradleRelease {
+    /// Parse the JSON array served by the distributions endpoint
+    pub fn parse_all(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Whether the release can be used as a wrapper distribution
+    pub fn is_published(&self) -> bool {
+        !(self.snapshot || self.nightly || self.release_nightly || self.broken)
+    }
+}
+
 impl MavenMetadata {
+    /// Metadata listing the given versions, for sources that are not Maven repositories
+    pub fn from_versions(versions: Vec<String>) -> Self {
+        MavenMetadata {
+            group_id: None,
+            artifact_id: None,
+            versioning: Some(Versioning {
+                latest: None,
+                release: None,
+                versions: Some(Versions { versions }),
+                last_updated: None,
+            }),
+            plugins: None,
+        }
+    }
+
     /// Parse maven-metadata.xml content
     pub fn parse(xml: &str) -> Result<Self, quick_xml::DeError> {
         quick_xml::de::from_str(xml)
@@ -49,6 +109,14 @@ impl MavenMetadata {
         self.versioning.as_ref()?.release.as_deref()
     }
 
+    /// Get the plugin prefixes of a group-level metadata file
+    pub fn get_plugins(&self) -> Vec<PluginPrefix> {
+        self.plugins
+            .as_ref()
+            .map(|p| p.plugins.clone())
+            .unwrap_or_default()
+    }
+
     /// Get the last updated timestamp
     pub fn get_last_updated(&self) -> Option<&str> {
         self.versioning.as_ref()?.last_updated.as_deref()
@@ -88,6 +156,49 @@ mod tests {
         assert_eq!(metadata.get_versions().len(), 6);
     }
 
+    #[test]
+    fn test_parse_group_plugins() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <plugins>
+    <plugin>
+      <name>Maven Surefire Plugin</name>
+      <prefix>surefire</prefix>
+      <artifactId>maven-surefire-plugin</artifactId>
+    </plugin>
+    <plugin>
+      <prefix>compiler</prefix>
+      <artifactId>maven-compiler-plugin</artifactId>
+    </plugin>
+  </plugins>
+</metadata>"#;
+
+        let metadata = MavenMetadata::parse(xml).unwrap();
+        let plugins = metadata.get_plugins();
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].prefix, "surefire");
+        assert_eq!(plugins[1].artifact_id, "maven-compiler-plugin");
+        assert!(metadata.get_versions().is_empty());
+    }
+
+    #[test]
+    fn test_parse_gradle_releases() {
+        let json = r#"[
+  {"version": "8.6-20231212012345+0000", "snapshot": true, "nightly": true},
+  {"version": "8.5", "current": true, "snapshot": false, "broken": false},
+  {"version": "8.5-rc-1", "activeRc": false, "rcFor": "8.5"},
+  {"version": "7.6.3", "broken": true}
+]"#;
+
+        let releases = GradleRelease::parse_all(json).unwrap();
+        let published: Vec<&str> = releases
+            .iter()
+            .filter(|r| r.is_published())
+            .map(|r| r.version.as_str())
+            .collect();
+        assert_eq!(published, vec!["8.5", "8.5-rc-1"]);
+    }
+
     #[test]
     fn test_parse_empty_versions() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1,5 +1,8 @@
 pub mod client;
 pub mod metadata;
 
-pub use client::{CachedMetadata, MavenClient};
-pub use metadata::MavenMetadata;
+pub use client::{
+    CachedMetadata, MavenClient, StreamVersions, GRADLE_PLUGIN_PORTAL, GRADLE_VERSIONS_ENDPOINT,
+    MAVEN_CENTRAL_BASE,
+};
+pub use metadata::{GradleRelease, MavenMetadata, PluginPrefix};
@@ -0,0 +1,138 @@
+use crate::models::{RangeError, VersionRange, VersionStability};
+
+/// A set of version constraints that must all hold for a single artifact
+#[derive(Debug, Clone)]
+pub struct ConstraintSet {
+    constraints: Vec<(String, VersionRange)>,
+}
+
+/// Outcome of intersecting a constraint set with the published versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintSolution {
+    /// Versions satisfying every constraint, in input order
+    pub satisfying: Vec<String>,
+    /// Indices of a minimal subset of constraints that cannot be satisfied
+    /// together; empty when the set is satisfiable
+    pub conflict: Vec<usize>,
+}
+
+impl ConstraintSet {
+    /// Parse every constraint, failing on the first invalid one
+    pub fn parse<S: AsRef<str>>(constraints: &[S]) -> Result<Self, RangeError> {
+        let constraints = constraints
+            .iter()
+            .map(|c| {
+                Ok((
+                    c.as_ref().trim().to_string(),
+                    VersionRange::parse(c.as_ref())?,
+                ))
+            })
+            .collect::<Result<Vec<_>, RangeError>>()?;
+        Ok(ConstraintSet { constraints })
+    }
+
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// The original constraint strings
+    pub fn raw(&self) -> impl Iterator<Item = &str> {
+        self.constraints.iter().map(|(raw, _)| raw.as_str())
+    }
+
+    /// The parsed constraints
+    pub fn ranges(&self) -> impl Iterator<Item = &VersionRange> {
+        self.constraints.iter().map(|(_, range)| range)
+    }
+
+    /// Intersect the constraints over the given versions (newest first)
+    pub fn solve(&self, versions: &[(String, VersionStability)]) -> ConstraintSolution {
+        let all: Vec<usize> = (0..self.constraints.len()).collect();
+        let satisfying: Vec<String> = versions
+            .iter()
+            .filter(|(v, s)| self.satisfied_by(&all, v, *s))
+            .map(|(v, _)| v.clone())
+            .collect();
+
+        let conflict = if satisfying.is_empty() && !all.is_empty() {
+            self.minimal_conflict(versions)
+        } else {
+            Vec::new()
+        };
+
+        ConstraintSolution {
+            satisfying,
+            conflict,
+        }
+    }
+
+    fn satisfied_by(&self, subset: &[usize], version: &str, stability: VersionStability) -> bool {
+        subset
+            .iter()
+            .all(|&i| self.constraints[i].1.matches(version, stability))
+    }
+
+    fn satisfiable(&self, subset: &[usize], versions: &[(String, VersionStability)]) -> bool {
+        versions
+            .iter()
+            .any(|(v, s)| self.satisfied_by(subset, v, *s))
+    }
+
+    /// Deletion filter: drop each constraint whose removal keeps the rest
+    /// unsatisfiable, leaving a minimal unsatisfiable subset
+    fn minimal_conflict(&self, versions: &[(String, VersionStability)]) -> Vec<usize> {
+        let mut core: Vec<usize> = (0..self.constraints.len()).collect();
+        let mut i = 0;
+        while i < core.len() {
+            let mut candidate = core.clone();
+            candidate.remove(i);
+            if !self.satisfiable(&candidate, versions) {
+                core = candidate;
+            } else {
+                i += 1;
+            }
+        }
+        core
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<(String, VersionStability)> {
+        list.iter()
+            .map(|v| (v.to_string(), VersionStability::classify(v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_satisfiable_constraints() {
+        let published = versions(&["2.16.0", "2.15.3", "2.15.2", "2.14.2", "2.14.1", "2.13.0"]);
+        let set = ConstraintSet::parse(&["[2.13,2.16)", "2.15.+", ">=2.14.2"]).unwrap();
+        let solution = set.solve(&published);
+        assert_eq!(solution.satisfying, vec!["2.15.3", "2.15.2"]);
+        assert!(solution.conflict.is_empty());
+    }
+
+    #[test]
+    fn test_minimal_conflict() {
+        let published = versions(&["2.16.0", "2.15.3", "2.14.2", "2.13.0"]);
+        let set =
+            ConstraintSet::parse(&[">=2.13", "[2.13,2.14)", "2.16.+", "latest.release"]).unwrap();
+        let solution = set.solve(&published);
+        assert!(solution.satisfying.is_empty());
+        assert_eq!(solution.conflict, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_unsatisfiable_single_constraint() {
+        let published = versions(&["1.0.0", "1.1.0"]);
+        let set = ConstraintSet::parse(&["1.+", "3.+"]).unwrap();
+        assert_eq!(set.solve(&published).conflict, vec![1]);
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
+use crate::models::VersionRange;
+
 #[derive(Error, Debug)]
 pub enum CoordinateError {
     #[error("Invalid Maven coordinate format: {0}. Expected 'groupId:artifactId' or 'groupId:artifactId:version'")]
@@ -47,7 +49,7 @@ impl MavenCoordinate {
                     version: None,
                 })
             }
-            3 | 4 | 5 => {
+            3..=5 => {
                 // 3 = g:a:v, 4 = g:a:packaging:v, 5 = g:a:packaging:classifier:v
                 let group_id = parts[0].trim();
                 let artifact_id = parts[1].trim();
@@ -88,6 +90,14 @@ impl MavenCoordinate {
         format!("{}/{}", self.group_path(), self.artifact_id)
     }
 
+    /// Returns true if the version is a Maven range or Gradle dynamic version
+    /// (e.g. "[1.2,2.0)", "1.+", "latest.release") rather than a literal version
+    pub fn has_dynamic_version(&self) -> bool {
+        self.version
+            .as_deref()
+            .is_some_and(VersionRange::is_dynamic)
+    }
+
     /// Returns coordinate without version as "groupId:artifactId"
     pub fn to_ga(&self) -> String {
         format!("{}:{}", self.group_id, self.artifact_id)
@@ -138,6 +148,18 @@ mod tests {
         assert_eq!(coord.version, Some("6.1.0".to_string()));
     }
 
+    #[test]
+    fn test_parse_version_range() {
+        let coord =
+            MavenCoordinate::parse("com.fasterxml.jackson.core:jackson-databind:[2.13,2.16)")
+                .unwrap();
+        assert_eq!(coord.version, Some("[2.13,2.16)".to_string()));
+        assert!(coord.has_dynamic_version());
+
+        let coord = MavenCoordinate::parse("org.springframework:spring-core:6.1.0").unwrap();
+        assert!(!coord.has_dynamic_version());
+    }
+
     #[test]
     fn test_group_path() {
         let coord = MavenCoordinate::parse("org.springframework:spring-core").unwrap();
@@ -1,5 +1,13 @@
+pub mod constraint;
 pub mod coordinate;
+pub mod policy;
+pub mod range;
+pub mod rules;
 pub mod version;
 
+pub use constraint::{ConstraintSet, ConstraintSolution};
 pub use coordinate::{CoordinateError, MavenCoordinate};
-pub use version::{MavenVersion, UpdateType, VersionStability};
+pub use policy::UpdatePolicy;
+pub use range::{LatestStatus, RangeError, VersionRange};
+pub use rules::{RuleSource, RulesError, StabilityClassification, StabilityRules};
+pub use version::{MavenVersion, UpdateType, VersionScheme, VersionStability};
@@ -0,0 +1,30 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Which maintenance line decides whether a dependency is outdated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(
+    description = "Which maintenance line decides is_outdated and which version updates move to. Defaults to the server's configured policy, 'major' unless configured"
+)]
+pub enum UpdatePolicy {
+    /// Only consider newer versions within the current major.minor line, or
+    /// released within a month for calendar versions
+    Patch,
+    /// Only consider newer versions within the current major line, or
+    /// released within a year for calendar versions
+    Minor,
+    /// Consider the newest version regardless of line
+    #[default]
+    Major,
+}
+
+impl std::fmt::Display for UpdatePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdatePolicy::Patch => write!(f, "patch"),
+            UpdatePolicy::Minor => write!(f, "minor"),
+            UpdatePolicy::Major => write!(f, "major"),
+        }
+    }
+}
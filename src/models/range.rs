@@ -0,0 +1,397 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::{MavenVersion, VersionStability};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    #[error("Empty version range")]
+    Empty,
+    #[error("Invalid version range '{0}': {1}")]
+    Invalid(String, String),
+}
+
+/// One end of a version interval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bound {
+    pub version: String,
+    pub inclusive: bool,
+}
+
+/// A single interval of a Maven version range, e.g. `[1.2,2.0)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interval {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower: Option<Bound>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper: Option<Bound>,
+}
+
+impl Interval {
+    pub fn contains(&self, version: &MavenVersion) -> bool {
+        let above_lower = self.lower.as_ref().is_none_or(|b| {
+            match version.cmp(&MavenVersion::parse(&b.version)) {
+                Ordering::Greater => true,
+                Ordering::Equal => b.inclusive,
+                Ordering::Less => false,
+            }
+        });
+        let below_upper = self.upper.as_ref().is_none_or(|b| {
+            match version.cmp(&MavenVersion::parse(&b.version)) {
+                Ordering::Less => true,
+                Ordering::Equal => b.inclusive,
+                Ordering::Greater => false,
+            }
+        });
+        above_lower && below_upper
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A closed interval with equal bounds is the hard requirement "[1.0]"
+        if let (Some(l), Some(u)) = (&self.lower, &self.upper) {
+            if l.inclusive && u.inclusive && l.version == u.version {
+                return write!(f, "[{}]", l.version);
+            }
+        }
+        let open = match &self.lower {
+            Some(b) if b.inclusive => '[',
+            _ => '(',
+        };
+        let close = match &self.upper {
+            Some(b) if b.inclusive => ']',
+            _ => ')',
+        };
+        write!(
+            f,
+            "{}{},{}{}",
+            open,
+            self.lower
+                .as_ref()
+                .map(|b| b.version.as_str())
+                .unwrap_or(""),
+            self.upper
+                .as_ref()
+                .map(|b| b.version.as_str())
+                .unwrap_or(""),
+            close
+        )
+    }
+}
+
+/// Gradle `latest.<status>` selectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LatestStatus {
+    /// `latest.release`: newest stable version
+    Release,
+    /// `latest.milestone`: newest version that is not a snapshot
+    Milestone,
+    /// `latest.integration`: newest version of any kind
+    Integration,
+}
+
+/// A version requirement: a Maven range, a Gradle dynamic version or a plain version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum VersionRange {
+    /// Maven range syntax, a union of intervals like `[1.2,2.0),[3.0,)`
+    Maven { intervals: Vec<Interval> },
+    /// Gradle prefix version like `1.+` or `+`
+    Prefix { prefix: String },
+    /// Gradle `latest.release`, `latest.milestone` or `latest.integration`
+    Latest { status: LatestStatus },
+    /// A plain version, matched exactly
+    Exact { version: String },
+}
+
+impl VersionRange {
+    /// Parse Maven range syntax, Gradle dynamic versions or a plain version
+    pub fn parse(input: &str) -> Result<Self, RangeError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(RangeError::Empty);
+        }
+
+        if input.starts_with('[') || input.starts_with('(') {
+            return Self::parse_maven(input);
+        }
+
+        if input.starts_with(['<', '>', '=']) {
+            return Self::parse_comparators(input);
+        }
+
+        if let Some(prefix) = input.strip_suffix('+') {
+            if prefix.contains('+') {
+                return Err(RangeError::Invalid(
+                    input.to_string(),
+                    "'+' is only allowed at the end".to_string(),
+                ));
+            }
+            return Ok(VersionRange::Prefix {
+                prefix: prefix.to_string(),
+            });
+        }
+
+        match input {
+            "latest.release" => Ok(VersionRange::Latest {
+                status: LatestStatus::Release,
+            }),
+            "latest.milestone" => Ok(VersionRange::Latest {
+                status: LatestStatus::Milestone,
+            }),
+            "latest.integration" => Ok(VersionRange::Latest {
+                status: LatestStatus::Integration,
+            }),
+            _ => Ok(VersionRange::Exact {
+                version: input.to_string(),
+            }),
+        }
+    }
+
+    fn parse_maven(input: &str) -> Result<Self, RangeError> {
+        let invalid = |reason: &str| RangeError::Invalid(input.to_string(), reason.to_string());
+        let mut intervals = Vec::new();
+        let mut rest = input;
+
+        while !rest.is_empty() {
+            let open = rest.chars().next().unwrap();
+            if open != '[' && open != '(' {
+                return Err(invalid("expected '[' or '('"));
+            }
+            let end = rest
+                .find([']', ')'])
+                .ok_or_else(|| invalid("missing closing ']' or ')'"))?;
+            let close = rest[end..].chars().next().unwrap();
+            let body = &rest[1..end];
+
+            let interval = match body.split(',').collect::<Vec<_>>().as_slice() {
+                [exact] => {
+                    let exact = exact.trim();
+                    if open != '[' || close != ']' || exact.is_empty() {
+                        return Err(invalid("a single version must be written as '[x]'"));
+                    }
+                    let bound = Bound {
+                        version: exact.to_string(),
+                        inclusive: true,
+                    };
+                    Interval {
+                        lower: Some(bound.clone()),
+                        upper: Some(bound),
+                    }
+                }
+                [lower, upper] => {
+                    let lower = lower.trim();
+                    let upper = upper.trim();
+                    let interval = Interval {
+                        lower: (!lower.is_empty()).then(|| Bound {
+                            version: lower.to_string(),
+                            inclusive: open == '[',
+                        }),
+                        upper: (!upper.is_empty()).then(|| Bound {
+                            version: upper.to_string(),
+                            inclusive: close == ']',
+                        }),
+                    };
+                    if let (Some(l), Some(u)) = (&interval.lower, &interval.upper) {
+                        if MavenVersion::parse(&l.version) > MavenVersion::parse(&u.version) {
+                            return Err(invalid("lower bound is greater than upper bound"));
+                        }
+                    }
+                    interval
+                }
+                _ => return Err(invalid("an interval has at most two bounds")),
+            };
+            intervals.push(interval);
+
+            rest = rest[end + 1..].trim_start();
+            if let Some(next) = rest.strip_prefix(',') {
+                rest = next.trim_start();
+                if rest.is_empty() {
+                    return Err(invalid("trailing ','"));
+                }
+            }
+        }
+
+        Ok(VersionRange::Maven { intervals })
+    }
+
+    /// Parse comparator constraints like ">=2.14.2" or ">=1.0 <2.0" into a single interval
+    fn parse_comparators(input: &str) -> Result<Self, RangeError> {
+        let invalid = |reason: &str| RangeError::Invalid(input.to_string(), reason.to_string());
+        let mut interval = Interval {
+            lower: None,
+            upper: None,
+        };
+
+        for term in input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+        {
+            let (op, version) = [">=", "<=", ">", "<", "="]
+                .iter()
+                .find_map(|op| term.strip_prefix(op).map(|v| (*op, v.trim())))
+                .ok_or_else(|| invalid("expected a comparator like '>=' or '<'"))?;
+            if version.is_empty() {
+                return Err(invalid("comparator without a version"));
+            }
+            let bound = |inclusive| {
+                Some(Bound {
+                    version: version.to_string(),
+                    inclusive,
+                })
+            };
+            match op {
+                ">=" => interval.lower = bound(true),
+                ">" => interval.lower = bound(false),
+                "<=" => interval.upper = bound(true),
+                "<" => interval.upper = bound(false),
+                _ => {
+                    interval.lower = bound(true);
+                    interval.upper = bound(true);
+                }
+            }
+        }
+
+        if let (Some(l), Some(u)) = (&interval.lower, &interval.upper) {
+            if MavenVersion::parse(&l.version) > MavenVersion::parse(&u.version) {
+                return Err(invalid("lower bound is greater than upper bound"));
+            }
+        }
+
+        Ok(VersionRange::Maven {
+            intervals: vec![interval],
+        })
+    }
+
+    /// Returns true if the string is a range or dynamic version rather than a plain version
+    pub fn is_dynamic(input: &str) -> bool {
+        !matches!(Self::parse(input), Ok(VersionRange::Exact { .. }) | Err(_))
+    }
+
+    /// Check whether a version with the given stability satisfies this requirement
+    pub fn matches(&self, version: &str, stability: VersionStability) -> bool {
+        match self {
+            VersionRange::Maven { intervals } => {
+                let parsed = MavenVersion::parse(version);
+                intervals.iter().any(|i| i.contains(&parsed))
+            }
+            VersionRange::Prefix { prefix } => version.starts_with(prefix.as_str()),
+            VersionRange::Latest { status } => match status {
+                LatestStatus::Release => stability.is_stable(),
+                LatestStatus::Milestone => stability != VersionStability::Snapshot,
+                LatestStatus::Integration => true,
+            },
+            VersionRange::Exact { version: exact } => {
+                MavenVersion::parse(version).cmp(&MavenVersion::parse(exact)) == Ordering::Equal
+            }
+        }
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionRange::Maven { intervals } => {
+                let parts: Vec<String> = intervals.iter().map(|i| i.to_string()).collect();
+                write!(f, "{}", parts.join(","))
+            }
+            VersionRange::Prefix { prefix } => write!(f, "{}+", prefix),
+            VersionRange::Latest { status } => match status {
+                LatestStatus::Release => write!(f, "latest.release"),
+                LatestStatus::Milestone => write!(f, "latest.milestone"),
+                LatestStatus::Integration => write!(f, "latest.integration"),
+            },
+            VersionRange::Exact { version } => write!(f, "{}", version),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(range: &str, version: &str) -> bool {
+        VersionRange::parse(range)
+            .unwrap()
+            .matches(version, VersionStability::classify(version))
+    }
+
+    #[test]
+    fn test_parse_maven_ranges() {
+        assert!(matches("[1.2,2.0)", "1.2"));
+        assert!(matches("[1.2,2.0)", "1.9.9"));
+        assert!(!matches("[1.2,2.0)", "2.0"));
+        assert!(matches("(,1.5]", "1.5"));
+        assert!(!matches("(,1.5]", "1.5.1"));
+        assert!(matches("[1.0]", "1.0"));
+        assert!(!matches("[1.0]", "1.0.1"));
+        assert!(matches("(,1.0],[1.2,)", "1.3"));
+        assert!(!matches("(,1.0],[1.2,)", "1.1"));
+    }
+
+    #[test]
+    fn test_parse_gradle_dynamic() {
+        assert!(matches("1.+", "1.10.0"));
+        assert!(!matches("1.+", "2.0.0"));
+        assert!(matches("+", "2.0.0"));
+        assert!(matches("latest.release", "2.0.0"));
+        assert!(!matches("latest.release", "2.0.0-RC1"));
+        assert!(matches("latest.milestone", "2.0.0-RC1"));
+        assert!(!matches("latest.milestone", "2.0.0-SNAPSHOT"));
+    }
+
+    #[test]
+    fn test_parse_comparators() {
+        assert!(matches(">=2.14.2", "2.14.2"));
+        assert!(!matches(">=2.14.2", "2.14.1"));
+        assert!(matches(">=1.0 <2.0", "1.5"));
+        assert!(!matches(">=1.0 <2.0", "2.0"));
+        assert!(matches("=1.0", "1.0"));
+        assert_eq!(
+            VersionRange::parse(">=1.0 <2.0").unwrap().to_string(),
+            "[1.0,2.0)"
+        );
+        assert!(VersionRange::parse(">=2.0 <1.0").is_err());
+        assert!(VersionRange::parse(">=").is_err());
+    }
+
+    #[test]
+    fn test_is_dynamic() {
+        assert!(VersionRange::is_dynamic("[1.2,2.0)"));
+        assert!(VersionRange::is_dynamic("1.+"));
+        assert!(VersionRange::is_dynamic("latest.release"));
+        assert!(!VersionRange::is_dynamic("1.2.3"));
+    }
+
+    #[test]
+    fn test_exact_matches_equivalent_versions() {
+        let soft = VersionRange::parse("1.0").unwrap();
+        assert!(soft.matches("1.0.0", VersionStability::Stable));
+        assert!(soft.matches("1.0", VersionStability::Stable));
+        assert!(!soft.matches("1.0.1", VersionStability::Stable));
+
+        let pinned = VersionRange::parse("[1.0]").unwrap();
+        assert!(pinned.matches("1.0.0", VersionStability::Stable));
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        assert!(VersionRange::parse("[2.0,1.0]").is_err());
+        assert!(VersionRange::parse("[1.0,2.0").is_err());
+        assert!(VersionRange::parse("(1.0)").is_err());
+        assert!(VersionRange::parse("[1.0,2.0],").is_err());
+        assert!(VersionRange::parse("").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for range in ["[1.2,2.0)", "(,1.5]", "[1.0]", "(,1.0],[1.2,)", "1.+"] {
+            assert_eq!(VersionRange::parse(range).unwrap().to_string(), range);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::VersionStability;
+
+/// Environment variable pointing at a stability rules file
+pub const STABILITY_RULES_ENV: &str = "MAVEN_MCP_STABILITY_RULES";
+
+#[derive(Error, Debug)]
+pub enum RulesError {
+    #[error("Failed to read stability rules from {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid stability rules file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid stability rule pattern '{pattern}': {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+}
+
+/// A single rule as written in the rules file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleDefinition {
+    /// Regular expression matched against the version string
+    pub pattern: String,
+    /// Stability assigned to versions matching the pattern
+    pub stability: VersionStability,
+    /// Optional human readable explanation of the rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// On-disk layout of a stability rules file
+///
+/// ```toml
+/// [[rules]]
+/// pattern = "(?i)-(eap|dev|preview)"
+/// stability = "beta"
+///
+/// [[groups."com.google.guava"]]
+/// pattern = "-(jre|android)$"
+/// stability = "stable"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RulesFile {
+    #[serde(default)]
+    pub rules: Vec<RuleDefinition>,
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<RuleDefinition>>,
+}
+
+impl RulesFile {
+    /// Read a stability rules file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| RulesError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Number of rules in the file
+    pub fn len(&self) -> usize {
+        self.rules.len() + self.groups.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A compiled stability rule
+#[derive(Debug, Clone)]
+pub struct StabilityRule {
+    pub definition: RuleDefinition,
+    regex: Regex,
+}
+
+impl StabilityRule {
+    pub fn new(definition: RuleDefinition) -> Result<Self, RulesError> {
+        let regex =
+            Regex::new(&definition.pattern).map_err(|source| RulesError::InvalidPattern {
+                pattern: definition.pattern.clone(),
+                source,
+            })?;
+        Ok(StabilityRule { definition, regex })
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        self.regex.is_match(version)
+    }
+}
+
+/// Where a stability classification came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum RuleSource {
+    /// A per-groupId override from the rules file
+    Group {
+        group: String,
+        pattern: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// A global rule from the rules file
+    Custom {
+        pattern: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// One of the built-in heuristics
+    Builtin { rule: String },
+}
+
+/// Result of classifying a version, including the rule that decided it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StabilityClassification {
+    pub stability: VersionStability,
+    pub source: RuleSource,
+}
+
+/// User-configurable stability classification rules
+///
+/// Rules are evaluated in order: per-groupId overrides (most specific group
+/// first), then global rules, then the built-in heuristics of
+/// [`VersionStability::classify`].
+#[derive(Debug, Clone, Default)]
+pub struct StabilityRules {
+    global: Vec<StabilityRule>,
+    groups: Vec<(String, Vec<StabilityRule>)>,
+}
+
+impl StabilityRules {
+    /// Rules that only use the built-in heuristics
+    pub fn builtin() -> Self {
+        Self::default()
+    }
+
+    /// Compile rules from their file representation
+    pub fn from_file(file: RulesFile) -> Result<Self, RulesError> {
+        let global = file
+            .rules
+            .into_iter()
+            .map(StabilityRule::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut groups = file
+            .groups
+            .into_iter()
+            .map(|(group, rules)| {
+                let rules = rules
+                    .into_iter()
+                    .map(StabilityRule::new)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((group, rules))
+            })
+            .collect::<Result<Vec<_>, RulesError>>()?;
+        // Most specific groupId wins
+        groups.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(StabilityRules { global, groups })
+    }
+
+    /// Parse rules from TOML content
+    pub fn from_toml(content: &str) -> Result<Self, RulesError> {
+        Self::from_file(toml::from_str(content)?)
+    }
+
+    /// Number of configured (non built-in) rules
+    pub fn len(&self) -> usize {
+        self.global.len() + self.groups.iter().map(|(_, r)| r.len()).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Classify a version of an artifact in the given group
+    pub fn classify(&self, group_id: &str, version: &str) -> VersionStability {
+        self.explain(group_id, version).stability
+    }
+
+    /// Classify a version and report which rule decided the result
+    pub fn explain(&self, group_id: &str, version: &str) -> StabilityClassification {
+        for (group, rules) in &self.groups {
+            if !group_matches(group, group_id) {
+                continue;
+            }
+            if let Some(rule) = rules.iter().find(|r| r.matches(version)) {
+                return StabilityClassification {
+                    stability: rule.definition.stability,
+                    source: RuleSource::Group {
+                        group: group.clone(),
+                        pattern: rule.definition.pattern.clone(),
+                        description: rule.definition.description.clone(),
+                    },
+                };
+            }
+        }
+
+        if let Some(rule) = self.global.iter().find(|r| r.matches(version)) {
+            return StabilityClassification {
+                stability: rule.definition.stability,
+                source: RuleSource::Custom {
+                    pattern: rule.definition.pattern.clone(),
+                    description: rule.definition.description.clone(),
+                },
+            };
+        }
+
+        let (stability, rule) = VersionStability::classify_builtin(version);
+        StabilityClassification {
+            stability,
+            source: RuleSource::Builtin {
+                rule: rule.to_string(),
+            },
+        }
+    }
+}
+
+/// A group key matches its own groupId and any groupId nested below it
+fn group_matches(key: &str, group_id: &str) -> bool {
+    group_id == key
+        || (group_id.starts_with(key) && group_id.as_bytes().get(key.len()) == Some(&b'.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+[[rules]]
+pattern = "(?i)-(eap|dev|preview)"
+stability = "beta"
+description = "Early access builds"
+
+[[groups."com.google"]]
+pattern = "-android$"
+stability = "alpha"
+
+[[groups."com.google.guava"]]
+pattern = "-(jre|android)$"
+stability = "stable"
+"#;
+
+    #[test]
+    fn test_builtin_fallback() {
+        let rules = StabilityRules::builtin();
+        let result = rules.explain("org.example", "1.0.0-RC1");
+        assert_eq!(result.stability, VersionStability::RC);
+        assert!(matches!(result.source, RuleSource::Builtin { .. }));
+    }
+
+    #[test]
+    fn test_global_rule() {
+        let rules = StabilityRules::from_toml(RULES).unwrap();
+        assert_eq!(
+            rules.classify("org.jetbrains", "2.0.0-EAP"),
+            VersionStability::Beta
+        );
+        assert_eq!(
+            rules.classify("org.jetbrains", "2.0.0-dev-123"),
+            VersionStability::Beta
+        );
+        assert_eq!(
+            rules.classify("org.jetbrains", "2.0.0"),
+            VersionStability::Stable
+        );
+    }
+
+    #[test]
+    fn test_group_override_most_specific_wins() {
+        let rules = StabilityRules::from_toml(RULES).unwrap();
+        let result = rules.explain("com.google.guava", "33.0.0-android");
+        assert_eq!(result.stability, VersionStability::Stable);
+        assert!(
+            matches!(result.source, RuleSource::Group { ref group, .. } if group == "com.google.guava")
+        );
+
+        assert_eq!(
+            rules.classify("com.google.errorprone", "1.0-android"),
+            VersionStability::Alpha
+        );
+        assert_eq!(
+            rules.classify("com.googlex", "1.0-android"),
+            VersionStability::Stable
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let err = StabilityRules::from_toml("[[rules]]\npattern = \"(\"\nstability = \"beta\"\n");
+        assert!(matches!(err, Err(RulesError::InvalidPattern { .. })));
+    }
+}
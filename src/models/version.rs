@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
@@ -17,19 +18,25 @@ pub enum VersionStability {
 impl VersionStability {
     /// Classify a version string into a stability category
     pub fn classify(version: &str) -> Self {
+        Self::classify_builtin(version).0
+    }
+
+    /// Classify a version string using the built-in heuristics, returning the
+    /// name of the heuristic that matched
+    pub fn classify_builtin(version: &str) -> (Self, &'static str) {
         let v = version.to_uppercase();
 
         if v.contains("SNAPSHOT") {
-            return VersionStability::Snapshot;
+            return (VersionStability::Snapshot, "snapshot qualifier");
         }
         if v.contains("ALPHA") || v.contains("-A.") || v.ends_with("-A") {
-            return VersionStability::Alpha;
+            return (VersionStability::Alpha, "alpha qualifier");
         }
         if v.contains("BETA") || v.contains("-B.") || v.ends_with("-B") {
-            return VersionStability::Beta;
+            return (VersionStability::Beta, "beta qualifier");
         }
         if v.contains("-RC") || v.contains(".RC") || v.contains("-CR") || v.contains(".CR") {
-            return VersionStability::RC;
+            return (VersionStability::RC, "RC/CR qualifier");
         }
         if v.contains("-M") || v.contains(".M") {
             // Check if it's actually a milestone (M followed by number)
@@ -39,11 +46,11 @@ impl VersionStability {
                     part.starts_with('M') && part.len() > 1 && part[1..].chars().all(|c| c.is_ascii_digit())
                 });
             if has_milestone {
-                return VersionStability::Milestone;
+                return (VersionStability::Milestone, "milestone qualifier");
             }
         }
 
-        VersionStability::Stable
+        (VersionStability::Stable, "no pre-release qualifier")
     }
 
     /// Returns true if this stability level is considered production-ready
@@ -77,11 +84,16 @@ impl std::fmt::Display for VersionStability {
     }
 }
 
-/// A parsed Maven version with comparison capabilities
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A parsed Maven version with comparison capabilities.
+///
+/// Equality follows the version order, so "1.0" equals "1.0.0" even though
+/// their `original` strings differ.
+#[derive(Debug, Clone)]
 pub struct MavenVersion {
     pub original: String,
     pub stability: VersionStability,
+    /// Suffix "stream" such as `jre`, `android` or `jakarta`; `None` for the default stream
+    pub stream: Option<String>,
     parts: Vec<VersionPart>,
 }
 
@@ -99,10 +111,37 @@ impl MavenVersion {
         MavenVersion {
             original: version.to_string(),
             stability,
+            stream: Self::detect_stream(version),
             parts,
         }
     }
 
+    /// Detect the suffix stream of a version (e.g. "31.0-jre" -> "jre").
+    ///
+    /// Stability qualifiers (`-RC1`, `.M2`, `-beta`) and release markers
+    /// (`.Final`, `.GA`) are not streams, so "5.6.15.Final" and "6.0.0.CR1"
+    /// both belong to the default stream.
+    pub fn detect_stream(version: &str) -> Option<String> {
+        let suffix = version
+            .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+            .trim_start_matches(['.', '-', '_']);
+
+        let tokens: Vec<String> = suffix
+            .split(['.', '-', '_'])
+            .map(|t| t.to_lowercase())
+            .filter(|t| {
+                let head = t.trim_end_matches(|c: char| c.is_ascii_digit());
+                !head.is_empty() && !is_qualifier(head) && !is_release_marker(head)
+            })
+            .collect();
+
+        if tokens.is_empty() {
+            None
+        } else {
+            Some(tokens.join("-"))
+        }
+    }
+
     fn parse_parts(version: &str) -> Vec<VersionPart> {
         let mut parts = Vec::new();
         let mut current = String::new();
@@ -147,6 +186,17 @@ impl MavenVersion {
         parts
     }
 
+    /// Leading numeric components of the version (e.g. [1, 2, 3] for "1.2.3-RC1")
+    pub fn release_numbers(&self) -> Vec<u64> {
+        self.parts
+            .iter()
+            .map_while(|p| match p {
+                VersionPart::Number(n) => Some(*n),
+                VersionPart::String(_) => None,
+            })
+            .collect()
+    }
+
     /// Compare two versions, returning the relationship
     pub fn compare(&self, other: &MavenVersion) -> Ordering {
         // Compare version parts
@@ -183,6 +233,10 @@ impl MavenVersion {
                     }
                     return Ordering::Greater;
                 }
+                // Trailing zeros are insignificant (1.0 == 1.0.0)
+                (Some(VersionPart::Number(0)), None) | (None, Some(VersionPart::Number(0))) => {
+                    continue
+                }
                 (Some(_), None) => {
                     // Having more parts usually means newer, unless it's a qualifier
                     if let Some(VersionPart::String(s)) = self_part {
@@ -215,6 +269,14 @@ fn is_qualifier(s: &str) -> bool {
     )
 }
 
+/// Qualifiers that mark a release or pre-release rather than a version stream
+fn is_release_marker(s: &str) -> bool {
+    matches!(
+        s,
+        "final" | "ga" | "release" | "sp" | "ea" | "eap" | "preview" | "pre" | "dev"
+    )
+}
+
 fn compare_qualifiers(a: &str, b: &str) -> Ordering {
     let priority = |s: &str| -> i32 {
         match s.to_lowercase().as_str() {
@@ -243,58 +305,212 @@ impl PartialOrd for MavenVersion {
     }
 }
 
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+/// Numbering scheme used by a version string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionScheme {
+    /// major.minor.patch style numbering
+    Semantic,
+    /// Calendar versioning with a leading year, e.g. "2024.1.2"
+    CalVer,
+    /// Date or timestamp versions, e.g. "20240303" or "v20231129-1"
+    Timestamp,
+}
+
+impl VersionScheme {
+    /// Detect the numbering scheme of a version string
+    pub fn detect(version: &str) -> Self {
+        let v = version.trim_start_matches(['v', 'V']);
+        let first: String = v.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+        match first.len() {
+            8 | 12 | 14 if parse_date(&first[..4], &first[4..6], &first[6..8]).is_some() => {
+                VersionScheme::Timestamp
+            }
+            4 if is_calendar_year(&first) && v[4..].starts_with(['.', '-']) => {
+                VersionScheme::CalVer
+            }
+            _ => VersionScheme::Semantic,
+        }
+    }
+
+    /// Returns true for calendar and timestamp based schemes
+    pub fn is_calendar(&self) -> bool {
+        matches!(self, VersionScheme::CalVer | VersionScheme::Timestamp)
+    }
+
+    /// Approximate release date encoded in a calendar or timestamp version
+    pub fn release_date(version: &str) -> Option<NaiveDate> {
+        let v = version.trim_start_matches(['v', 'V']);
+        match Self::detect(version) {
+            VersionScheme::Timestamp => parse_date(&v[..4], &v[4..6], &v[6..8]),
+            VersionScheme::CalVer => {
+                let mut numbers = v
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|s| !s.is_empty());
+                let year: i32 = numbers.next()?.parse().ok()?;
+                // The second component is the month when it looks like one
+                let month = numbers
+                    .next()
+                    .and_then(|m| m.parse::<u32>().ok())
+                    .filter(|m| (1..=12).contains(m))
+                    .unwrap_or(1);
+                NaiveDate::from_ymd_opt(year, month, 1)
+            }
+            VersionScheme::Semantic => None,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionScheme::Semantic => write!(f, "semantic"),
+            VersionScheme::CalVer => write!(f, "calver"),
+            VersionScheme::Timestamp => write!(f, "timestamp"),
+        }
+    }
+}
+
+fn is_calendar_year(year: &str) -> bool {
+    year.parse::<u32>()
+        .map(|y| (1990..=2100).contains(&y))
+        .unwrap_or(false)
+}
+
+fn parse_date(year: &str, month: &str, day: &str) -> Option<NaiveDate> {
+    if !is_calendar_year(year) {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
 /// Determines the type of update between two versions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum UpdateType {
+    /// Both versions are equal
+    None,
+    /// The target is older than the current version
+    Downgrade,
     Major,
     Minor,
     Patch,
+    /// Only the fourth or later numeric component changed (e.g. 1.0.0 -> 1.0.0.1)
+    Build,
+    /// Same release numbers, moving from a pre-release to its release (e.g. 1.0-RC1 -> 1.0)
+    #[serde(rename = "prerelease_to_release")]
+    PrereleaseToRelease,
+    /// Calendar-versioned update released less than a month later
+    #[serde(rename = "calendar_days")]
+    CalendarDays,
+    /// Calendar-versioned update released less than a year later
+    #[serde(rename = "calendar_months")]
+    CalendarMonths,
+    /// Calendar-versioned update released a year or more later
+    #[serde(rename = "calendar_years")]
+    CalendarYears,
     Other,
 }
 
 impl UpdateType {
-    /// Determine update type between current and target version
+    /// Determine update type between current and target version, using the
+    /// built-in stability classification
     pub fn between(current: &str, target: &str) -> Self {
-        let current_parts = Self::extract_numeric_parts(current);
-        let target_parts = Self::extract_numeric_parts(target);
+        Self::classified_between(current, target, |v| MavenVersion::parse(v).stability)
+    }
 
-        if current_parts.is_empty() || target_parts.is_empty() {
-            return UpdateType::Other;
+    /// Determine update type between current and target version, telling
+    /// pre-releases from releases with `classify`, e.g. configured rules
+    pub fn classified_between(
+        current: &str,
+        target: &str,
+        classify: impl Fn(&str) -> VersionStability,
+    ) -> Self {
+        let current_parsed = MavenVersion::parse(current);
+        let target_parsed = MavenVersion::parse(target);
+
+        match current_parsed.cmp(&target_parsed) {
+            Ordering::Equal => return UpdateType::None,
+            Ordering::Greater => return UpdateType::Downgrade,
+            Ordering::Less => {}
         }
 
-        let current_major = current_parts.first().copied().unwrap_or(0);
-        let target_major = target_parts.first().copied().unwrap_or(0);
+        let current_scheme = VersionScheme::detect(current);
+        let target_scheme = VersionScheme::detect(target);
 
-        if target_major != current_major {
-            return UpdateType::Major;
+        if current_scheme.is_calendar() || target_scheme.is_calendar() {
+            return match Self::elapsed_days(current, target) {
+                Some(days) if days < 31 => UpdateType::CalendarDays,
+                Some(days) if days < 365 => UpdateType::CalendarMonths,
+                Some(_) => UpdateType::CalendarYears,
+                None => UpdateType::Other,
+            };
         }
 
-        let current_minor = current_parts.get(1).copied().unwrap_or(0);
-        let target_minor = target_parts.get(1).copied().unwrap_or(0);
+        let current_numbers = current_parsed.release_numbers();
+        let target_numbers = target_parsed.release_numbers();
 
-        if target_minor != current_minor {
-            return UpdateType::Minor;
+        if current_numbers.is_empty() || target_numbers.is_empty() {
+            return UpdateType::Other;
         }
 
-        UpdateType::Patch
+        // Compare release numbers, treating missing components as zero
+        let len = current_numbers.len().max(target_numbers.len());
+        let changed = (0..len).find(|&i| {
+            current_numbers.get(i).copied().unwrap_or(0)
+                != target_numbers.get(i).copied().unwrap_or(0)
+        });
+
+        match changed {
+            Some(0) => UpdateType::Major,
+            Some(1) => UpdateType::Minor,
+            Some(2) => UpdateType::Patch,
+            Some(_) => UpdateType::Build,
+            None if !classify(current).is_stable() && classify(target).is_stable() => {
+                UpdateType::PrereleaseToRelease
+            }
+            None => UpdateType::Other,
+        }
     }
 
-    fn extract_numeric_parts(version: &str) -> Vec<u64> {
-        version
-            .split(|c: char| !c.is_ascii_digit())
-            .filter(|s| !s.is_empty())
-            .filter_map(|s| s.parse().ok())
-            .collect()
+    /// Days between the release dates encoded in two calendar versions
+    pub fn elapsed_days(current: &str, target: &str) -> Option<i64> {
+        let current = VersionScheme::release_date(current)?;
+        let target = VersionScheme::release_date(target)?;
+        Some((target - current).num_days().abs())
+    }
+
+    /// Returns true for updates between calendar-versioned releases
+    pub fn is_calendar(&self) -> bool {
+        matches!(
+            self,
+            UpdateType::CalendarDays | UpdateType::CalendarMonths | UpdateType::CalendarYears
+        )
     }
 }
 
 impl std::fmt::Display for UpdateType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            UpdateType::None => write!(f, "none"),
+            UpdateType::Downgrade => write!(f, "downgrade"),
             UpdateType::Major => write!(f, "major"),
             UpdateType::Minor => write!(f, "minor"),
             UpdateType::Patch => write!(f, "patch"),
+            UpdateType::Build => write!(f, "build"),
+            UpdateType::PrereleaseToRelease => write!(f, "prerelease_to_release"),
+            UpdateType::CalendarDays => write!(f, "calendar_days"),
+            UpdateType::CalendarMonths => write!(f, "calendar_months"),
+            UpdateType::CalendarYears => write!(f, "calendar_years"),
             UpdateType::Other => write!(f, "other"),
         }
     }
@@ -303,6 +519,7 @@ impl std::fmt::Display for UpdateType {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeSet;
 
     #[test]
     fn test_stability_classification() {
@@ -342,11 +559,93 @@ mod tests {
         assert!(v1 < v2);
     }
 
+    #[test]
+    fn test_equality_matches_ordering() {
+        let short = MavenVersion::parse("1.0");
+        let long = MavenVersion::parse("1.0.0");
+        assert_eq!(short.cmp(&long), Ordering::Equal);
+        assert_eq!(short, long);
+        assert_ne!(short, MavenVersion::parse("1.0.1"));
+        assert_ne!(short, MavenVersion::parse("1.0-RC1"));
+
+        let versions: BTreeSet<MavenVersion> = ["1.0", "1.0.0", "1.1"]
+            .into_iter()
+            .map(MavenVersion::parse)
+            .collect();
+        assert_eq!(versions.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_stream() {
+        assert_eq!(
+            MavenVersion::detect_stream("31.0-jre"),
+            Some("jre".to_string())
+        );
+        assert_eq!(
+            MavenVersion::detect_stream("33.0.0-android"),
+            Some("android".to_string())
+        );
+        assert_eq!(
+            MavenVersion::detect_stream("4.0.0-M1-jakarta"),
+            Some("jakarta".to_string())
+        );
+        assert_eq!(MavenVersion::detect_stream("5.6.15.Final"), None);
+        assert_eq!(MavenVersion::detect_stream("6.0.0.CR1"), None);
+        assert_eq!(MavenVersion::detect_stream("2.0.0-RC1"), None);
+        assert_eq!(MavenVersion::detect_stream("1.0.0-SNAPSHOT"), None);
+        assert_eq!(MavenVersion::detect_stream("2.12.7.1"), None);
+    }
+
     #[test]
     fn test_update_type() {
         assert_eq!(UpdateType::between("1.0.0", "2.0.0"), UpdateType::Major);
         assert_eq!(UpdateType::between("1.0.0", "1.1.0"), UpdateType::Minor);
         assert_eq!(UpdateType::between("1.0.0", "1.0.1"), UpdateType::Patch);
-        assert_eq!(UpdateType::between("1.0.0", "1.0.0"), UpdateType::Patch);
+        assert_eq!(UpdateType::between("1.0.0", "1.0.0"), UpdateType::None);
+        assert_eq!(UpdateType::between("1.0", "1.0.0"), UpdateType::None);
+        assert_eq!(UpdateType::between("2.0.0", "1.9.0"), UpdateType::Downgrade);
+        assert_eq!(UpdateType::between("1.0.0", "1.0.0.1"), UpdateType::Build);
+        assert_eq!(
+            UpdateType::between("1.0-RC1", "1.0"),
+            UpdateType::PrereleaseToRelease
+        );
+        assert_eq!(UpdateType::between("1.0-RC1", "1.0-RC2"), UpdateType::Other);
+        // Custom rules decide what counts as a pre-release
+        assert_eq!(
+            UpdateType::classified_between("1.0-RC1", "1.0", |_| VersionStability::Stable),
+            UpdateType::Other
+        );
+        assert_eq!(UpdateType::between("1.0.0-RC1", "1.1.0"), UpdateType::Minor);
+    }
+
+    #[test]
+    fn test_version_scheme() {
+        assert_eq!(VersionScheme::detect("6.1.0"), VersionScheme::Semantic);
+        assert_eq!(VersionScheme::detect("2.12.7.1"), VersionScheme::Semantic);
+        assert_eq!(VersionScheme::detect("2024.1.2"), VersionScheme::CalVer);
+        assert_eq!(VersionScheme::detect("20240303"), VersionScheme::Timestamp);
+        assert_eq!(
+            VersionScheme::detect("v20231129-1"),
+            VersionScheme::Timestamp
+        );
+        assert_eq!(VersionScheme::detect("12345678"), VersionScheme::Semantic);
+    }
+
+    #[test]
+    fn test_calendar_update_type() {
+        assert_eq!(
+            UpdateType::between("20240303", "20240310"),
+            UpdateType::CalendarDays
+        );
+        assert_eq!(
+            UpdateType::between("2024.1.2", "2024.6.0"),
+            UpdateType::CalendarMonths
+        );
+        assert_eq!(
+            UpdateType::between("v20211129-1", "v20231129-1"),
+            UpdateType::CalendarYears
+        );
+        assert_eq!(UpdateType::between("2.12.7", "2.12.7.1"), UpdateType::Build);
+        assert_eq!(UpdateType::between("2.12.7", "20240303"), UpdateType::Other);
     }
 }
@@ -0,0 +1,223 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::report::{dependency_source, health_sections};
+use crate::tools::responses::ProjectHealthResponse;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 72rem; padding: 0 1rem; color: #1f2328; }
+h1 { display: flex; align-items: center; gap: 0.75rem; }
+.grade { display: inline-block; min-width: 2.5rem; padding: 0.25rem 0.5rem; border-radius: 0.5rem; color: #fff; text-align: center; }
+.grade-A { background: #1a7f37; } .grade-B { background: #4c9a2a; } .grade-C { background: #bf8700; }
+.grade-D { background: #d1600b; } .grade-F { background: #cf222e; }
+.summary { display: flex; gap: 1rem; flex-wrap: wrap; margin: 1rem 0; }
+.summary div { border: 1px solid #d0d7de; border-radius: 0.5rem; padding: 0.5rem 1rem; text-align: center; }
+.summary strong { display: block; font-size: 1.5rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1.5rem; }
+th, td { border-bottom: 1px solid #d0d7de; padding: 0.4rem 0.6rem; text-align: left; }
+th { cursor: pointer; user-select: none; background: #f6f8fa; }
+th[data-order="asc"]::after { content: " \25B2"; } th[data-order="desc"]::after { content: " \25BC"; }
+td.number { text-align: right; }
+code { font-size: 0.9em; }
+footer { color: #656d76; font-size: 0.85rem; }
+"#;
+
+/// Sorts a table by the clicked column, numerically where both values are numbers
+const SCRIPT: &str = r#"
+document.querySelectorAll("th").forEach(function (th) {
+  th.addEventListener("click", function () {
+    var table = th.closest("table");
+    var body = table.tBodies[0];
+    var index = Array.prototype.indexOf.call(th.parentNode.children, th);
+    var ascending = th.dataset.order !== "asc";
+    table.querySelectorAll("th").forEach(function (other) { delete other.dataset.order; });
+    th.dataset.order = ascending ? "asc" : "desc";
+    var key = function (row) {
+      var cell = row.cells[index];
+      return cell.dataset.sort !== undefined ? cell.dataset.sort : cell.textContent;
+    };
+    var rows = Array.prototype.slice.call(body.rows).sort(function (a, b) {
+      var x = key(a), y = key(b);
+      var order = isNaN(x) || isNaN(y)
+        ? x.localeCompare(y, undefined, { numeric: true })
+        : parseFloat(x) - parseFloat(y);
+      return ascending ? order : -order;
+    });
+    rows.forEach(function (row) { body.appendChild(row); });
+  });
+});
+"#;
+
+/// A standalone HTML health report with a sortable table for each
+/// classification. It needs no external assets.
+pub fn health_html(
+    title: &str,
+    health: &ProjectHealthResponse,
+    generated: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+    let title = escape(title);
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1><span class=\"grade grade-{grade}\">{grade}</span> {title}</h1>\n\
+         <p>Health score <strong>{score:.1}</strong>/100 across {total} dependencies</p>\n",
+        grade = health.health_grade,
+        score = health.health_score,
+        total = health.total_dependencies,
+    );
+
+    let summary = &health.summary;
+    out.push_str("<section class=\"summary\">\n");
+    for (label, count) in [
+        ("Current", summary.current),
+        ("Fresh", summary.fresh),
+        ("Aging", summary.aging),
+        ("Stale", summary.stale),
+        ("Outdated", summary.outdated),
+        ("Errors", summary.errors),
+    ] {
+        let _ = writeln!(out, "<div><strong>{}</strong>{}</div>", count, label);
+    }
+    out.push_str("</section>\n");
+
+    if !health.recommendations.is_empty() {
+        out.push_str("<h2>Recommendations</h2>\n<ul>\n");
+        for recommendation in &health.recommendations {
+            let _ = writeln!(out, "<li>{}</li>", escape(recommendation));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    for (section, members) in health_sections(health) {
+        let _ = writeln!(out, "<h2>{} ({})</h2>", section, members.len());
+        if section == "Errors" {
+            out.push_str(
+                "<table>\n<thead><tr><th>Dependency</th><th>Version</th><th>Error</th></tr></thead>\n<tbody>\n",
+            );
+            for d in members {
+                let _ = writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                    escape(&d.dependency),
+                    text(d.current_version.as_deref()),
+                    text(d.error.as_deref())
+                );
+            }
+        } else {
+            out.push_str(
+                "<table>\n<thead><tr><th>Dependency</th><th>Version</th><th>Latest</th>\
+                 <th>Update</th><th>Score</th><th>Source</th></tr></thead>\n<tbody>\n",
+            );
+            for d in members {
+                let source = dependency_source(d);
+                let _ = writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td>\
+                     <td class=\"number\" data-sort=\"{score}\">{score:.0}</td><td>{}</td></tr>",
+                    escape(&d.dependency),
+                    text(d.current_version.as_deref()),
+                    text(d.latest_version.as_deref()),
+                    text(d.update_type.map(|u| u.to_string()).as_deref()),
+                    escape(&source),
+                    score = d.health_score,
+                );
+            }
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    if !health.warnings.is_empty() {
+        out.push_str("<h2>Warnings</h2>\n<ul>\n");
+        for warning in &health.warnings {
+            let _ = writeln!(out, "<li>{}</li>", escape(warning));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    let _ = write!(
+        out,
+        "<footer>Generated by {} {} on {}</footer>\n<script>{}</script>\n</body>\n</html>\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        generated.format("%Y-%m-%d %H:%M UTC"),
+        SCRIPT
+    );
+    out
+}
+
+/// Escaped text, with "-" for missing values
+fn text(value: Option<&str>) -> String {
+    value.map(escape).unwrap_or_else(|| "-".to_string())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::responses::{
+        AgeClassification, DependencyHealthResult, HealthGrade, HealthSummary, SourceLocation,
+    };
+
+    #[test]
+    fn test_health_html() {
+        let health = ProjectHealthResponse {
+            total_dependencies: 1,
+            health_score: 50.0,
+            health_grade: HealthGrade::F,
+            summary: HealthSummary {
+                current: 0,
+                fresh: 0,
+                aging: 0,
+                stale: 1,
+                outdated: 0,
+                errors: 0,
+            },
+            dependencies: vec![DependencyHealthResult {
+                dependency: "com.acme:<lib>".to_string(),
+                current_version: Some("1.0".to_string()),
+                latest_version: Some("1.9".to_string()),
+                age_classification: Some(AgeClassification::Stale),
+                health_score: 50.0,
+                update_type: None,
+                error: None,
+                location: Some(SourceLocation {
+                    file: "pom.xml".to_string(),
+                    line: 7,
+                }),
+                introduced_by: None,
+            }],
+            recommendations: vec!["Plan upgrades soon.".to_string()],
+            warnings: Vec::new(),
+        };
+        let generated = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let html = health_html("Release 1.2 & co", &health, generated);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Release 1.2 &amp; co</title>"));
+        assert!(html.contains("<span class=\"grade grade-F\">F</span>"));
+        assert!(html.contains("<h2>Stale (1)</h2>"));
+        assert!(html.contains("<code>com.acme:&lt;lib&gt;</code>"));
+        assert!(html.contains("data-sort=\"50\">50</td><td>pom.xml:7</td>"));
+        assert!(html.contains("on 2024-05-01 10:00 UTC"));
+        assert!(!html.contains("<h2>Errors"));
+    }
+}
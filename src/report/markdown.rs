@@ -0,0 +1,174 @@
+use crate::report::{dependency_source, health_sections};
+use crate::tools::responses::{DependencyHealthResult, HealthGrade, ProjectHealthResponse};
+
+/// How many recommendations the summary lists
+const TOP_RECOMMENDATIONS: usize = 5;
+
+/// A Markdown health summary: grade badge, counts per classification,
+/// top recommendations and a table for each classification
+pub fn health_markdown(title: &str, health: &ProjectHealthResponse) -> String {
+    let mut out = format!("## {}\n\n", escape(title));
+    out.push_str(&format!(
+        "![Dependency health {grade}]({url})\n\n**Grade {grade}** · score {score:.1}/100 · {total} dependencies\n\n",
+        grade = health.health_grade,
+        url = badge_url(health.health_grade, health.health_score),
+        score = health.health_score,
+        total = health.total_dependencies,
+    ));
+
+    let summary = &health.summary;
+    out.push_str("| Current | Fresh | Aging | Stale | Outdated | Errors |\n");
+    out.push_str("|--------:|------:|------:|------:|---------:|-------:|\n");
+    out.push_str(&format!(
+        "| {} | {} | {} | {} | {} | {} |\n\n",
+        summary.current,
+        summary.fresh,
+        summary.aging,
+        summary.stale,
+        summary.outdated,
+        summary.errors
+    ));
+
+    if !health.recommendations.is_empty() {
+        out.push_str("### Recommendations\n\n");
+        for recommendation in health.recommendations.iter().take(TOP_RECOMMENDATIONS) {
+            out.push_str(&format!("- {}\n", escape(recommendation)));
+        }
+        out.push('\n');
+    }
+
+    for (title, members) in health_sections(health) {
+        out.push_str(&format!("### {} ({})\n\n", title, members.len()));
+        if title == "Errors" {
+            out.push_str("| Dependency | Version | Error |\n|---|---|---|\n");
+            for d in members {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    d.dependency,
+                    cell(d.current_version.as_deref()),
+                    cell(d.error.as_deref())
+                ));
+            }
+        } else {
+            out.push_str("| Dependency | Version | Latest | Update | Score | Source |\n");
+            out.push_str("|---|---|---|---|---:|---|\n");
+            for d in members {
+                out.push_str(&row(d));
+            }
+        }
+        out.push('\n');
+    }
+
+    if !health.warnings.is_empty() {
+        out.push_str("### Warnings\n\n");
+        for warning in &health.warnings {
+            out.push_str(&format!("- {}\n", escape(warning)));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!(
+        "<sub>Generated by {} {}</sub>\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    ));
+    out
+}
+
+fn row(d: &DependencyHealthResult) -> String {
+    let source = dependency_source(d);
+    format!(
+        "| `{}` | {} | {} | {} | {:.0} | {} |\n",
+        d.dependency,
+        cell(d.current_version.as_deref()),
+        cell(d.latest_version.as_deref()),
+        cell(d.update_type.map(|u| u.to_string()).as_deref()),
+        d.health_score,
+        cell(Some(&source).filter(|s| !s.is_empty()).map(String::as_str))
+    )
+}
+
+/// shields.io badge showing the grade and score
+fn badge_url(grade: HealthGrade, score: f32) -> String {
+    let color = match grade {
+        HealthGrade::A => "brightgreen",
+        HealthGrade::B => "green",
+        HealthGrade::C => "yellow",
+        HealthGrade::D => "orange",
+        HealthGrade::F => "red",
+    };
+    format!(
+        "https://img.shields.io/badge/dependency%20health-{}%20({:.0})-{}",
+        grade, score, color
+    )
+}
+
+/// A table cell, with "-" for missing values
+fn cell(value: Option<&str>) -> String {
+    value.map(escape).unwrap_or_else(|| "-".to_string())
+}
+
+/// Keep text from breaking out of a table row
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::responses::{AgeClassification, HealthSummary};
+
+    fn dependency(name: &str, age: AgeClassification, score: f32) -> DependencyHealthResult {
+        DependencyHealthResult {
+            dependency: name.to_string(),
+            current_version: Some("1.0".to_string()),
+            latest_version: Some("2.0".to_string()),
+            age_classification: Some(age),
+            health_score: score,
+            update_type: None,
+            error: None,
+            location: None,
+            introduced_by: None,
+        }
+    }
+
+    #[test]
+    fn test_health_markdown() {
+        let mut broken = dependency("com.acme:gone", AgeClassification::Current, 0.0);
+        broken.age_classification = None;
+        broken.error = Some("HTTP 404 | not found".to_string());
+        let health = ProjectHealthResponse {
+            total_dependencies: 3,
+            health_score: 70.0,
+            health_grade: HealthGrade::C,
+            summary: HealthSummary {
+                current: 1,
+                fresh: 0,
+                aging: 0,
+                stale: 0,
+                outdated: 1,
+                errors: 1,
+            },
+            dependencies: vec![
+                dependency("com.acme:new", AgeClassification::Current, 100.0),
+                dependency("com.acme:old", AgeClassification::Outdated, 40.0),
+                broken,
+            ],
+            recommendations: vec![
+                "1 dependencies are a major version behind. Prioritize these upgrades.".to_string(),
+            ],
+            warnings: Vec::new(),
+        };
+
+        let markdown = health_markdown("Dependency health", &health);
+        assert!(markdown.starts_with("## Dependency health\n"));
+        assert!(markdown.contains("dependency%20health-C%20(70)-yellow"));
+        assert!(markdown.contains("| 1 | 0 | 0 | 0 | 1 | 1 |"));
+        assert!(markdown.contains("- 1 dependencies are a major version behind."));
+        let outdated = markdown.find("### Outdated (1)").unwrap();
+        let current = markdown.find("### Current (1)").unwrap();
+        assert!(outdated < current);
+        assert!(markdown.contains("| `com.acme:old` | 1.0 | 2.0 | - | 40 | - |"));
+        assert!(markdown.contains("| `com.acme:gone` | 1.0 | HTTP 404 \\| not found |"));
+    }
+}
@@ -0,0 +1,79 @@
+pub mod html;
+pub mod markdown;
+pub mod sarif;
+pub mod sbom;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::responses::{AgeClassification, DependencyHealthResult, ProjectHealthResponse};
+
+pub use sbom::{ComponentHash, HashAlgorithm, SbomComponent, SbomFormat};
+
+/// How an analysis tool returns its results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    /// The tool's JSON response
+    #[default]
+    Json,
+    /// A SARIF 2.1.0 log of stale, outdated and relocated dependencies, for
+    /// code scanning
+    Sarif,
+    /// A Markdown health summary, e.g. for pull request comments
+    Markdown,
+    /// A standalone HTML health report with sortable tables
+    Html,
+}
+
+/// Dependencies of a health report grouped for display, most urgent
+/// first, without empty groups
+pub(crate) fn health_sections(
+    health: &ProjectHealthResponse,
+) -> Vec<(&'static str, Vec<&DependencyHealthResult>)> {
+    let classes = [
+        ("Outdated", AgeClassification::Outdated),
+        ("Stale", AgeClassification::Stale),
+        ("Aging", AgeClassification::Aging),
+        ("Fresh", AgeClassification::Fresh),
+        ("Current", AgeClassification::Current),
+    ];
+    let mut sections: Vec<(&'static str, Vec<&DependencyHealthResult>)> = classes
+        .into_iter()
+        .map(|(title, class)| {
+            let members = health
+                .dependencies
+                .iter()
+                .filter(|d| d.error.is_none() && d.age_classification == Some(class))
+                .collect();
+            (title, members)
+        })
+        .collect();
+    sections.push((
+        "Errors",
+        health
+            .dependencies
+            .iter()
+            .filter(|d| d.error.is_some())
+            .collect(),
+    ));
+    sections.retain(|(_, members)| !members.is_empty());
+    sections
+}
+
+/// Where a dependency was found, e.g. "pom.xml:12 via com.acme:lib:1.0"
+pub(crate) fn dependency_source(dependency: &DependencyHealthResult) -> String {
+    let location = dependency
+        .location
+        .as_ref()
+        .map(|l| format!("{}:{}", l.file, l.line));
+    let via = dependency
+        .introduced_by
+        .as_ref()
+        .map(|d| format!("via {}", d));
+    [location, via]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::config::ScoringConfig;
+use crate::tools::responses::{AgeClassification, DependencyHealthResult};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A check whose findings become SARIF results
+struct Rule {
+    id: &'static str,
+    name: &'static str,
+    description: String,
+    /// How a result message describes the dependency
+    finding: String,
+    help: &'static str,
+    level: &'static str,
+}
+
+/// The checks, described with the configured staleness threshold
+fn rules(scoring: &ScoringConfig) -> [Rule; 3] {
+    [
+        Rule {
+            id: "outdated-dependency",
+            name: "OutdatedDependency",
+            description: "Dependency is a major version behind the latest stable release"
+                .to_string(),
+            finding: "is a major version behind the latest stable release".to_string(),
+            help: "Plan an upgrade to the latest major version; it may require code changes.",
+            level: "error",
+        },
+        Rule {
+            id: "stale-dependency",
+            name: "StaleDependency",
+            description: format!(
+                "Dependency is more than {} stable releases behind",
+                scoring.aging_within
+            ),
+            finding: format!(
+                "is more than {} stable releases behind the latest",
+                scoring.aging_within
+            ),
+            help: "Upgrade to the latest release within the same major version.",
+            level: "warning",
+        },
+        Rule {
+            id: "relocated-dependency",
+            name: "RelocatedDependency",
+            description: "Dependency has been relocated to new Maven coordinates".to_string(),
+            finding: "has been relocated to".to_string(),
+            help: "Replace the dependency with the coordinates its POM relocates it to.",
+            level: "warning",
+        },
+    ]
+}
+
+/// Index of the rule reported for a health classification; current, fresh
+/// and aging dependencies are not findings
+fn age_rule(age: AgeClassification) -> Option<usize> {
+    match age {
+        AgeClassification::Outdated => Some(0),
+        AgeClassification::Stale => Some(1),
+        _ => None,
+    }
+}
+
+/// A SARIF 2.1.0 log with a result for every stale, outdated or relocated
+/// dependency. `relocations` maps groupId:artifactId:version to the
+/// coordinates it was relocated to.
+pub fn sarif(
+    health: &[DependencyHealthResult],
+    relocations: &HashMap<String, String>,
+    scoring: &ScoringConfig,
+) -> Value {
+    let rules = rules(scoring);
+    let mut results = Vec::new();
+    for dependency in health {
+        let Some(version) = &dependency.current_version else {
+            continue;
+        };
+        let coordinate = format!("{}:{}", dependency.dependency, version);
+        let through = dependency
+            .introduced_by
+            .as_ref()
+            .map(|d| format!(", pulled in by {}", d))
+            .unwrap_or_default();
+
+        if let Some(target) = relocations.get(&coordinate) {
+            let message = format!("{} {} {}{}", coordinate, rules[2].finding, target, through);
+            results.push(result(&rules, 2, dependency, message));
+        }
+        if let Some(index) = dependency.age_classification.and_then(age_rule) {
+            let latest = dependency.latest_version.as_deref().unwrap_or("unknown");
+            let message = format!(
+                "{} {} {} {}{}",
+                dependency.dependency, version, rules[index].finding, latest, through
+            );
+            results.push(result(&rules, index, dependency, message));
+        }
+    }
+
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id,
+                "name": rule.name,
+                "shortDescription": { "text": rule.description },
+                "help": { "text": rule.help },
+                "defaultConfiguration": { "level": rule.level },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn result(
+    rules: &[Rule],
+    index: usize,
+    dependency: &DependencyHealthResult,
+    message: String,
+) -> Value {
+    let rule = &rules[index];
+    let mut location = json!({
+        "logicalLocations": [{
+            "name": dependency.dependency,
+            "kind": "package",
+        }],
+    });
+    // Inline content has no file that code scanning could point at
+    if let Some(source) = dependency
+        .location
+        .as_ref()
+        .filter(|l| l.file != "<inline>" && l.line > 0)
+    {
+        location["physicalLocation"] = json!({
+            "artifactLocation": { "uri": artifact_uri(&source.file) },
+            "region": { "startLine": source.line },
+        });
+    }
+
+    let mut properties = json!({ "healthScore": dependency.health_score });
+    if let Some(latest) = &dependency.latest_version {
+        properties["latestVersion"] = json!(latest);
+    }
+    if let Some(age) = dependency.age_classification {
+        properties["ageClassification"] = json!(age);
+    }
+
+    json!({
+        "ruleId": rule.id,
+        "ruleIndex": index,
+        "level": rule.level,
+        "message": { "text": message },
+        "locations": [location],
+        "properties": properties,
+    })
+}
+
+/// URI of a file for `artifactLocation`: relative to the working
+/// directory when it lies below it, so code scanning can match it to the
+/// checkout, and a `file://` URI otherwise
+fn artifact_uri(file: &str) -> String {
+    let path = Path::new(file);
+    if path.is_relative() {
+        return file.replace('\\', "/");
+    }
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    match relative {
+        Some(relative) => relative.to_string_lossy().replace('\\', "/"),
+        None => format!("file://{}", file.replace('\\', "/")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::responses::SourceLocation;
+
+    fn health(
+        dependency: &str,
+        version: &str,
+        age: AgeClassification,
+        line: usize,
+    ) -> DependencyHealthResult {
+        DependencyHealthResult {
+            dependency: dependency.to_string(),
+            current_version: Some(version.to_string()),
+            latest_version: Some("9.0".to_string()),
+            age_classification: Some(age),
+            health_score: 40.0,
+            update_type: None,
+            error: None,
+            location: Some(SourceLocation {
+                file: "pom.xml".to_string(),
+                line,
+            }),
+            introduced_by: None,
+        }
+    }
+
+    #[test]
+    fn test_results_by_classification() {
+        let results = [
+            health("com.acme:old", "1.0", AgeClassification::Outdated, 12),
+            health("com.acme:stale", "8.1", AgeClassification::Stale, 20),
+            health("com.acme:fresh", "8.9", AgeClassification::Fresh, 28),
+        ];
+        let scoring = ScoringConfig {
+            aging_within: 8,
+            ..ScoringConfig::default()
+        };
+        let log = sarif(&results, &HashMap::new(), &scoring);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 3);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "outdated-dependency");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "com.acme:old 1.0 is a major version behind the latest stable release 9.0"
+        );
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "pom.xml");
+        assert_eq!(location["region"]["startLine"], 12);
+        assert_eq!(results[1]["ruleId"], "stale-dependency");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(
+            results[1]["message"]["text"],
+            "com.acme:stale 8.1 is more than 8 stable releases behind the latest 9.0"
+        );
+    }
+
+    #[test]
+    fn test_relocated_and_inline() {
+        let mut result = health(
+            "mysql:mysql-connector-java",
+            "8.0.33",
+            AgeClassification::Current,
+            5,
+        );
+        result.location.as_mut().unwrap().file = "<inline>".to_string();
+        let relocations = HashMap::from([(
+            "mysql:mysql-connector-java:8.0.33".to_string(),
+            "com.mysql:mysql-connector-j:8.0.33".to_string(),
+        )]);
+        let log = sarif(&[result], &relocations, &ScoringConfig::default());
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "relocated-dependency");
+        assert_eq!(results[0]["ruleIndex"], 2);
+        assert!(results[0]["locations"][0].get("physicalLocation").is_none());
+        assert_eq!(
+            results[0]["locations"][0]["logicalLocations"][0]["name"],
+            "mysql:mysql-connector-java"
+        );
+    }
+}
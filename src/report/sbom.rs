@@ -0,0 +1,654 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::build::pom::{PomLicense, PomOrganization};
+use crate::maven::MAVEN_CENTRAL_BASE;
+
+/// Namespace of the properties this server adds to components
+pub const PROPERTY_NAMESPACE: &str = "maven-mcp";
+
+const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// SPDX identifiers of common licenses, matched by the exact (lowercase)
+/// name in the POM or by a fragment of the license URL
+const KNOWN_LICENSES: &[(&str, &[&str], &[&str])] = &[
+    (
+        "Apache-2.0",
+        &[
+            "apache license, version 2.0",
+            "apache license 2.0",
+            "apache 2.0",
+            "apache-2.0",
+            "apache 2",
+            "the apache software license, version 2.0",
+            "the apache license, version 2.0",
+        ],
+        &[
+            "apache.org/licenses/license-2.0",
+            "opensource.org/licenses/apache-2.0",
+        ],
+    ),
+    // Before MIT, whose URL fragment is a prefix of this one
+    (
+        "MIT-0",
+        &["mit-0", "mit no attribution", "mit no attribution license"],
+        &["opensource.org/licenses/mit-0"],
+    ),
+    (
+        "MIT",
+        &["mit", "mit license", "the mit license"],
+        &["opensource.org/licenses/mit"],
+    ),
+    (
+        "BSD-3-Clause",
+        &[
+            "bsd-3-clause",
+            "new bsd license",
+            "bsd 3-clause",
+            "bsd 3-clause license",
+            "the bsd 3-clause license",
+            "eclipse distribution license - v 1.0",
+            "edl 1.0",
+        ],
+        &[
+            "opensource.org/licenses/bsd-3-clause",
+            "eclipse.org/org/documents/edl-v10",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &[
+            "bsd-2-clause",
+            "simplified bsd license",
+            "the bsd 2-clause license",
+        ],
+        &["opensource.org/licenses/bsd-2-clause"],
+    ),
+    (
+        "EPL-1.0",
+        &[
+            "eclipse public license - v 1.0",
+            "eclipse public license 1.0",
+            "epl-1.0",
+        ],
+        &[
+            "eclipse.org/legal/epl-v10",
+            "opensource.org/licenses/epl-1.0",
+        ],
+    ),
+    (
+        "EPL-2.0",
+        &[
+            "eclipse public license - v 2.0",
+            "eclipse public license v2.0",
+            "eclipse public license 2.0",
+            "epl-2.0",
+            "epl 2.0",
+        ],
+        &["eclipse.org/legal/epl-2.0", "eclipse.org/legal/epl-v20"],
+    ),
+    (
+        "LGPL-2.1-only",
+        &[
+            "gnu lesser general public license, version 2.1",
+            "lgpl 2.1",
+            "lgpl-2.1",
+        ],
+        &["gnu.org/licenses/old-licenses/lgpl-2.1"],
+    ),
+    (
+        "MPL-2.0",
+        &["mozilla public license, version 2.0", "mpl 2.0", "mpl-2.0"],
+        &["mozilla.org/mpl/2.0"],
+    ),
+    (
+        "GPL-2.0-with-classpath-exception",
+        &[
+            "gpl2 w/ cpe",
+            "gnu general public license, version 2 with the classpath exception",
+        ],
+        &["openjdk.java.net/legal/gplv2+ce"],
+    ),
+    (
+        "CDDL-1.0",
+        &[
+            "cddl 1.0",
+            "cddl-1.0",
+            "common development and distribution license 1.0",
+        ],
+        &["opensource.org/licenses/cddl-1.0"],
+    ),
+    (
+        "CC0-1.0",
+        &["cc0", "cc0-1.0", "public domain, per creative commons cc0"],
+        &["creativecommons.org/publicdomain/zero/1.0"],
+    ),
+];
+
+/// Document format of a generated SBOM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    #[default]
+    Cyclonedx,
+    /// SPDX 2.3 JSON
+    Spdx,
+}
+
+/// Checksum algorithms Maven repositories publish next to artifacts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Extension of the checksum file, e.g. "sha256" for `a-1.0.jar.sha256`
+    pub fn extension(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn cyclonedx_name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha512 => "SHA-512",
+        }
+    }
+
+    fn spdx_name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha1 => "SHA1",
+            HashAlgorithm::Sha256 => "SHA256",
+            HashAlgorithm::Sha512 => "SHA512",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentHash {
+    pub algorithm: HashAlgorithm,
+    /// Lowercase hex digest
+    pub value: String,
+}
+
+/// A Maven artifact to describe in a generated SBOM
+#[derive(Debug, Clone, Default)]
+pub struct SbomComponent {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    /// POM packaging, e.g. "jar" or "pom"
+    pub packaging: String,
+    pub licenses: Vec<PomLicense>,
+    pub supplier: Option<PomOrganization>,
+    pub hashes: Vec<ComponentHash>,
+    /// groupId:artifactId:version of the components this one depends on
+    pub dependencies: Vec<String>,
+    /// Requested directly rather than resolved as a dependency
+    pub direct: bool,
+    /// Name/value metadata, named without the `maven-mcp:` namespace
+    pub properties: Vec<(String, String)>,
+}
+
+impl SbomComponent {
+    /// groupId:artifactId:version
+    pub fn coordinate(&self) -> String {
+        format!("{}:{}:{}", self.group_id, self.artifact_id, self.version)
+    }
+
+    /// Package URL, e.g. `pkg:maven/com.google.guava/guava@33.0.0-jre`
+    pub fn purl(&self) -> String {
+        let purl = format!(
+            "pkg:maven/{}/{}@{}",
+            self.group_id, self.artifact_id, self.version
+        );
+        match self.packaging.as_str() {
+            "" | "jar" => purl,
+            packaging => format!("{}?type={}", purl, packaging),
+        }
+    }
+
+    /// Extension of the artifact file for the POM packaging
+    pub fn extension(&self) -> &str {
+        artifact_extension(&self.packaging)
+    }
+
+    /// Where the artifact file is published on Maven Central
+    pub fn download_url(&self) -> String {
+        format!(
+            "{}/{}/{}/{}/{}-{}.{}",
+            MAVEN_CENTRAL_BASE,
+            self.group_id.replace('.', "/"),
+            self.artifact_id,
+            self.version,
+            self.artifact_id,
+            self.version,
+            self.extension()
+        )
+    }
+
+    fn property_name(name: &str) -> String {
+        format!("{}:{}", PROPERTY_NAMESPACE, name)
+    }
+}
+
+/// Extension of the file a packaging produces; plugin and bundle
+/// packagings are published as jars
+pub fn artifact_extension(packaging: &str) -> &str {
+    match packaging {
+        "pom" | "war" | "ear" | "aar" | "rar" => packaging,
+        _ => "jar",
+    }
+}
+
+/// SPDX identifier of a POM license, if it is a well-known one
+pub fn spdx_license_id(license: &PomLicense) -> Option<&'static str> {
+    let name = license.name.as_deref().map(|n| n.trim().to_lowercase());
+    let url = license.url.as_deref().map(|u| {
+        u.trim()
+            .to_lowercase()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .to_string()
+    });
+    KNOWN_LICENSES
+        .iter()
+        .find(|(_, names, urls)| {
+            name.as_deref().is_some_and(|n| names.contains(&n))
+                || url
+                    .as_deref()
+                    .is_some_and(|u| urls.iter().any(|fragment| u.starts_with(fragment)))
+        })
+        .map(|(id, _, _)| *id)
+}
+
+/// A CycloneDX 1.5 document. `name` becomes the described application,
+/// depending on every direct component.
+pub fn cyclonedx(name: &str, components: &[SbomComponent], timestamp: DateTime<Utc>) -> Value {
+    let entries: Vec<Value> = components
+        .iter()
+        .map(|component| {
+            let mut entry = json!({
+                "type": "library",
+                "bom-ref": component.purl(),
+                "group": component.group_id,
+                "name": component.artifact_id,
+                "version": component.version,
+                "purl": component.purl(),
+                "externalReferences": [
+                    { "type": "distribution", "url": component.download_url() }
+                ],
+            });
+            if let Some(supplier) = &component.supplier {
+                let mut value = json!({});
+                if let Some(name) = &supplier.name {
+                    value["name"] = json!(name);
+                }
+                if let Some(url) = &supplier.url {
+                    value["url"] = json!([url]);
+                }
+                entry["supplier"] = value;
+            }
+            if !component.hashes.is_empty() {
+                entry["hashes"] = component
+                    .hashes
+                    .iter()
+                    .map(|h| json!({ "alg": h.algorithm.cyclonedx_name(), "content": h.value }))
+                    .collect();
+            }
+            if !component.licenses.is_empty() {
+                entry["licenses"] = component.licenses.iter().map(cyclonedx_license).collect();
+            }
+            if !component.properties.is_empty() {
+                entry["properties"] = component
+                    .properties
+                    .iter()
+                    .map(|(name, value)| {
+                        json!({ "name": SbomComponent::property_name(name), "value": value })
+                    })
+                    .collect();
+            }
+            entry
+        })
+        .collect();
+
+    let purl_of = |coordinate: &str| {
+        components
+            .iter()
+            .find(|c| c.coordinate() == coordinate)
+            .map(SbomComponent::purl)
+    };
+    let root = json!({
+        "ref": name,
+        "dependsOn": components
+            .iter()
+            .filter(|c| c.direct)
+            .map(SbomComponent::purl)
+            .collect::<Vec<_>>(),
+    });
+    let dependencies: Vec<Value> = std::iter::once(root)
+        .chain(components.iter().map(|component| {
+            json!({
+                "ref": component.purl(),
+                "dependsOn": component
+                    .dependencies
+                    .iter()
+                    .filter_map(|d| purl_of(d))
+                    .collect::<Vec<_>>(),
+            })
+        }))
+        .collect();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "tools": {
+                "components": [
+                    { "type": "application", "name": TOOL_NAME, "version": TOOL_VERSION }
+                ]
+            },
+            "component": { "type": "application", "bom-ref": name, "name": name },
+        },
+        "components": entries,
+        "dependencies": dependencies,
+    })
+}
+
+fn cyclonedx_license(license: &PomLicense) -> Value {
+    if let Some(id) = spdx_license_id(license) {
+        return json!({ "license": { "id": id } });
+    }
+    let mut value = json!({
+        "name": license.name.as_deref().or(license.url.as_deref()).unwrap_or("unknown"),
+    });
+    if let Some(url) = &license.url {
+        value["url"] = json!(url);
+    }
+    json!({ "license": value })
+}
+
+/// An SPDX 2.3 document describing every direct component. Health
+/// properties are recorded as annotations, which SPDX has in place of
+/// free-form properties.
+pub fn spdx(name: &str, components: &[SbomComponent], timestamp: DateTime<Utc>) -> Value {
+    let created = timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let annotator = format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION);
+
+    let packages: Vec<Value> = components
+        .iter()
+        .map(|component| {
+            let known: Option<Vec<&str>> =
+                component.licenses.iter().map(spdx_license_id).collect();
+            let license_declared = match known {
+                // Maven lets users choose any of several listed licenses
+                Some(ids) if !ids.is_empty() => ids.join(" OR "),
+                _ => "NOASSERTION".to_string(),
+            };
+            let supplier = component
+                .supplier
+                .as_ref()
+                .and_then(|s| s.name.as_deref())
+                .map(|name| format!("Organization: {}", name))
+                .unwrap_or_else(|| "NOASSERTION".to_string());
+
+            let mut package = json!({
+                "SPDXID": spdx_id(&component.coordinate()),
+                "name": format!("{}:{}", component.group_id, component.artifact_id),
+                "versionInfo": component.version,
+                "supplier": supplier,
+                "downloadLocation": component.download_url(),
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": license_declared,
+                "copyrightText": "NOASSERTION",
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": component.purl(),
+                }],
+            });
+            if license_declared == "NOASSERTION" && !component.licenses.is_empty() {
+                let names: Vec<&str> = component
+                    .licenses
+                    .iter()
+                    .filter_map(|l| l.name.as_deref().or(l.url.as_deref()))
+                    .collect();
+                package["licenseComments"] = json!(format!("Declared in POM: {}", names.join("; ")));
+            }
+            if !component.hashes.is_empty() {
+                package["checksums"] = component
+                    .hashes
+                    .iter()
+                    .map(|h| json!({ "algorithm": h.algorithm.spdx_name(), "checksumValue": h.value }))
+                    .collect();
+            }
+            if !component.properties.is_empty() {
+                package["annotations"] = component
+                    .properties
+                    .iter()
+                    .map(|(name, value)| {
+                        json!({
+                            "annotationType": "OTHER",
+                            "annotator": annotator,
+                            "annotationDate": created,
+                            "comment": format!("{}={}", SbomComponent::property_name(name), value),
+                        })
+                    })
+                    .collect();
+            }
+            package
+        })
+        .collect();
+
+    let mut relationships: Vec<Value> = components
+        .iter()
+        .filter(|c| c.direct)
+        .map(|c| {
+            json!({
+                "spdxElementId": "SPDXRef-DOCUMENT",
+                "relationshipType": "DESCRIBES",
+                "relatedSpdxElement": spdx_id(&c.coordinate()),
+            })
+        })
+        .collect();
+    for component in components {
+        for dependency in &component.dependencies {
+            if components.iter().any(|c| &c.coordinate() == dependency) {
+                relationships.push(json!({
+                    "spdxElementId": spdx_id(&component.coordinate()),
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": spdx_id(dependency),
+                }));
+            }
+        }
+    }
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": name,
+        "documentNamespace": format!(
+            "{}/spdx/{}-{}",
+            env!("CARGO_PKG_REPOSITORY"),
+            spdx_id_part(name),
+            timestamp.format("%Y%m%dT%H%M%SZ")
+        ),
+        "creationInfo": {
+            "created": created,
+            "creators": [annotator],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+/// `SPDXRef-Package-` followed by the coordinate, limited to the
+/// characters SPDX allows in identifiers
+fn spdx_id(coordinate: &str) -> String {
+    format!("SPDXRef-Package-{}", spdx_id_part(coordinate))
+}
+
+fn spdx_id_part(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guava() -> SbomComponent {
+        SbomComponent {
+            group_id: "com.google.guava".to_string(),
+            artifact_id: "guava".to_string(),
+            version: "32.1.2-jre".to_string(),
+            packaging: "bundle".to_string(),
+            licenses: vec![PomLicense {
+                name: Some("Apache License, Version 2.0".to_string()),
+                url: Some("http://www.apache.org/licenses/LICENSE-2.0.txt".to_string()),
+            }],
+            supplier: Some(PomOrganization {
+                name: Some("Google LLC".to_string()),
+                url: Some("http://www.google.com".to_string()),
+            }),
+            hashes: vec![ComponentHash {
+                algorithm: HashAlgorithm::Sha1,
+                value: "5e64ec7e056456bef3a4bc4c6fdaef71e8ab6318".to_string(),
+            }],
+            dependencies: vec!["com.google.guava:failureaccess:1.0.1".to_string()],
+            direct: true,
+            properties: vec![("latest_version".to_string(), "33.0.0-jre".to_string())],
+        }
+    }
+
+    fn failureaccess() -> SbomComponent {
+        SbomComponent {
+            group_id: "com.google.guava".to_string(),
+            artifact_id: "failureaccess".to_string(),
+            version: "1.0.1".to_string(),
+            packaging: "jar".to_string(),
+            licenses: vec![PomLicense {
+                name: Some("Custom License".to_string()),
+                url: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_spdx_license_id() {
+        let license = |name: &str, url: &str| PomLicense {
+            name: Some(name.to_string()),
+            url: Some(url.to_string()),
+        };
+        assert_eq!(
+            spdx_license_id(&license("The Apache Software License, Version 2.0", "")),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            spdx_license_id(&license(
+                "EPL 2.0",
+                "https://www.eclipse.org/legal/epl-2.0/"
+            )),
+            Some("EPL-2.0")
+        );
+        assert_eq!(
+            spdx_license_id(&license("", "https://opensource.org/licenses/MIT-0")),
+            Some("MIT-0")
+        );
+        assert_eq!(spdx_license_id(&license("MIT-0", "")), Some("MIT-0"));
+        assert_eq!(
+            spdx_license_id(&license("", "https://opensource.org/licenses/MIT")),
+            Some("MIT")
+        );
+        assert_eq!(spdx_license_id(&license("Proprietary", "")), None);
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let timestamp = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let bom = cyclonedx("app", &[guava(), failureaccess()], timestamp);
+        assert_eq!(bom["specVersion"], "1.5");
+        let component = &bom["components"][0];
+        assert_eq!(
+            component["purl"],
+            "pkg:maven/com.google.guava/guava@32.1.2-jre?type=bundle"
+        );
+        assert_eq!(component["licenses"][0]["license"]["id"], "Apache-2.0");
+        assert_eq!(component["supplier"]["name"], "Google LLC");
+        assert_eq!(component["hashes"][0]["alg"], "SHA-1");
+        assert_eq!(
+            component["properties"][0]["name"],
+            "maven-mcp:latest_version"
+        );
+        assert_eq!(
+            component["externalReferences"][0]["url"],
+            "https://repo1.maven.org/maven2/com/google/guava/guava/32.1.2-jre/guava-32.1.2-jre.jar"
+        );
+        assert_eq!(
+            bom["components"][1]["licenses"][0]["license"]["name"],
+            "Custom License"
+        );
+        assert_eq!(bom["dependencies"][0]["dependsOn"][0], component["bom-ref"]);
+        assert_eq!(
+            bom["dependencies"][1]["dependsOn"][0],
+            "pkg:maven/com.google.guava/failureaccess@1.0.1"
+        );
+    }
+
+    #[test]
+    fn test_spdx() {
+        let timestamp = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let document = spdx("app", &[guava(), failureaccess()], timestamp);
+        assert_eq!(document["spdxVersion"], "SPDX-2.3");
+        let package = &document["packages"][0];
+        assert_eq!(
+            package["SPDXID"],
+            "SPDXRef-Package-com.google.guava-guava-32.1.2-jre"
+        );
+        assert_eq!(package["licenseDeclared"], "Apache-2.0");
+        assert_eq!(package["supplier"], "Organization: Google LLC");
+        assert_eq!(package["checksums"][0]["algorithm"], "SHA1");
+        assert_eq!(
+            package["annotations"][0]["comment"],
+            "maven-mcp:latest_version=33.0.0-jre"
+        );
+        assert_eq!(document["packages"][1]["licenseDeclared"], "NOASSERTION");
+        assert_eq!(
+            document["relationships"][0]["relationshipType"],
+            "DESCRIBES"
+        );
+        assert_eq!(
+            document["relationships"][1]["relationshipType"],
+            "DEPENDS_ON"
+        );
+        assert_eq!(document["relationships"].as_array().unwrap().len(), 2);
+    }
+}
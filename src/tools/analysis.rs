@@ -0,0 +1,1301 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use futures::future::join_all;
+use tracing::error;
+
+use crate::build::edit::{locate_version, replace_version};
+use crate::build::plugins::{conventional_prefix, PLUGIN_GROUPS};
+use crate::build::pom::{EffectivePom, Pom};
+use crate::build::{is_single_reference, DeclaredDependency, ResolvedDependency, VersionEdit};
+use crate::maven::{CachedMetadata, MavenClient, MAVEN_CENTRAL_BASE};
+use crate::models::{MavenCoordinate, MavenVersion, UpdatePolicy, UpdateType, VersionScheme};
+use crate::tools::responses::*;
+
+/// How many `${property}` indirections are followed to find a version literal
+const MAX_DEFINITION_DEPTH: usize = 10;
+
+/// Coordinates with a range or dynamic version have no single version to check
+const DYNAMIC_VERSION_ERROR: &str =
+    "Version ranges and dynamic versions are not checked; use resolve_version_range";
+
+/// How many dependency levels below the requested artifacts are resolved
+const MAX_TRANSITIVE_DEPTH: usize = 10;
+
+/// Most components a generated SBOM describes
+const MAX_SBOM_COMPONENTS: usize = 1000;
+
+/// Options shared by the update-checking tools
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CheckOptions {
+    pub stable_only: bool,
+    pub same_stream: bool,
+    pub update_policy: UpdatePolicy,
+}
+
+/// Compute the latest patch, minor and major targets for a current version
+pub(crate) fn update_targets(
+    metadata: &CachedMetadata,
+    current: &MavenVersion,
+    options: CheckOptions,
+) -> UpdateTargets {
+    let stream = current.stream.as_deref();
+    let eligible = |v: &str| {
+        (!options.stable_only || metadata.is_stable(v))
+            && (!options.same_stream || MavenVersion::detect_stream(v).as_deref() == stream)
+    };
+
+    let numbers = current.release_numbers();
+    let major = numbers.first().copied();
+    let minor = numbers.get(1).copied().unwrap_or(0);
+
+    let latest_major = if options.same_stream {
+        metadata.latest_in_stream(stream, options.stable_only)
+    } else if options.stable_only {
+        metadata.latest_stable.clone()
+    } else {
+        metadata.latest_any.clone()
+    };
+
+    // Calendar versions have no maintenance lines; the patch and minor
+    // targets are the latest released within a month and within a year
+    if VersionScheme::detect(&current.original).is_calendar() {
+        let current = current.original.as_str();
+        return UpdateTargets {
+            latest_patch: metadata.latest_calendar_update(
+                current,
+                &[UpdateType::CalendarDays],
+                eligible,
+            ),
+            latest_minor: metadata.latest_calendar_update(
+                current,
+                &[UpdateType::CalendarDays, UpdateType::CalendarMonths],
+                eligible,
+            ),
+            latest_major,
+        };
+    }
+
+    UpdateTargets {
+        latest_patch: major.and_then(|m| metadata.latest_in_minor_line(m, minor, eligible)),
+        latest_minor: major.and_then(|m| metadata.latest_in_major_line(m, eligible)),
+        latest_major,
+    }
+}
+
+/// Helper function to check a single dependency against a repository
+pub(crate) async fn check_single_dependency(
+    client: &MavenClient,
+    repository: &str,
+    dependency: &str,
+    options: CheckOptions,
+) -> DependencyCheckResult {
+    let coordinate = match MavenCoordinate::parse(dependency) {
+        Ok(c) => c,
+        Err(e) => {
+            return DependencyCheckResult {
+                dependency: dependency.to_string(),
+                current_version: None,
+                latest_version: None,
+                is_outdated: false,
+                update_type: None,
+                targets: None,
+                stream: None,
+                alternatives: Vec::new(),
+                error: Some(e.to_string()),
+                location: None,
+                introduced_by: None,
+            }
+        }
+    };
+
+    let current_version = coordinate.version.clone();
+    if coordinate.has_dynamic_version() {
+        return DependencyCheckResult {
+            dependency: coordinate.to_ga(),
+            current_version,
+            latest_version: None,
+            is_outdated: false,
+            update_type: None,
+            targets: None,
+            stream: None,
+            alternatives: Vec::new(),
+            error: Some(DYNAMIC_VERSION_ERROR.to_string()),
+            location: None,
+            introduced_by: None,
+        };
+    }
+    let current_stream = current_version
+        .as_deref()
+        .and_then(MavenVersion::detect_stream);
+
+    let metadata = match client.get_metadata_in(repository, &coordinate).await {
+        Ok(m) => m,
+        Err(e) => {
+            error!("Failed to fetch metadata for {}: {}", dependency, e);
+            return DependencyCheckResult {
+                dependency: coordinate.to_ga(),
+                current_version,
+                latest_version: None,
+                is_outdated: false,
+                update_type: None,
+                targets: None,
+                stream: current_stream,
+                alternatives: Vec::new(),
+                error: Some(e.to_string()),
+                location: None,
+                introduced_by: None,
+            };
+        }
+    };
+
+    check_against_metadata(client, &coordinate, &metadata, options)
+}
+
+/// Compare a coordinate's version with already fetched metadata
+pub(crate) fn check_against_metadata(
+    client: &MavenClient,
+    coordinate: &MavenCoordinate,
+    metadata: &CachedMetadata,
+    options: CheckOptions,
+) -> DependencyCheckResult {
+    let current_version = coordinate.version.clone();
+    let current_stream = current_version
+        .as_deref()
+        .and_then(MavenVersion::detect_stream);
+
+    let Some(current) = current_version else {
+        // No current version specified, just return latest
+        let latest_version = if options.stable_only {
+            metadata.latest_stable.clone()
+        } else {
+            metadata.latest_any.clone()
+        };
+        let error = latest_version
+            .is_none()
+            .then(|| "No versions found".to_string());
+
+        return DependencyCheckResult {
+            dependency: coordinate.to_ga(),
+            current_version: None,
+            latest_version,
+            is_outdated: false,
+            update_type: None,
+            targets: None,
+            stream: None,
+            alternatives: Vec::new(),
+            error,
+            location: None,
+            introduced_by: None,
+        };
+    };
+
+    let current_parsed = MavenVersion::parse(&current);
+    let targets = update_targets(metadata, &current_parsed, options);
+    let alternatives = StreamAlternative::from_pairs(
+        metadata.stream_alternatives(current_stream.as_deref(), options.stable_only),
+    );
+
+    if targets.latest_major.is_none() {
+        return DependencyCheckResult {
+            dependency: coordinate.to_ga(),
+            current_version: Some(current),
+            latest_version: None,
+            is_outdated: false,
+            update_type: None,
+            targets: None,
+            stream: current_stream,
+            alternatives,
+            error: Some("No versions found".to_string()),
+            location: None,
+            introduced_by: None,
+        };
+    }
+
+    // Stay on the current version when the policy's line has no eligible target
+    let latest = targets
+        .for_policy(options.update_policy)
+        .cloned()
+        .unwrap_or_else(|| current.clone());
+    let is_outdated = current_parsed < MavenVersion::parse(&latest);
+    let update_type = Some(client.update_type(&coordinate.group_id, &current, &latest));
+
+    DependencyCheckResult {
+        dependency: coordinate.to_ga(),
+        current_version: Some(current),
+        latest_version: Some(latest),
+        is_outdated,
+        update_type,
+        targets: Some(targets),
+        stream: current_stream,
+        alternatives,
+        error: None,
+        location: None,
+        introduced_by: None,
+    }
+}
+
+/// Helper function to analyze health of a single dependency in a repository
+pub(crate) async fn analyze_single_health(
+    client: &MavenClient,
+    repository: &str,
+    dependency: &str,
+) -> DependencyHealthResult {
+    let coordinate = match MavenCoordinate::parse(dependency) {
+        Ok(c) => c,
+        Err(e) => {
+            return DependencyHealthResult {
+                dependency: dependency.to_string(),
+                current_version: None,
+                latest_version: None,
+                age_classification: None,
+                health_score: 0.0,
+                update_type: None,
+                error: Some(e.to_string()),
+                location: None,
+                introduced_by: None,
+            }
+        }
+    };
+
+    let current_version = match &coordinate.version {
+        Some(v) => v.clone(),
+        None => {
+            return DependencyHealthResult {
+                dependency: coordinate.to_ga(),
+                current_version: None,
+                latest_version: None,
+                age_classification: None,
+                health_score: 0.0,
+                update_type: None,
+                error: Some("Version is required for health analysis".to_string()),
+                location: None,
+                introduced_by: None,
+            }
+        }
+    };
+    if coordinate.has_dynamic_version() {
+        return DependencyHealthResult {
+            dependency: coordinate.to_ga(),
+            current_version: Some(current_version),
+            latest_version: None,
+            age_classification: None,
+            health_score: 0.0,
+            update_type: None,
+            error: Some(DYNAMIC_VERSION_ERROR.to_string()),
+            location: None,
+            introduced_by: None,
+        };
+    }
+
+    let metadata = match client.get_metadata_in(repository, &coordinate).await {
+        Ok(m) => m,
+        Err(e) => {
+            error!("Failed to fetch metadata for {}: {}", dependency, e);
+            return DependencyHealthResult {
+                dependency: coordinate.to_ga(),
+                current_version: Some(current_version),
+                latest_version: None,
+                age_classification: None,
+                health_score: 0.0,
+                update_type: None,
+                error: Some(e.to_string()),
+                location: None,
+                introduced_by: None,
+            };
+        }
+    };
+
+    health_against_metadata(client, &coordinate, current_version, &metadata)
+}
+
+/// Score a version against already fetched metadata. Only releases in the
+/// version's stream count, so guava 31.0-jre is compared with -jre releases.
+pub(crate) fn health_against_metadata(
+    client: &MavenClient,
+    coordinate: &MavenCoordinate,
+    current_version: String,
+    metadata: &CachedMetadata,
+) -> DependencyHealthResult {
+    let current_parsed = MavenVersion::parse(&current_version);
+    let stream = current_parsed.stream.as_deref();
+    let latest_stable = metadata.latest_in_stream(stream, true);
+
+    let stable_versions_since = metadata.newer_in_stream(&current_parsed, true);
+
+    // Calculate health score and age classification
+    let scoring = client.config().scoring;
+    let (age_classification, health_score) = if stable_versions_since == 0 {
+        (AgeClassification::Current, scoring.current)
+    } else if stable_versions_since <= scoring.fresh_within {
+        (AgeClassification::Fresh, scoring.fresh)
+    } else if stable_versions_since <= scoring.aging_within {
+        (AgeClassification::Aging, scoring.aging)
+    } else {
+        // Check for major version difference
+        if let Some(latest) = &latest_stable {
+            let update_type = client.update_type(&coordinate.group_id, &current_version, latest);
+            if update_type == UpdateType::Major {
+                (AgeClassification::Outdated, scoring.outdated)
+            } else {
+                (AgeClassification::Stale, scoring.stale)
+            }
+        } else {
+            (AgeClassification::Stale, scoring.stale)
+        }
+    };
+
+    let update_type = latest_stable
+        .as_ref()
+        .map(|latest| client.update_type(&coordinate.group_id, &current_version, latest));
+
+    DependencyHealthResult {
+        dependency: coordinate.to_ga(),
+        current_version: Some(current_version),
+        latest_version: latest_stable,
+        age_classification: Some(age_classification),
+        health_score,
+        update_type,
+        error: None,
+        location: None,
+        introduced_by: None,
+    }
+}
+
+/// A coordinate to analyze, with where it was found
+pub(crate) struct DependencyInput {
+    pub coordinate: String,
+    pub location: Option<SourceLocation>,
+    pub introduced_by: Option<String>,
+}
+
+impl DependencyInput {
+    /// Inputs for coordinates passed directly by the caller
+    pub(crate) fn from_coordinates(coordinates: Vec<String>) -> Vec<Self> {
+        coordinates
+            .into_iter()
+            .map(|coordinate| DependencyInput {
+                coordinate,
+                location: None,
+                introduced_by: None,
+            })
+            .collect()
+    }
+}
+
+/// Add resolved dependencies read from `file`. A coordinate locked for
+/// several configurations or reached through several paths is added once.
+pub(crate) fn add_resolved_inputs(
+    inputs: &mut Vec<DependencyInput>,
+    file: &str,
+    resolved: &[ResolvedDependency],
+) {
+    let mut seen: HashSet<String> = inputs.iter().map(|i| i.coordinate.clone()).collect();
+    for dependency in resolved {
+        let coordinate = dependency.to_coordinate();
+        if seen.insert(coordinate.clone()) {
+            inputs.push(DependencyInput {
+                coordinate,
+                location: Some(SourceLocation {
+                    file: file.to_string(),
+                    line: dependency.line,
+                }),
+                introduced_by: dependency.introduced_by.clone(),
+            });
+        }
+    }
+}
+
+/// Aggregate per-dependency update checks into a bulk response
+pub(crate) fn bulk_check_response(
+    results: Vec<DependencyCheckResult>,
+    update_policy: UpdatePolicy,
+) -> BulkCheckResponse {
+    let mut outdated_count = 0;
+    let mut up_to_date_count = 0;
+    let mut error_count = 0;
+    let mut summary = BulkCheckSummary {
+        major_updates: 0,
+        minor_updates: 0,
+        patch_updates: 0,
+        build_updates: 0,
+        prerelease_to_release_updates: 0,
+        calendar_updates: 0,
+        other_updates: 0,
+        downgrades: 0,
+    };
+
+    for result in &results {
+        if result.error.is_some() {
+            error_count += 1;
+            continue;
+        } else if result.is_outdated {
+            outdated_count += 1;
+        } else {
+            up_to_date_count += 1;
+        }
+
+        match result.update_type {
+            Some(UpdateType::Major) => summary.major_updates += 1,
+            Some(UpdateType::Minor) => summary.minor_updates += 1,
+            Some(UpdateType::Patch) => summary.patch_updates += 1,
+            Some(UpdateType::Build) => summary.build_updates += 1,
+            Some(UpdateType::PrereleaseToRelease) => summary.prerelease_to_release_updates += 1,
+            Some(UpdateType::Downgrade) => summary.downgrades += 1,
+            Some(UpdateType::Other) => summary.other_updates += 1,
+            Some(t) if t.is_calendar() => summary.calendar_updates += 1,
+            _ => {}
+        }
+    }
+
+    BulkCheckResponse {
+        total_checked: results.len(),
+        update_policy,
+        outdated_count,
+        up_to_date_count,
+        error_count,
+        dependencies: results,
+        summary,
+        warnings: Vec::new(),
+    }
+}
+
+/// Aggregate per-dependency health results into a scored project report
+pub(crate) fn project_health_response(
+    results: Vec<DependencyHealthResult>,
+) -> ProjectHealthResponse {
+    let mut summary = HealthSummary {
+        current: 0,
+        fresh: 0,
+        aging: 0,
+        stale: 0,
+        outdated: 0,
+        errors: 0,
+    };
+
+    let mut total_score = 0.0;
+    let mut scored_count = 0;
+
+    for result in &results {
+        if result.error.is_some() {
+            summary.errors += 1;
+        } else if let Some(age) = result.age_classification {
+            scored_count += 1;
+            total_score += result.health_score;
+            match age {
+                AgeClassification::Current => summary.current += 1,
+                AgeClassification::Fresh => summary.fresh += 1,
+                AgeClassification::Aging => summary.aging += 1,
+                AgeClassification::Stale => summary.stale += 1,
+                AgeClassification::Outdated => summary.outdated += 1,
+            }
+        }
+    }
+
+    let health_score = if scored_count > 0 {
+        total_score / scored_count as f32
+    } else {
+        0.0
+    };
+
+    let health_grade = HealthGrade::from_score(health_score);
+
+    // Generate recommendations
+    let mut recommendations = Vec::new();
+    if summary.outdated > 0 {
+        recommendations.push(format!(
+            "{} dependencies are a major version behind. Prioritize these upgrades.",
+            summary.outdated
+        ));
+    }
+    if summary.stale > 0 {
+        recommendations.push(format!(
+            "{} dependencies are significantly outdated. Plan upgrades soon.",
+            summary.stale
+        ));
+    }
+    if summary.aging > 0 {
+        recommendations.push(format!(
+            "{} dependencies have updates available. Consider upgrading.",
+            summary.aging
+        ));
+    }
+    // Name the direct dependencies that bring in stale transitive ones
+    let mut stale_via: BTreeMap<&str, usize> = BTreeMap::new();
+    for result in &results {
+        if let (Some(direct), Some(AgeClassification::Stale | AgeClassification::Outdated)) =
+            (&result.introduced_by, result.age_classification)
+        {
+            *stale_via.entry(direct).or_default() += 1;
+        }
+    }
+    for (direct, count) in stale_via {
+        recommendations.push(format!(
+            "{} stale or outdated transitive dependencies come in through {}. Upgrading it may bring newer versions.",
+            count, direct
+        ));
+    }
+    if summary.current + summary.fresh == results.len() - summary.errors {
+        recommendations.push("All dependencies are up to date!".to_string());
+    }
+
+    ProjectHealthResponse {
+        total_dependencies: results.len(),
+        health_score,
+        health_grade,
+        summary,
+        dependencies: results,
+        recommendations,
+        warnings: Vec::new(),
+    }
+}
+
+/// Run update and health checks for declarations found in a build file.
+///
+/// Declarations resolving to the same coordinate are checked once and
+/// reported at their first location.
+pub(crate) async fn check_declarations(
+    client: &Arc<MavenClient>,
+    file: &str,
+    declarations: &[DeclaredDependency],
+    options: CheckOptions,
+) -> (BulkCheckResponse, ProjectHealthResponse) {
+    let mut locations: HashMap<(String, String), SourceLocation> = HashMap::new();
+    let mut coordinates = Vec::new();
+    for declaration in declarations {
+        let Some(coordinate) = declaration.checkable_coordinate() else {
+            continue;
+        };
+        let key = (declaration.repository().to_string(), coordinate);
+        if !locations.contains_key(&key) {
+            locations.insert(
+                key.clone(),
+                SourceLocation {
+                    file: file.to_string(),
+                    line: declaration.line,
+                },
+            );
+            coordinates.push(key);
+        }
+    }
+
+    let checks = join_all(coordinates.iter().map(|(repository, dep)| {
+        let client = client.clone();
+        async move { check_single_dependency(&client, repository, dep, options).await }
+    }));
+    let health = join_all(coordinates.iter().map(|(repository, dep)| {
+        let client = client.clone();
+        async move { analyze_single_health(&client, repository, dep).await }
+    }));
+    let (mut checks, mut health) = futures::join!(checks, health);
+
+    for (key, (check, health)) in coordinates.iter().zip(checks.iter_mut().zip(&mut health)) {
+        check.location = locations.get(key).cloned();
+        health.location = locations.get(key).cloned();
+    }
+
+    (
+        bulk_check_response(checks, options.update_policy),
+        project_health_response(health),
+    )
+}
+
+/// Analyze the health of each distinct coordinate once
+pub(crate) async fn health_by_coordinate(
+    client: &Arc<MavenClient>,
+    coordinates: &[String],
+) -> HashMap<String, DependencyHealthResult> {
+    let results = join_all(coordinates.iter().map(|dep| {
+        let client = client.clone();
+        async move { analyze_single_health(&client, MAVEN_CENTRAL_BASE, dep).await }
+    }))
+    .await;
+    coordinates.iter().cloned().zip(results).collect()
+}
+
+/// Newest candidate version that every member publishes, so that a shared
+/// version variable can be bumped for all of them at once
+pub(crate) async fn common_target(
+    client: &MavenClient,
+    members: &[&DeclaredDependency],
+    mut candidates: Vec<String>,
+) -> Option<String> {
+    candidates.sort_by_key(|v| std::cmp::Reverse(MavenVersion::parse(v)));
+    candidates.dedup();
+
+    let mut published = Vec::new();
+    for member in members {
+        let coordinate = MavenCoordinate::parse(&member.to_ga()).ok()?;
+        if let Ok(metadata) = client
+            .get_metadata_in(member.repository(), &coordinate)
+            .await
+        {
+            published.push(metadata);
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find(|candidate| published.iter().all(|m| m.all_versions.contains(candidate)))
+}
+
+/// Where a shared version is defined in the file being edited
+pub(crate) struct VersionDefinition {
+    pub line: usize,
+    /// The value exactly as written, e.g. "2.15.0" or "${jackson.base}"
+    pub value: String,
+}
+
+/// Plan in-place edits that move outdated declarations to their targets.
+///
+/// Declarations sharing a version property or `version.ref` are updated
+/// through its single definition, to a version every member publishes.
+/// `definition` looks up a property defined in the edited file.
+pub(crate) async fn plan_version_edits(
+    client: &MavenClient,
+    content: &str,
+    declarations: &[DeclaredDependency],
+    updates: &BulkCheckResponse,
+    definition: impl Fn(&str) -> Option<VersionDefinition>,
+) -> (Vec<VersionEdit>, Vec<SkippedUpdate>) {
+    let target_for = |d: &DeclaredDependency| {
+        updates
+            .dependencies
+            .iter()
+            .find(|r| r.dependency == d.to_ga() && r.current_version == d.resolved_version)
+            .filter(|r| r.is_outdated)
+            .and_then(|r| r.latest_version.clone())
+    };
+
+    let mut edits: Vec<VersionEdit> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut skip = |d: &DeclaredDependency, reason: String| {
+        skipped.push(SkippedUpdate {
+            dependency: d.to_ga(),
+            line: d.line,
+            reason,
+        })
+    };
+
+    // Shared definitions, keyed by the property that finally holds the literal
+    let mut shared: BTreeMap<String, (VersionDefinition, Vec<&DeclaredDependency>)> =
+        BTreeMap::new();
+
+    for declaration in declarations {
+        if declaration.checkable_coordinate().is_none() {
+            continue;
+        }
+        let target = target_for(declaration);
+
+        if declaration.version_property.is_some() {
+            let resolved = match declaration.shared_property() {
+                Some(property) => resolve_definition(property, &definition),
+                None => Err("Version is composed from properties".to_string()),
+            };
+            match resolved {
+                Ok((name, found)) => shared
+                    .entry(name)
+                    .or_insert_with(|| (found, Vec::new()))
+                    .1
+                    .push(declaration),
+                Err(reason) if target.is_some() => skip(declaration, reason),
+                Err(_) => {}
+            }
+            continue;
+        }
+
+        let Some(target) = target else {
+            continue;
+        };
+        let Some(from) = &declaration.declared_version else {
+            let declared_here = declarations
+                .iter()
+                .any(|d| d.to_ga() == declaration.to_ga() && d.declared_version.is_some());
+            if !declared_here {
+                skip(
+                    declaration,
+                    "Version is managed outside this file".to_string(),
+                );
+            }
+            continue;
+        };
+        match locate_version(content, declaration.line, from) {
+            Some(line) if !edits.iter().any(|e| e.line == line) => edits.push(VersionEdit {
+                line,
+                from: from.clone(),
+                to: target,
+                property: None,
+                dependencies: vec![declaration.to_ga()],
+            }),
+            Some(_) => skip(
+                declaration,
+                "Version shares a line with another update".to_string(),
+            ),
+            None => skip(
+                declaration,
+                format!("Could not locate version '{}' in the file", from),
+            ),
+        }
+    }
+
+    for (property, (found, members)) in shared {
+        let candidates: Vec<String> = members.iter().filter_map(|d| target_for(d)).collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        let Some(target) = common_target(client, &members, candidates).await else {
+            for member in members.iter().filter(|d| target_for(d).is_some()) {
+                skip(
+                    member,
+                    format!(
+                        "No newer version is published for every dependency sharing '{}'",
+                        property
+                    ),
+                );
+            }
+            continue;
+        };
+        let line = content.lines().nth(found.line.saturating_sub(1));
+        if line
+            .and_then(|l| replace_version(l, &found.value, &target))
+            .is_none()
+        {
+            for member in &members {
+                skip(
+                    member,
+                    format!("Could not locate the value of '{}' in the file", property),
+                );
+            }
+            continue;
+        }
+        let mut dependencies: Vec<String> = members.iter().map(|d| d.to_ga()).collect();
+        dependencies.sort();
+        dependencies.dedup();
+        edits.push(VersionEdit {
+            line: found.line,
+            from: found.value,
+            to: target,
+            property: Some(property),
+            dependencies,
+        });
+    }
+
+    edits.sort_by_key(|e| e.line);
+    (edits, skipped)
+}
+
+/// Follow `${other}` indirections to the property holding a literal version
+fn resolve_definition(
+    property: &str,
+    definition: &impl Fn(&str) -> Option<VersionDefinition>,
+) -> Result<(String, VersionDefinition), String> {
+    let mut name = property.to_string();
+    for _ in 0..MAX_DEFINITION_DEPTH {
+        let found = definition(&name)
+            .ok_or_else(|| format!("Property '{}' is not defined in this file", name))?;
+        if is_single_reference(&found.value) {
+            name = found.value.trim()[1..]
+                .trim_start_matches('{')
+                .trim_end_matches('}')
+                .to_string();
+        } else if found.value.contains('$') {
+            return Err(format!(
+                "Property '{}' is composed from other properties",
+                name
+            ));
+        } else {
+            return Ok((name, found));
+        }
+    }
+    Err(format!(
+        "Property '{}' references too many properties",
+        property
+    ))
+}
+
+/// Resolve a goal prefix like "surefire" to a plugin's groupId:artifactId.
+///
+/// Plugins declared in the build win, matched by the plugin naming
+/// convention; otherwise the `<plugins>` entries of each default plugin
+/// group's maven-metadata.xml are searched, as Maven does.
+pub(crate) async fn resolve_plugin_prefix(
+    client: &MavenClient,
+    prefix: &str,
+    declared: &[&DeclaredDependency],
+) -> Result<(String, String), String> {
+    if let Some(plugin) = declared
+        .iter()
+        .find(|d| conventional_prefix(&d.artifact_id) == Some(prefix))
+    {
+        return Ok((plugin.to_ga(), format!("{} of the POM", plugin.section)));
+    }
+
+    let mut errors = Vec::new();
+    for group in PLUGIN_GROUPS {
+        match client.get_plugin_prefixes(group).await {
+            Ok(prefixes) => {
+                if let Some(found) = prefixes.iter().find(|p| p.prefix == prefix) {
+                    return Ok((
+                        format!("{}:{}", group, found.artifact_id),
+                        format!("{} group metadata", group),
+                    ));
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", group, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Err(format!(
+            "No plugin with prefix '{}' in {}",
+            prefix,
+            PLUGIN_GROUPS.join(", ")
+        ))
+    } else {
+        Err(format!(
+            "Could not resolve prefix '{}': {}",
+            prefix,
+            errors.join("; ")
+        ))
+    }
+}
+
+/// Combine update and health results checked from different files
+pub(crate) fn merge_checks(
+    checks: Vec<(BulkCheckResponse, ProjectHealthResponse)>,
+    update_policy: UpdatePolicy,
+) -> (BulkCheckResponse, ProjectHealthResponse) {
+    let (updates, health): (Vec<_>, Vec<_>) = checks.into_iter().unzip();
+    (
+        bulk_check_response(
+            updates.into_iter().flat_map(|u| u.dependencies).collect(),
+            update_policy,
+        ),
+        project_health_response(health.into_iter().flat_map(|h| h.dependencies).collect()),
+    )
+}
+
+/// An artifact to describe in an SBOM
+pub(crate) struct ResolvedArtifact {
+    pub coordinate: MavenCoordinate,
+    /// `None` when the POM could not be loaded
+    pub pom: Option<EffectivePom>,
+    /// groupId:artifactId:version of its dependencies within the resolved set
+    pub dependencies: Vec<String>,
+    /// Requested by the caller rather than pulled in as a dependency
+    pub direct: bool,
+}
+
+/// Load the POMs of `roots` and, if `transitive`, of everything they
+/// depend on at runtime. Like Maven, the version nearest to a root wins
+/// when an artifact is reached at several versions; exclusions and the
+/// consumer's dependency management are not applied.
+pub(crate) async fn resolve_artifacts(
+    client: &MavenClient,
+    roots: Vec<MavenCoordinate>,
+    transitive: bool,
+    warnings: &mut Vec<String>,
+) -> Vec<ResolvedArtifact> {
+    // Selected version of every groupId:artifactId, in resolution order
+    let mut selected: HashMap<String, String> = HashMap::new();
+    let mut resolved: Vec<(ResolvedArtifact, Vec<String>)> = Vec::new();
+
+    let mut level = Vec::new();
+    for root in roots {
+        let version = root.version.clone().unwrap_or_default();
+        if selected.insert(root.to_ga(), version).is_none() {
+            level.push(root);
+        }
+    }
+    let mut truncated = false;
+
+    for depth in 0..=MAX_TRANSITIVE_DEPTH {
+        if level.is_empty() {
+            break;
+        }
+        let poms = join_all(level.iter().map(|coordinate| async move {
+            let content = client.get_pom(coordinate).await?;
+            let pom = Pom::parse(&content)?;
+            anyhow::Ok(EffectivePom::resolve(pom, None, None, client).await)
+        }))
+        .await;
+
+        let mut next = Vec::new();
+        for (coordinate, pom) in level.into_iter().zip(poms) {
+            let gav = coordinate.to_gav().unwrap_or_default();
+            let pom = match pom {
+                Ok(pom) => Some(pom),
+                Err(e) => {
+                    warnings.push(format!("Could not load POM for {}: {}", gav, e));
+                    None
+                }
+            };
+
+            let mut dependencies = Vec::new();
+            for dependency in pom.iter().flat_map(EffectivePom::runtime_dependencies) {
+                let ga = dependency.to_ga();
+                let Some(version) = dependency.resolved_version.clone() else {
+                    if transitive {
+                        warnings.push(format!(
+                            "Skipped {} required by {}: {}",
+                            ga,
+                            gav,
+                            dependency.unresolved_reason.as_deref().unwrap_or_default()
+                        ));
+                    }
+                    continue;
+                };
+                if transitive && !selected.contains_key(&ga) {
+                    if dependency.unresolved_reason.is_some() {
+                        warnings.push(format!(
+                            "Skipped {}:{} required by {}: version ranges are not resolved",
+                            ga, version, gav
+                        ));
+                        continue;
+                    }
+                    if depth == MAX_TRANSITIVE_DEPTH || selected.len() >= MAX_SBOM_COMPONENTS {
+                        truncated = true;
+                        continue;
+                    }
+                    selected.insert(ga.clone(), version.clone());
+                    next.push(MavenCoordinate {
+                        group_id: dependency.group_id.clone(),
+                        artifact_id: dependency.artifact_id.clone(),
+                        version: Some(version),
+                    });
+                }
+                dependencies.push(ga);
+            }
+
+            if let Some(pom) = &pom {
+                warnings.extend(pom.warnings.iter().map(|w| format!("{}: {}", gav, w)));
+            }
+            let artifact = ResolvedArtifact {
+                coordinate,
+                pom,
+                dependencies: Vec::new(),
+                direct: depth == 0,
+            };
+            resolved.push((artifact, dependencies));
+        }
+        level = next;
+    }
+
+    if truncated {
+        warnings.push(format!(
+            "Transitive resolution stopped at {} levels or {} components",
+            MAX_TRANSITIVE_DEPTH, MAX_SBOM_COMPONENTS
+        ));
+    }
+
+    resolved
+        .into_iter()
+        .map(|(mut artifact, dependencies)| {
+            artifact.dependencies = dependencies
+                .into_iter()
+                .filter_map(|ga| selected.get(&ga).map(|v| format!("{}:{}", ga, v)))
+                .collect();
+            artifact
+        })
+        .collect()
+}
+
+/// Coordinates each analyzed version was relocated to, keyed by
+/// groupId:artifactId:version, read from the `<relocation>` of its POM
+pub(crate) async fn find_relocations(
+    client: &MavenClient,
+    health: &[DependencyHealthResult],
+) -> HashMap<String, String> {
+    let coordinates: HashSet<String> = health
+        .iter()
+        .filter_map(|h| {
+            let version = h.current_version.as_ref()?;
+            Some(format!("{}:{}", h.dependency, version))
+        })
+        .collect();
+
+    let targets = join_all(coordinates.iter().map(|gav| async move {
+        let coordinate = MavenCoordinate::parse(gav).ok()?;
+        let content = client.get_pom(&coordinate).await.ok()?;
+        Pom::parse(&content).ok()?.relocation_target()
+    }))
+    .await;
+
+    coordinates
+        .into_iter()
+        .zip(targets)
+        .filter_map(|(gav, target)| Some((gav, target?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maven::MavenMetadata;
+
+    #[test]
+    fn test_health_compares_within_stream() {
+        let client = MavenClient::new();
+        let guava = MavenCoordinate::parse("com.google.guava:guava").unwrap();
+        let versions = [
+            "31.0-jre",
+            "31.0-android",
+            "31.1-jre",
+            "31.1-android",
+            "32.0.0-android",
+        ];
+        let metadata = client.process_metadata(
+            &guava,
+            &MavenMetadata::from_versions(versions.iter().map(|v| v.to_string()).collect()),
+        );
+
+        let health = health_against_metadata(&client, &guava, "31.0-jre".to_string(), &metadata);
+        assert_eq!(health.latest_version.as_deref(), Some("31.1-jre"));
+        assert_eq!(health.update_type, Some(UpdateType::Minor));
+        assert!(matches!(
+            health.age_classification,
+            Some(AgeClassification::Fresh)
+        ));
+    }
+
+    #[test]
+    fn test_calendar_targets_use_release_dates() {
+        let client = MavenClient::new();
+        let lib = MavenCoordinate::parse("com.acme:dated:20240101.1").unwrap();
+        let versions = ["20240101.1", "20240115.1", "20240601.1", "20250301.1"];
+        let metadata = client.process_metadata(
+            &lib,
+            &MavenMetadata::from_versions(versions.iter().map(|v| v.to_string()).collect()),
+        );
+        assert!(metadata.major_lines.is_empty());
+
+        let options = CheckOptions {
+            stable_only: true,
+            same_stream: true,
+            update_policy: UpdatePolicy::Patch,
+        };
+        let targets = update_targets(&metadata, &MavenVersion::parse("20240101.1"), options);
+        assert_eq!(targets.latest_patch.as_deref(), Some("20240115.1"));
+        assert_eq!(targets.latest_minor.as_deref(), Some("20240601.1"));
+        assert_eq!(targets.latest_major.as_deref(), Some("20250301.1"));
+
+        let result = check_against_metadata(&client, &lib, &metadata, options);
+        assert!(result.is_outdated);
+        assert_eq!(result.latest_version.as_deref(), Some("20240115.1"));
+        assert_eq!(result.update_type, Some(UpdateType::CalendarDays));
+    }
+
+    /// Client whose Maven Central requests go to a local server publishing
+    /// the given versions of com.fasterxml.jackson.core artifacts
+    async fn jackson_mirror(artifacts: &[(&str, &[&str])]) -> Arc<MavenClient> {
+        let mut router = axum::Router::new();
+        for (artifact, versions) in artifacts {
+            let versions: String = versions
+                .iter()
+                .map(|v| format!("<version>{}</version>", v))
+                .collect();
+            let body = format!(
+                "<metadata><versioning><versions>{}</versions></versioning></metadata>",
+                versions
+            );
+            router = router.route(
+                &format!(
+                    "/com/fasterxml/jackson/core/{}/maven-metadata.xml",
+                    artifact
+                ),
+                axum::routing::get(move || async move { body }),
+            );
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = crate::config::ServerConfig::from_toml(&format!(
+            "[[repositories]]\nurl = \"http://{}\"\nmirror_of = \"central\"\n",
+            address
+        ))
+        .unwrap();
+        Arc::new(MavenClient::with_config(config).unwrap())
+    }
+
+    const JACKSON: &[(&str, &[&str])] = &[
+        ("jackson-databind", &["2.15.0", "2.16.1", "2.17.0"]),
+        ("jackson-core", &["2.15.0", "2.16.1"]),
+    ];
+
+    const OPTIONS: CheckOptions = CheckOptions {
+        stable_only: true,
+        same_stream: true,
+        update_policy: UpdatePolicy::Major,
+    };
+
+    #[tokio::test]
+    async fn test_coordinates_with_ranges_are_not_checked() {
+        let client = jackson_mirror(JACKSON).await;
+        for dependency in [
+            "com.fasterxml.jackson.core:jackson-databind:[2.15,2.17)",
+            "com.fasterxml.jackson.core:jackson-databind:2.+",
+        ] {
+            let check =
+                check_single_dependency(&client, MAVEN_CENTRAL_BASE, dependency, OPTIONS).await;
+            assert!(!check.is_outdated);
+            assert!(check.latest_version.is_none());
+            assert_eq!(check.error.as_deref(), Some(DYNAMIC_VERSION_ERROR));
+
+            let health = analyze_single_health(&client, MAVEN_CENTRAL_BASE, dependency).await;
+            assert!(health.age_classification.is_none());
+            assert_eq!(health.error.as_deref(), Some(DYNAMIC_VERSION_ERROR));
+        }
+
+        let check = check_single_dependency(
+            &client,
+            MAVEN_CENTRAL_BASE,
+            "com.fasterxml.jackson.core:jackson-databind:2.15.0",
+            OPTIONS,
+        )
+        .await;
+        assert!(check.is_outdated);
+    }
+
+    #[tokio::test]
+    async fn test_edits_pom_property_instead_of_declarations() {
+        let client = jackson_mirror(JACKSON).await;
+        let content = r#"<project>
+  <properties>
+    <jackson.version>2.15.0</jackson.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+      <version>${jackson.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-core</artifactId>
+      <version>${jackson.version}</version>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+        let pom = Pom::parse(content).unwrap();
+        let properties = pom.properties.clone();
+        let declarations = EffectivePom::resolve(pom, None, None, &client)
+            .await
+            .declarations();
+        let (updates, _) = check_declarations(&client, "pom.xml", &declarations, OPTIONS).await;
+
+        let (edits, skipped) =
+            plan_version_edits(&client, content, &declarations, &updates, |name| {
+                properties
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| VersionDefinition {
+                        line: p.line,
+                        value: p.value.clone(),
+                    })
+            })
+            .await;
+
+        assert!(skipped.is_empty());
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].line, 3);
+        assert_eq!(edits[0].property.as_deref(), Some("jackson.version"));
+        // The newest version both artifacts publish, not databind's 2.17.0
+        assert_eq!(
+            (edits[0].from.as_str(), edits[0].to.as_str()),
+            ("2.15.0", "2.16.1")
+        );
+        assert_eq!(
+            edits[0].dependencies,
+            vec![
+                "com.fasterxml.jackson.core:jackson-core",
+                "com.fasterxml.jackson.core:jackson-databind"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_versions_composed_from_properties() {
+        let client = jackson_mirror(&[("jackson-databind", &["2.15.0-jre", "2.16.1-jre"])]).await;
+        let content = r#"<project>
+  <properties>
+    <jackson.version>2.15.0</jackson.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+      <version>${jackson.version}-jre</version>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+        let pom = Pom::parse(content).unwrap();
+        let properties = pom.properties.clone();
+        let declarations = EffectivePom::resolve(pom, None, None, &client)
+            .await
+            .declarations();
+        let (updates, _) = check_declarations(&client, "pom.xml", &declarations, OPTIONS).await;
+        assert!(updates.dependencies[0].is_outdated);
+
+        let (edits, skipped) =
+            plan_version_edits(&client, content, &declarations, &updates, |name| {
+                properties
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| VersionDefinition {
+                        line: p.line,
+                        value: p.value.clone(),
+                    })
+            })
+            .await;
+
+        // Writing 2.16.1-jre into the property would yield 2.16.1-jre-jre
+        assert!(edits.is_empty());
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 6);
+        assert_eq!(skipped[0].reason, "Version is composed from properties");
+    }
+
+    #[tokio::test]
+    async fn test_edits_catalog_version_ref_and_inline_versions() {
+        let client = jackson_mirror(JACKSON).await;
+        let content = r#"[versions]
+jackson = "2.15.0"
+
+[libraries]
+jackson-databind = { module = "com.fasterxml.jackson.core:jackson-databind", version.ref = "jackson" }
+jackson-core = { module = "com.fasterxml.jackson.core:jackson-core", version = "2.15.0" }
+"#;
+        let catalog = crate::build::VersionCatalog::parse(content).unwrap();
+        let declarations = catalog.declarations();
+        let (updates, _) =
+            check_declarations(&client, "libs.versions.toml", &declarations, OPTIONS).await;
+
+        let (edits, skipped) =
+            plan_version_edits(&client, content, &declarations, &updates, |name| {
+                catalog
+                    .versions
+                    .iter()
+                    .find(|v| v.name == name)
+                    .and_then(|v| {
+                        Some(VersionDefinition {
+                            line: v.line,
+                            value: v.version.clone()?,
+                        })
+                    })
+            })
+            .await;
+
+        assert!(skipped.is_empty());
+        let planned: Vec<_> = edits
+            .iter()
+            .map(|e| (e.line, e.property.as_deref(), e.to.as_str()))
+            .collect();
+        assert_eq!(
+            planned,
+            vec![(2, Some("jackson"), "2.17.0"), (6, None, "2.16.1")]
+        );
+    }
+}
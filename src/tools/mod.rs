@@ -1,3 +1,5 @@
+mod analysis;
+pub mod resources;
 pub mod responses;
 pub mod service;
 
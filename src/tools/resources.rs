@@ -0,0 +1,264 @@
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+    ResourceTemplate,
+};
+use rmcp::ErrorData as McpError;
+
+use crate::build::pom::{EffectivePom, Pom};
+use crate::maven::MavenClient;
+use crate::models::MavenCoordinate;
+use crate::tools::responses::*;
+
+const SCHEME: &str = "maven://";
+
+const JSON: &str = "application/json";
+const XML: &str = "application/xml";
+
+/// Artifact metadata exposed as an MCP resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactResource {
+    /// `maven://{groupId}/{artifactId}`: versions and their stability
+    Versions(MavenCoordinate),
+    /// `maven://{groupId}/{artifactId}/{version}/pom`: the published POM
+    Pom(MavenCoordinate),
+    /// `maven://{groupId}/{artifactId}/{version}/dependencies`: resolved
+    /// compile and runtime dependencies
+    Dependencies(MavenCoordinate),
+}
+
+impl ArtifactResource {
+    /// Parse a `maven://` URI, returning `None` for anything else
+    pub fn parse(uri: &str) -> Option<Self> {
+        let segments: Vec<&str> = uri.strip_prefix(SCHEME)?.split('/').collect();
+        if segments.iter().any(|s| s.is_empty() || s.contains(':')) {
+            return None;
+        }
+        let coordinate = |version: Option<&str>| MavenCoordinate {
+            group_id: segments[0].to_string(),
+            artifact_id: segments[1].to_string(),
+            version: version.map(String::from),
+        };
+        match segments.as_slice() {
+            [_, _] => Some(Self::Versions(coordinate(None))),
+            [_, _, version, "pom"] => Some(Self::Pom(coordinate(Some(version)))),
+            [_, _, version, "dependencies"] => Some(Self::Dependencies(coordinate(Some(version)))),
+            _ => None,
+        }
+    }
+
+    pub fn coordinate(&self) -> &MavenCoordinate {
+        match self {
+            Self::Versions(c) | Self::Pom(c) | Self::Dependencies(c) => c,
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        let c = self.coordinate();
+        let version = c.version.as_deref().unwrap_or_default();
+        match self {
+            Self::Versions(_) => format!("{}{}/{}", SCHEME, c.group_id, c.artifact_id),
+            Self::Pom(_) => format!("{}{}/{}/{}/pom", SCHEME, c.group_id, c.artifact_id, version),
+            Self::Dependencies(_) => format!(
+                "{}{}/{}/{}/dependencies",
+                SCHEME, c.group_id, c.artifact_id, version
+            ),
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Pom(_) => XML,
+            Self::Versions(_) | Self::Dependencies(_) => JSON,
+        }
+    }
+
+    /// Entry for `resources/list`
+    pub fn resource(&self) -> Resource {
+        let coordinate = self.coordinate();
+        let (name, description) = match self {
+            Self::Versions(_) => (
+                format!("{} versions", coordinate),
+                "Published versions and their stability",
+            ),
+            Self::Pom(_) => (format!("{} POM", coordinate), "Published POM"),
+            Self::Dependencies(_) => (
+                format!("{} dependencies", coordinate),
+                "Compile and runtime dependencies after parent and BOM resolution",
+            ),
+        };
+        RawResource {
+            description: Some(description.to_string()),
+            mime_type: Some(self.mime_type().to_string()),
+            ..RawResource::new(self.uri(), name)
+        }
+        .no_annotation()
+    }
+
+    /// Fetch the resource contents through the client's cache
+    pub async fn read(&self, client: &MavenClient) -> Result<ReadResourceResult, McpError> {
+        let text = match self {
+            Self::Versions(coordinate) => versions(client, coordinate).await?,
+            Self::Pom(coordinate) => client
+                .get_pom(coordinate)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?
+                .to_string(),
+            Self::Dependencies(coordinate) => dependencies(client, coordinate).await?,
+        };
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: self.uri(),
+                mime_type: Some(self.mime_type().to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+}
+
+/// URI templates clients can fill in for any artifact
+pub fn templates() -> Vec<ResourceTemplate> {
+    [
+        (
+            "maven://{groupId}/{artifactId}",
+            "artifact-versions",
+            "Published versions of an artifact and their stability",
+            JSON,
+        ),
+        (
+            "maven://{groupId}/{artifactId}/{version}/pom",
+            "artifact-pom",
+            "POM of an artifact version",
+            XML,
+        ),
+        (
+            "maven://{groupId}/{artifactId}/{version}/dependencies",
+            "artifact-dependencies",
+            "Compile and runtime dependencies of an artifact version",
+            JSON,
+        ),
+    ]
+    .into_iter()
+    .map(|(uri_template, name, description, mime_type)| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(mime_type.to_string()),
+        }
+        .no_annotation()
+    })
+    .collect()
+}
+
+/// Resources for the artifacts the client queried most recently: the
+/// versions of each artifact, and the POM and dependencies of each version
+pub fn recent_resources(client: &MavenClient) -> Vec<Resource> {
+    client
+        .recent_artifacts()
+        .into_iter()
+        .flat_map(|coordinate| match coordinate.version {
+            Some(_) => vec![
+                ArtifactResource::Pom(coordinate.clone()),
+                ArtifactResource::Dependencies(coordinate),
+            ],
+            None => vec![ArtifactResource::Versions(coordinate)],
+        })
+        .map(|resource| resource.resource())
+        .collect()
+}
+
+async fn versions(client: &MavenClient, coordinate: &MavenCoordinate) -> Result<String, McpError> {
+    let metadata = client
+        .get_metadata(coordinate)
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+    let contents = ArtifactVersionsResource {
+        dependency: coordinate.to_ga(),
+        latest: LatestVersions {
+            stable: metadata.latest_stable.clone(),
+            rc: metadata.latest_rc.clone(),
+            beta: metadata.latest_beta.clone(),
+            alpha: metadata.latest_alpha.clone(),
+            milestone: metadata.latest_milestone.clone(),
+            any: metadata.latest_any.clone(),
+        },
+        last_updated: metadata.last_updated.clone(),
+        versions: metadata
+            .all_versions
+            .iter()
+            .map(|version| VersionEntry {
+                version: version.clone(),
+                stability: client.classify(coordinate, version),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&contents)
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+}
+
+async fn dependencies(
+    client: &MavenClient,
+    coordinate: &MavenCoordinate,
+) -> Result<String, McpError> {
+    let content = client
+        .get_pom(coordinate)
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+    let pom = Pom::parse(&content).map_err(|e| {
+        McpError::internal_error(format!("Invalid POM for {}: {}", coordinate, e), None)
+    })?;
+    let effective = EffectivePom::resolve(pom, None, None, client).await;
+
+    let contents = ArtifactDependenciesResource {
+        dependency: coordinate.to_string(),
+        dependencies: effective
+            .runtime_dependencies()
+            .into_iter()
+            .map(|d| ArtifactDependency {
+                group_id: d.group_id,
+                artifact_id: d.artifact_id,
+                version: d.resolved_version,
+                scope: d.scope,
+            })
+            .collect(),
+        warnings: effective.warnings,
+    };
+    serde_json::to_string_pretty(&contents)
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        for uri in [
+            "maven://org.slf4j/slf4j-api",
+            "maven://org.slf4j/slf4j-api/2.0.9/pom",
+            "maven://org.slf4j/slf4j-api/2.0.9/dependencies",
+        ] {
+            let resource = ArtifactResource::parse(uri).unwrap();
+            assert_eq!(resource.coordinate().to_ga(), "org.slf4j:slf4j-api");
+            assert_eq!(resource.uri(), uri);
+        }
+        assert!(matches!(
+            ArtifactResource::parse("maven://org.slf4j/slf4j-api/2.0.9/pom"),
+            Some(ArtifactResource::Pom(c)) if c.version.as_deref() == Some("2.0.9")
+        ));
+
+        for uri in [
+            "file:///pom.xml",
+            "maven://org.slf4j",
+            "maven://org.slf4j/",
+            "maven://org.slf4j/slf4j-api/2.0.9",
+            "maven://org.slf4j/slf4j-api/2.0.9/jar",
+            "maven://org:slf4j/slf4j-api",
+        ] {
+            assert_eq!(ArtifactResource::parse(uri), None, "{}", uri);
+        }
+    }
+}
@@ -1,6 +1,13 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::models::{UpdateType, VersionStability};
+use crate::build::{BuildFileKind, DeclaredDependency, VersionConflict, VersionEdit, WrapperKind};
+use crate::config::ServerConfig;
+use crate::models::{
+    RuleSource, UpdatePolicy, UpdateType, VersionRange, VersionScheme, VersionStability,
+};
+use crate::report::SbomFormat;
 
 /// Response for get_latest_version tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +36,28 @@ pub struct LatestVersions {
     pub any: Option<String>,
 }
 
+/// Response for get_gradle_plugin_version tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradlePluginResponse {
+    pub plugin_id: String,
+    /// Marker coordinate the plugin id is published under
+    pub marker: String,
+    pub repository: String,
+    pub latest: LatestVersions,
+    pub total_versions: usize,
+    pub stable_versions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    /// Plugin version the implementation artifact was resolved for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_version: Option<String>,
+    /// groupId:artifactId:version the marker points to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Response for check_version_exists tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionExistsResponse {
@@ -41,6 +70,113 @@ pub struct VersionExistsResponse {
     pub latest_stable: Option<String>,
 }
 
+/// Response for explain_version_stability tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StabilityExplanationResponse {
+    pub dependency: String,
+    pub version: String,
+    pub stability: VersionStability,
+    pub rule: RuleSource,
+    pub builtin_stability: VersionStability,
+    pub overridden: bool,
+}
+
+/// Response for get_server_config tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfigResponse {
+    pub server_version: String,
+    /// Config file the server was started with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_file: Option<String>,
+    /// Effective config with passwords and tokens redacted
+    pub config: ServerConfig,
+}
+
+/// Contents of the `maven://{groupId}/{artifactId}` resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactVersionsResource {
+    pub dependency: String,
+    pub latest: LatestVersions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    /// All published versions, newest first
+    pub versions: Vec<VersionEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionEntry {
+    pub version: String,
+    pub stability: VersionStability,
+}
+
+/// Contents of the `maven://{groupId}/{artifactId}/{version}/dependencies` resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactDependenciesResource {
+    pub dependency: String,
+    /// Compile and runtime dependencies after parent and BOM resolution
+    pub dependencies: Vec<ArtifactDependency>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// Response for resolve_version_range tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRangeResponse {
+    pub dependency: String,
+    pub range: String,
+    pub parsed_range: VersionRange,
+    /// The version a build would select (the newest matching version)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_stability: Option<VersionStability>,
+    /// Every published version satisfying the range, newest first
+    pub matching_versions: Vec<String>,
+    pub total_versions: usize,
+}
+
+/// Response for solve_version_constraints tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintSolveResponse {
+    pub dependency: String,
+    pub constraints: Vec<ConstraintResult>,
+    pub satisfiable: bool,
+    /// Newest version satisfying every constraint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newest_version: Option<String>,
+    /// Every version satisfying all constraints, newest first
+    pub satisfying_versions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<ConstraintConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintResult {
+    pub constraint: String,
+    pub parsed: VersionRange,
+    /// Number of published versions this constraint allows on its own
+    pub matching_versions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newest_match: Option<String>,
+}
+
+/// A minimal set of constraints that cannot be satisfied together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintConflict {
+    pub constraints: Vec<String>,
+    pub explanation: String,
+}
+
 /// Response for compare_versions tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionComparisonResponse {
@@ -52,20 +188,75 @@ pub struct VersionComparisonResponse {
     pub is_outdated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_type: Option<UpdateType>,
+    pub update_policy: UpdatePolicy,
+    pub targets: UpdateTargets,
     pub versions_behind: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<StreamAlternative>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub recommendation: Option<String>,
 }
 
+/// Update targets per maintenance line
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTargets {
+    /// Latest version with the same major.minor (e.g. "latest 5.3.x")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_patch: Option<String>,
+    /// Latest version with the same major (e.g. "latest 5.x")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_minor: Option<String>,
+    /// Latest version overall
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_major: Option<String>,
+}
+
+impl UpdateTargets {
+    /// The target that decides `is_outdated` under the given policy
+    pub fn for_policy(&self, policy: UpdatePolicy) -> Option<&String> {
+        match policy {
+            UpdatePolicy::Patch => self.latest_patch.as_ref(),
+            UpdatePolicy::Minor => self.latest_minor.as_ref(),
+            UpdatePolicy::Major => self.latest_major.as_ref(),
+        }
+    }
+}
+
+/// Latest version of another version stream (e.g. `android` when on `jre`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamAlternative {
+    /// Stream name, or "default" for versions without a stream suffix
+    pub stream: String,
+    pub latest_version: String,
+}
+
+impl StreamAlternative {
+    pub fn from_pairs(pairs: Vec<(Option<String>, String)>) -> Vec<Self> {
+        pairs
+            .into_iter()
+            .map(|(stream, latest_version)| StreamAlternative {
+                stream: stream.unwrap_or_else(|| "default".to_string()),
+                latest_version,
+            })
+            .collect()
+    }
+}
+
 /// Response for check_multiple_dependencies tool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkCheckResponse {
     pub total_checked: usize,
+    pub update_policy: UpdatePolicy,
     pub outdated_count: usize,
     pub up_to_date_count: usize,
     pub error_count: usize,
     pub dependencies: Vec<DependencyCheckResult>,
     pub summary: BulkCheckSummary,
+    /// Inputs that could not be checked, e.g. non-Maven SBOM components
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,7 +270,28 @@ pub struct DependencyCheckResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update_type: Option<UpdateType>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<UpdateTargets>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<StreamAlternative>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Where the dependency is declared, when checked from a build file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    /// Direct dependency this transitive one is pulled in through, when
+    /// checked from a dependency graph
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_by: Option<String>,
+}
+
+/// Position of a declaration in a build file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +299,236 @@ pub struct BulkCheckSummary {
     pub major_updates: usize,
     pub minor_updates: usize,
     pub patch_updates: usize,
+    pub build_updates: usize,
+    pub prerelease_to_release_updates: usize,
+    pub calendar_updates: usize,
+    pub other_updates: usize,
+    pub downgrades: usize,
+}
+
+/// Response for analyze_pom tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PomAnalysisResponse {
+    /// Path of the analyzed POM, or "<inline>" for inline content
+    pub pom: String,
+    /// groupId:artifactId:version of the project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Parent POMs that were resolved, nearest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<String>,
+    pub declarations: Vec<DeclaredDependency>,
+    pub unresolved_count: usize,
+    pub updates: BulkCheckResponse,
+    pub health: ProjectHealthResponse,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Response for analyze_gradle_build tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradleAnalysisResponse {
+    /// Path of the analyzed build script, or "<inline>" for inline content
+    pub build_file: String,
+    pub declarations: Vec<DeclaredDependency>,
+    pub unresolved_count: usize,
+    pub updates: BulkCheckResponse,
+    pub health: ProjectHealthResponse,
+}
+
+/// Response for analyze_version_catalog tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionCatalogResponse {
+    /// Path of the analyzed catalog, or "<inline>" for inline content
+    pub catalog: String,
+    /// Updates grouped by the `[versions]` entry that would be bumped
+    pub version_refs: Vec<VersionRefUpdate>,
+    pub bundles: Vec<BundleStatus>,
+    pub declarations: Vec<DeclaredDependency>,
+    pub unresolved_count: usize,
+    pub updates: BulkCheckResponse,
+    pub health: ProjectHealthResponse,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A `[versions]` entry together with every library and plugin using it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRefUpdate {
+    pub version_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<String>,
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<String>,
+    /// Newest version allowed by the update policy that every member publishes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_version: Option<String>,
+    pub is_outdated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<UpdateType>,
+}
+
+/// Libraries of a `[bundles]` entry with available updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleStatus {
+    pub bundle: String,
+    pub libraries: Vec<String>,
+    pub outdated_libraries: Vec<String>,
+}
+
+/// Response for analyze_maven_plugins tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginAnalysisResponse {
+    /// Path of the analyzed POM, or "<inline>" for inline content
+    pub pom: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Build plugins and pluginManagement entries
+    pub plugins: Vec<DeclaredDependency>,
+    /// Build extensions from the POM and `.mvn/extensions.xml`
+    pub extensions: Vec<DeclaredDependency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<ResolvedGoal>,
+    pub plugin_updates: BulkCheckResponse,
+    pub plugin_health: ProjectHealthResponse,
+    pub extension_updates: BulkCheckResponse,
+    pub extension_health: ProjectHealthResponse,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A command-line goal resolved to the plugin that runs it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedGoal {
+    pub goal: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// groupId:artifactId of the plugin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    /// Version pinned on the command line or in the POM
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Where the prefix was resolved, e.g. "org.apache.maven.plugins group metadata"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for check_wrapper_versions tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrapperCheckResponse {
+    pub wrappers: Vec<WrapperReport>,
+    pub outdated_count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Distribution and wrapper versions pinned by one wrapper properties file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrapperReport {
+    pub file: String,
+    pub kind: WrapperKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution_url: Option<String>,
+    pub checks: Vec<DependencyCheckResult>,
+}
+
+/// Response for analyze_bazel_pins tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BazelPinsResponse {
+    /// Path of the analyzed pin file, or "<inline>" for inline content
+    pub pin_file: String,
+    /// Format version of the pin file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_version: Option<String>,
+    /// Repositories metadata is fetched from, in the order they are listed
+    pub repositories: Vec<String>,
+    pub declarations: Vec<DeclaredDependency>,
+    pub unresolved_count: usize,
+    /// Pinned artifacts no other pinned artifact depends on
+    pub top_level: Vec<String>,
+    /// For each outdated artifact, the pinned artifacts that depend on it
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_by: BTreeMap<String, Vec<String>>,
+    pub updates: BulkCheckResponse,
+    pub health: ProjectHealthResponse,
+}
+
+/// Response for generate_sbom tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SbomResponse {
+    pub format: SbomFormat,
+    pub component_count: usize,
+    /// File the SBOM was written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+    /// Artifacts, POMs or checksums that could not be loaded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// The CycloneDX or SPDX JSON document
+    pub sbom: serde_json::Value,
+}
+
+/// Response for generate_update_diff tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDiffResponse {
+    /// Path of the edited build file, or "<inline>" for inline content
+    pub file: String,
+    pub kind: BuildFileKind,
+    pub update_policy: UpdatePolicy,
+    pub edits: Vec<VersionEdit>,
+    /// Outdated declarations that could not be edited safely
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedUpdate>,
+    /// Unified diff of all edits, empty when nothing needs updating
+    pub diff: String,
+    /// Whether the edits were written back to the file
+    pub written: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// An outdated declaration left unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedUpdate {
+    pub dependency: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Response for analyze_maven_reactor tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactorAnalysisResponse {
+    pub root: String,
+    pub total_modules: usize,
+    pub modules: Vec<ModuleHealth>,
+    /// Health of every distinct external dependency across all modules
+    pub health: ProjectHealthResponse,
+    /// External dependencies declared at different versions
+    pub version_conflicts: Vec<VersionConflict>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Health breakdown of a single reactor module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleHealth {
+    /// Path of the module's pom.xml relative to the root
+    pub module: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub total_dependencies: usize,
+    pub unresolved_count: usize,
+    pub health_score: f32,
+    pub health_grade: HealthGrade,
+    pub summary: HealthSummary,
 }
 
 /// Response for analyze_dependency_age tool
@@ -94,12 +536,18 @@ pub struct BulkCheckSummary {
 pub struct DependencyAgeResponse {
     pub dependency: String,
     pub version: String,
+    pub version_scheme: VersionScheme,
     pub age_classification: AgeClassification,
     pub versions_since: usize,
     pub stable_versions_since: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub latest_stable: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<UpdateType>,
+    /// Days between the current and latest release for calendar-versioned artifacts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_gap_days: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub recommendation: Option<String>,
 }
 
@@ -108,11 +556,11 @@ pub struct DependencyAgeResponse {
 pub enum AgeClassification {
     /// Using the latest version
     Current,
-    /// 1-2 versions behind
+    /// 1-2 stable versions behind (`scoring.fresh_within`)
     Fresh,
-    /// 3-5 versions behind
+    /// 3-5 stable versions behind (`scoring.aging_within`)
     Aging,
-    /// More than 5 versions behind
+    /// More stable versions behind than `scoring.aging_within`
     Stale,
     /// Using a major version behind
     Outdated,
@@ -139,6 +587,9 @@ pub struct ProjectHealthResponse {
     pub summary: HealthSummary,
     pub dependencies: Vec<DependencyHealthResult>,
     pub recommendations: Vec<String>,
+    /// Inputs that could not be analyzed, e.g. non-Maven SBOM components
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,9 +616,14 @@ pub struct DependencyHealthResult {
     pub update_type: Option<UpdateType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub introduced_by: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Letter grade of a health score, ordered from best (A) to worst (F)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HealthGrade {
     A,
     B,
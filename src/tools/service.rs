@@ -1,16 +1,47 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use futures::future::join_all;
 use rmcp::handler::server::tool::ToolRouter;
 use rmcp::handler::server::wrapper::Parameters;
-use rmcp::model::{CallToolResult, Content, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo};
-use rmcp::{tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler};
+use rmcp::model::{
+    CallToolResult, Content, Implementation, ListResourceTemplatesResult, ListResourcesResult,
+    PaginatedRequestParam, ProtocolVersion, ReadResourceRequestParam, ReadResourceResult,
+    ServerCapabilities, ServerInfo,
+};
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_handler, tool_router, ErrorData as McpError, RoleServer, ServerHandler};
 use schemars::JsonSchema;
-use serde::Deserialize;
-use tracing::{error, info, instrument};
-
-use crate::maven::MavenClient;
-use crate::models::{MavenCoordinate, MavenVersion, UpdateType, VersionStability};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+use crate::build::bazel::MAVEN_INSTALL_JSON;
+use crate::build::edit::{apply_edits, unified_diff};
+use crate::build::gradle::{parse_properties, plugin_marker};
+use crate::build::plugins::{extension_declarations, find_extensions_file, GoalReference};
+use crate::build::reactor::find_version_conflicts;
+use crate::build::resolved::parse_resolved;
+use crate::build::wrapper::{GRADLE_WRAPPER_PROPERTIES, MAVEN_WRAPPER_PROPERTIES};
+use crate::build::{
+    is_within, BuildFileKind, DeclaredDependency, EffectivePom, GradleBuild, MavenInstall, Pom,
+    Reactor, ResolvedFormat, Sbom, VersionCatalog, WrapperKind, WrapperProperties,
+};
+use crate::config::{ConfigError, ServerConfig};
+use crate::maven::{
+    MavenClient, GRADLE_PLUGIN_PORTAL, GRADLE_VERSIONS_ENDPOINT, MAVEN_CENTRAL_BASE,
+};
+use crate::models::{
+    ConstraintSet, MavenCoordinate, MavenVersion, UpdatePolicy, UpdateType, VersionRange,
+    VersionScheme, VersionStability,
+};
+use crate::report::html::health_html;
+use crate::report::markdown::health_markdown;
+use crate::report::sarif::sarif;
+use crate::report::sbom::{artifact_extension, cyclonedx, spdx};
+use crate::report::{ComponentHash, HashAlgorithm, ReportFormat, SbomComponent, SbomFormat};
+use crate::tools::analysis::*;
+use crate::tools::resources::{recent_resources, templates, ArtifactResource};
 use crate::tools::responses::*;
 
 /// MCP Service providing Maven Central tools
@@ -18,15 +49,130 @@ use crate::tools::responses::*;
 pub struct MavenToolsService {
     client: Arc<MavenClient>,
     tool_router: ToolRouter<Self>,
+    /// Config file the client was configured from
+    config_path: Option<PathBuf>,
+    files: FileAccess,
+}
+
+/// Server-side files that tool calls may use
+#[derive(Debug, Clone, Default)]
+enum FileAccess {
+    /// Any file, for a local client over stdio or the CLI
+    #[default]
+    Local,
+    /// Reads under a canonical root and no writes, for clients on other
+    /// hosts. Without a root, file content must be sent inline.
+    Remote { root: Option<PathBuf> },
 }
 
 impl MavenToolsService {
     pub fn new() -> Self {
+        Self::with_client(MavenClient::new())
+    }
+
+    /// Create a service backed by a preconfigured client
+    pub fn with_client(client: MavenClient) -> Self {
         MavenToolsService {
-            client: Arc::new(MavenClient::new()),
+            client: Arc::new(client),
             tool_router: Self::tool_router(),
+            config_path: None,
+            files: FileAccess::Local,
+        }
+    }
+
+    /// Create a service from a validated server config, read from `path`
+    pub fn with_config(config: ServerConfig, path: Option<PathBuf>) -> Result<Self, ConfigError> {
+        Ok(MavenToolsService {
+            config_path: path,
+            ..Self::with_client(MavenClient::with_config(config)?)
+        })
+    }
+
+    /// Service for a new client session. Caches are shared, but the
+    /// session lists only the artifacts it queried as resources.
+    pub fn new_session(&self) -> Self {
+        MavenToolsService {
+            client: Arc::new(self.client.with_new_history()),
+            ..self.clone()
+        }
+    }
+
+    /// Service for clients on other hosts. Path inputs are resolved against
+    /// the canonical `root` and must stay under it; without a root, files
+    /// cannot be read. Files are never written.
+    pub fn for_remote_clients(self, root: Option<PathBuf>) -> Self {
+        MavenToolsService {
+            files: FileAccess::Remote { root },
+            ..self
+        }
+    }
+
+    /// Directory that files read on behalf of the client, such as parent
+    /// POMs, must stay under
+    fn file_root(&self) -> Option<&Path> {
+        match &self.files {
+            FileAccess::Local => None,
+            FileAccess::Remote { root } => root.as_deref(),
+        }
+    }
+
+    /// The file to read for a path input, if the session may read it
+    async fn input_path(
+        &self,
+        path: Option<impl AsRef<Path>>,
+    ) -> Result<Option<PathBuf>, McpError> {
+        let Some(path) = path else {
+            return Ok(None);
+        };
+        let path = path.as_ref();
+        match &self.files {
+            FileAccess::Local => Ok(Some(path.to_path_buf())),
+            FileAccess::Remote { root: None } => Err(McpError::invalid_params(
+                format!(
+                    "Cannot read {}: HTTP clients must send file content inline",
+                    path.display()
+                ),
+                None,
+            )),
+            FileAccess::Remote { root: Some(root) } => {
+                let resolved = tokio::fs::canonicalize(root.join(path))
+                    .await
+                    .map_err(|e| {
+                        McpError::invalid_params(
+                            format!("Failed to read {}: {}", path.display(), e),
+                            None,
+                        )
+                    })?;
+                if resolved.starts_with(root) {
+                    Ok(Some(resolved))
+                } else {
+                    Err(McpError::invalid_params(
+                        format!(
+                            "Cannot read {}: outside the directory HTTP clients may read",
+                            path.display()
+                        ),
+                        None,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Fail when the session may not write files, naming the parameter
+    fn check_writable(&self, parameter: &str) -> Result<(), McpError> {
+        match self.files {
+            FileAccess::Local => Ok(()),
+            FileAccess::Remote { .. } => Err(McpError::invalid_params(
+                format!("{} is not available to HTTP clients", parameter),
+                None,
+            )),
         }
     }
+
+    /// The requested update policy, else the configured default
+    fn update_policy(&self, requested: Option<UpdatePolicy>) -> UpdatePolicy {
+        requested.unwrap_or(self.client.config().policies.update_policy)
+    }
 }
 
 impl Default for MavenToolsService {
@@ -41,16 +187,49 @@ impl ServerHandler for MavenToolsService {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(
                 "Maven Central MCP Server - Query Maven Central for dependency versions, \
                  updates, and project health analysis. Supports Maven coordinates in format \
-                 'groupId:artifactId' or 'groupId:artifactId:version'."
+                 'groupId:artifactId' or 'groupId:artifactId:version'. Artifact versions, \
+                 POMs and dependencies are also available as maven:// resources."
                     .to_string(),
             ),
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let resources = recent_resources(&self.client);
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(templates()))
+    }
+
+    #[instrument(skip(self, _context))]
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let resource = ArtifactResource::parse(&request.uri).ok_or_else(|| {
+            McpError::resource_not_found(format!("Unknown resource '{}'", request.uri), None)
+        })?;
+        resource.read(&self.client).await
+    }
 }
 
 // Tool parameter structs with JSON Schema derivation for MCP
@@ -67,6 +246,24 @@ pub struct GetLatestVersionParams {
     pub prefer_stable: bool,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetGradlePluginVersionParams {
+    /// Gradle plugin id as written in `plugins { id("...") }`
+    #[schemars(description = "Gradle plugin id like 'org.jetbrains.kotlin.jvm'")]
+    pub plugin_id: String,
+
+    /// Plugin version whose implementation artifact is resolved
+    #[schemars(
+        description = "Plugin version to resolve the implementation artifact for (default: the latest version)"
+    )]
+    pub version: Option<String>,
+
+    /// If true, prioritize stable versions over pre-release versions
+    #[schemars(description = "Prioritize stable versions (default: true)")]
+    #[serde(default = "default_true")]
+    pub prefer_stable: bool,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CheckVersionExistsParams {
     /// Maven coordinate with version in format "groupId:artifactId:version"
@@ -88,6 +285,16 @@ pub struct CompareVersionsParams {
     #[schemars(description = "Only suggest stable version upgrades (default: true)")]
     #[serde(default = "default_true")]
     pub stable_only: bool,
+
+    /// Recommend the newest version in the same suffix stream (e.g. -jre vs -android)
+    #[schemars(
+        description = "Recommend the newest version in the same version stream, e.g. '-jre' vs '-android' (default: true)"
+    )]
+    #[serde(default = "default_true")]
+    pub same_stream: bool,
+
+    /// Which maintenance line decides whether the dependency is outdated
+    pub update_policy: Option<UpdatePolicy>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -96,12 +303,33 @@ pub struct CheckMultipleDependenciesParams {
     #[schemars(
         description = "List of Maven coordinates like ['org.springframework:spring-core:5.3.0', 'com.google.guava:guava:31.0-jre']"
     )]
+    #[serde(default)]
     pub dependencies: Vec<String>,
 
+    /// Path to a CycloneDX SBOM whose Maven components are checked
+    #[schemars(
+        description = "Path to a CycloneDX SBOM (JSON or XML) whose Maven components are checked"
+    )]
+    pub sbom_path: Option<String>,
+
+    /// Inline CycloneDX SBOM, used when no sbom_path is given
+    #[schemars(description = "Inline CycloneDX SBOM content (alternative to sbom_path)")]
+    pub sbom_content: Option<String>,
+
     /// Only show stable versions in results
     #[schemars(description = "Only suggest stable version upgrades (default: true)")]
     #[serde(default = "default_true")]
     pub stable_only: bool,
+
+    /// Recommend the newest version in the same suffix stream (e.g. -jre vs -android)
+    #[schemars(
+        description = "Recommend the newest version in the same version stream, e.g. '-jre' vs '-android' (default: true)"
+    )]
+    #[serde(default = "default_true")]
+    pub same_stream: bool,
+
+    /// Which maintenance line decides whether the dependency is outdated
+    pub update_policy: Option<UpdatePolicy>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -119,13 +347,426 @@ pub struct AnalyzeProjectHealthParams {
     #[schemars(
         description = "List of Maven coordinates with versions like ['org.springframework:spring-core:5.3.0', 'com.fasterxml.jackson.core:jackson-core:2.15.0']"
     )]
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+
+    /// Path to a file listing resolved dependencies
+    #[schemars(
+        description = "Path to a gradle.lockfile, or saved 'mvn dependency:list' / 'mvn dependency:tree' output, whose resolved versions are analyzed"
+    )]
+    pub resolved_path: Option<String>,
+
+    /// Inline resolved dependency listing, used when no resolved_path is given
+    #[schemars(
+        description = "Inline lockfile or dependency:list/tree output (alternative to resolved_path)"
+    )]
+    pub resolved_content: Option<String>,
+
+    /// Format of the resolved listing, detected when omitted
+    #[schemars(
+        description = "Format of the resolved listing: 'gradle_lockfile', 'dependency_list' or 'dependency_tree' (default: detected)"
+    )]
+    pub resolved_format: Option<ResolvedFormat>,
+
+    /// Path to a CycloneDX SBOM whose Maven components are analyzed
+    #[schemars(
+        description = "Path to a CycloneDX SBOM (JSON or XML) whose Maven components are analyzed"
+    )]
+    pub sbom_path: Option<String>,
+
+    /// Inline CycloneDX SBOM, used when no sbom_path is given
+    #[schemars(description = "Inline CycloneDX SBOM content (alternative to sbom_path)")]
+    pub sbom_content: Option<String>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResolveVersionRangeParams {
+    /// Maven coordinate, optionally with the range as its version
+    #[schemars(
+        description = "Maven coordinate like 'com.fasterxml.jackson.core:jackson-databind' or with a range as version like 'com.fasterxml.jackson.core:jackson-databind:[2.13,2.16)'"
+    )]
+    pub dependency: String,
+
+    /// Version range; overrides the version part of the coordinate
+    #[schemars(
+        description = "Maven range like '[1.2,2.0)' or '(,1.5]', or Gradle dynamic version like '1.+' or 'latest.release'"
+    )]
+    pub range: Option<String>,
+
+    /// Only consider stable versions
+    #[schemars(description = "Only match stable versions (default: false, like Maven and Gradle)")]
+    #[serde(default)]
+    pub stable_only: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SolveVersionConstraintsParams {
+    /// Maven coordinate in format "groupId:artifactId"
+    #[schemars(
+        description = "Maven coordinate like 'com.fasterxml.jackson.core:jackson-databind'"
+    )]
+    pub dependency: String,
+
+    /// Version constraints that must all hold
+    #[schemars(
+        description = "Constraints that must all hold, e.g. ['[2.13,2.16)', '2.15.+', '>=2.14.2']. Supports Maven ranges, Gradle dynamic versions and comparators (>=, >, <=, <, =)"
+    )]
+    pub constraints: Vec<String>,
+
+    /// Only consider stable versions
+    #[schemars(description = "Only match stable versions (default: false, like Maven and Gradle)")]
+    #[serde(default)]
+    pub stable_only: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExplainVersionStabilityParams {
+    /// Maven coordinate with version in format "groupId:artifactId:version"
+    #[schemars(
+        description = "Maven coordinate with version like 'com.google.guava:guava:33.0.0-jre'"
+    )]
+    pub dependency: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzePomParams {
+    /// Path to a pom.xml file
+    #[schemars(
+        description = "Path to a pom.xml file; local parent POMs are resolved relative to it"
+    )]
+    pub path: Option<String>,
+
+    /// Inline pom.xml content, used when no path is given
+    #[schemars(description = "Inline pom.xml content (alternative to path)")]
+    pub content: Option<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether the dependency is outdated
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzeMavenPluginsParams {
+    /// Path to a pom.xml or the directory containing it
+    #[schemars(description = "Path to a pom.xml or the directory containing it")]
+    pub path: Option<String>,
+
+    /// Inline pom.xml content, used when no path is given
+    #[schemars(description = "Inline pom.xml content (alternative to path)")]
+    pub content: Option<String>,
+
+    /// Goals invoked on the command line, resolved to plugins through their prefix
+    #[schemars(
+        description = "Command-line goals like 'versions:display-dependency-updates' or 'org.jacoco:jacoco-maven-plugin:0.8.11:report'"
+    )]
+    #[serde(default)]
+    pub goals: Vec<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether the plugin is outdated
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CheckWrapperVersionsParams {
+    /// Project directory, or a maven-wrapper.properties / gradle-wrapper.properties file
+    #[schemars(
+        description = "Project directory containing .mvn/wrapper or gradle/wrapper, or the path of a maven-wrapper.properties or gradle-wrapper.properties file"
+    )]
+    pub path: String,
+
+    /// Endpoint listing Gradle distributions
+    #[schemars(
+        description = "Endpoint listing Gradle distributions as JSON (default: https://services.gradle.org/versions/all)"
+    )]
+    pub gradle_versions_url: Option<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether the wrapper is outdated
+    pub update_policy: Option<UpdatePolicy>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzeBazelPinsParams {
+    /// Path to maven_install.json or the workspace directory containing it
+    #[schemars(
+        description = "Path to a Bazel rules_jvm_external maven_install.json, or the workspace directory containing it"
+    )]
+    pub path: Option<String>,
+
+    /// Inline pin file content, used when no path is given
+    #[schemars(description = "Inline maven_install.json content (alternative to path)")]
+    pub content: Option<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether a pin is outdated
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GenerateSbomParams {
+    /// Coordinates to describe
+    #[schemars(description = "Dependencies as 'groupId:artifactId:version' coordinates")]
     pub dependencies: Vec<String>,
+
+    /// Document format
+    #[schemars(
+        description = "SBOM format: 'cyclonedx' (CycloneDX 1.5 JSON, default) or 'spdx' (SPDX 2.3 JSON)"
+    )]
+    #[serde(default)]
+    pub format: SbomFormat,
+
+    /// Also describe what the coordinates depend on
+    #[schemars(
+        description = "Also include the compile and runtime dependencies of the coordinates, resolved from their POMs (default: false)"
+    )]
+    #[serde(default)]
+    pub include_transitive: bool,
+
+    /// Name of the described application
+    #[schemars(
+        description = "Name of the application the SBOM describes (default: 'application')"
+    )]
+    pub name: Option<String>,
+
+    /// Write the document to a file
+    #[schemars(description = "Also write the SBOM to this file (not available over HTTP)")]
+    pub output_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GenerateUpdateDiffParams {
+    /// Path to a pom.xml, build.gradle(.kts) or libs.versions.toml
+    #[schemars(description = "Path to a pom.xml, build.gradle(.kts) or gradle/libs.versions.toml")]
+    pub path: Option<String>,
+
+    /// Inline build file content, used when no path is given
+    #[schemars(description = "Inline build file content (alternative to path)")]
+    pub content: Option<String>,
+
+    /// Kind of build file, detected from the file name or content when omitted
+    #[schemars(
+        description = "Build file kind: 'pom', 'gradle' or 'version_catalog' (default: detected)"
+    )]
+    pub kind: Option<BuildFileKind>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line the edits move dependencies to
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Directory the diff paths are relative to
+    #[schemars(
+        description = "Directory the file names in the diff are relative to (default: the project root above path, e.g. the top of a multi-module build)"
+    )]
+    pub base_dir: Option<String>,
+
+    /// Write the edited file back to disk
+    #[schemars(
+        description = "Write the edits to the file at path instead of only returning the diff (default: false; not available over HTTP)"
+    )]
+    #[serde(default)]
+    pub write: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzeMavenReactorParams {
+    /// Path to the root pom.xml or its directory
+    #[schemars(
+        description = "Path to the root pom.xml of a multi-module build, or the directory containing it"
+    )]
+    pub path: String,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzeGradleBuildParams {
+    /// Path to a build.gradle or build.gradle.kts file
+    #[schemars(
+        description = "Path to a build.gradle or build.gradle.kts file; gradle.properties next to it is read for version variables"
+    )]
+    pub path: Option<String>,
+
+    /// Inline build script content, used when no path is given
+    #[schemars(
+        description = "Inline build.gradle or build.gradle.kts content (alternative to path)"
+    )]
+    pub content: Option<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether the dependency is outdated
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AnalyzeVersionCatalogParams {
+    /// Path to a Gradle version catalog
+    #[schemars(description = "Path to a version catalog like 'gradle/libs.versions.toml'")]
+    pub path: Option<String>,
+
+    /// Inline catalog content, used when no path is given
+    #[schemars(description = "Inline libs.versions.toml content (alternative to path)")]
+    pub content: Option<String>,
+
+    /// Only compare against stable versions
+    #[schemars(description = "Only suggest stable version upgrades (default: true)")]
+    #[serde(default = "default_true")]
+    pub stable_only: bool,
+
+    /// Which maintenance line decides whether the dependency is outdated
+    pub update_policy: Option<UpdatePolicy>,
+
+    /// Format of the tool result
+    #[serde(default)]
+    pub output_format: ReportFormat,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Read a file, or take inline content in its place. Returns the content
+/// and the name to report locations under.
+async fn read_input(
+    path: Option<impl AsRef<Path>>,
+    content: Option<String>,
+) -> Result<Option<(String, String)>, McpError> {
+    match (path, content) {
+        (Some(path), _) => {
+            let path = path.as_ref().display().to_string();
+            let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                McpError::invalid_params(format!("Failed to read {}: {}", path, e), None)
+            })?;
+            Ok(Some((content, path)))
+        }
+        (None, Some(content)) => Ok(Some((content, "<inline>".to_string()))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// [`read_input`] for tools that need one of the two
+async fn require_input(
+    path: Option<impl AsRef<Path>>,
+    content: Option<String>,
+) -> Result<(String, String), McpError> {
+    read_input(path, content)
+        .await?
+        .ok_or_else(|| McpError::invalid_params("Either path or content is required", None))
+}
+
+/// `path`, or the file called `name` inside it when `path` is a directory
+async fn file_in_dir(path: &Path, name: &str) -> PathBuf {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) if metadata.is_dir() => path.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Properties from the gradle.properties next to a build script, if any
+/// and under `root`
+async fn read_gradle_properties(
+    build_file: Option<&Path>,
+    root: Option<&Path>,
+) -> HashMap<String, String> {
+    let Some(dir) = build_file.and_then(Path::parent) else {
+        return HashMap::new();
+    };
+    let file = dir.join("gradle.properties");
+    if !is_within(&file, root).await {
+        return HashMap::new();
+    }
+    tokio::fs::read_to_string(file)
+        .await
+        .map(|p| parse_properties(&p))
+        .unwrap_or_default()
+}
+
+/// Serialize a tool response, or render the health results it contains in
+/// another report format under `title`
+async fn render_report<T: Serialize>(
+    client: &MavenClient,
+    response: &T,
+    title: &str,
+    health: &ProjectHealthResponse,
+    format: ReportFormat,
+) -> Result<CallToolResult, McpError> {
+    let text = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(response),
+        ReportFormat::Sarif => {
+            let relocations = find_relocations(client, &health.dependencies).await;
+            let scoring = &client.config().scoring;
+            serde_json::to_string_pretty(&sarif(&health.dependencies, &relocations, scoring))
+        }
+        ReportFormat::Markdown => Ok(health_markdown(title, health)),
+        ReportFormat::Html => Ok(health_html(title, health, chrono::Utc::now())),
+    }
+    .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+    Ok(CallToolResult::success(vec![Content::text(text)]))
+}
+
+/// Add the Maven components of a CycloneDX SBOM, returning a warning for
+/// each component that is skipped
+fn add_sbom_inputs(
+    inputs: &mut Vec<DependencyInput>,
+    content: &str,
+    source: &str,
+) -> Result<Vec<String>, McpError> {
+    let sbom = Sbom::parse(content)
+        .map_err(|e| McpError::invalid_params(format!("{}: {}", source, e), None))?;
+    add_resolved_inputs(inputs, source, &sbom.dependencies);
+    Ok(sbom
+        .skipped
+        .into_iter()
+        .map(|s| format!("Skipped SBOM component {}", s))
+        .collect())
+}
+
 // Tool implementations
 
 #[tool_router]
@@ -136,7 +777,7 @@ impl MavenToolsService {
         description = "Get the latest version of a Maven dependency from Maven Central with stability classification (stable, RC, beta, alpha, milestone)"
     )]
     #[instrument(skip(self))]
-    async fn get_latest_version(
+    pub(crate) async fn get_latest_version(
         &self,
         params: Parameters<GetLatestVersionParams>,
     ) -> Result<CallToolResult, McpError> {
@@ -172,19 +813,110 @@ impl MavenToolsService {
         )]))
     }
 
-    /// Check if a specific version exists on Maven Central
+    /// Get the latest version of a Gradle plugin by its plugin id
     #[tool(
-        name = "check_version_exists",
-        description = "Verify if a specific version of a Maven dependency exists on Maven Central and get its stability classification"
+        name = "get_gradle_plugin_version",
+        description = "Get the latest versions of a Gradle plugin by plugin id (e.g. 'org.jetbrains.kotlin.jvm') from the Gradle Plugin Portal. Resolves the id to its '<id>:<id>.gradle.plugin' marker artifact and the marker to the implementation artifact it points to"
     )]
     #[instrument(skip(self))]
-    async fn check_version_exists(
+    async fn get_gradle_plugin_version(
         &self,
-        params: Parameters<CheckVersionExistsParams>,
+        params: Parameters<GetGradlePluginVersionParams>,
     ) -> Result<CallToolResult, McpError> {
-        info!("check_version_exists: {}", params.0.dependency);
+        let params = params.0;
+        let plugin_id = params.plugin_id.trim();
+        info!("get_gradle_plugin_version: {}", plugin_id);
 
-        let coordinate = MavenCoordinate::parse(&params.0.dependency)
+        let marker = format!("{}:{}", plugin_id, plugin_marker(plugin_id));
+        let coordinate = MavenCoordinate::parse(&marker)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let metadata = self
+            .client
+            .get_metadata_in(GRADLE_PLUGIN_PORTAL, &coordinate)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let latest = if params.prefer_stable {
+            metadata
+                .latest_stable
+                .clone()
+                .or(metadata.latest_any.clone())
+        } else {
+            metadata.latest_any.clone()
+        };
+        let resolved_version = params.version.or(latest);
+
+        // The marker POM has a single dependency on the implementation artifact
+        let mut error = None;
+        let mut implementation = None;
+        if let Some(version) = &resolved_version {
+            let marker_version = MavenCoordinate {
+                version: Some(version.clone()),
+                ..coordinate.clone()
+            };
+            match self
+                .client
+                .get_pom_in(GRADLE_PLUGIN_PORTAL, &marker_version)
+                .await
+                .and_then(|pom| Ok(Pom::parse(&pom)?))
+            {
+                Ok(pom) => {
+                    implementation = pom.dependencies.first().and_then(|d| {
+                        Some(format!(
+                            "{}:{}:{}",
+                            d.group_id.as_deref()?,
+                            d.artifact_id,
+                            d.version.as_deref()?
+                        ))
+                    });
+                    if implementation.is_none() {
+                        error = Some("Marker POM declares no implementation artifact".to_string());
+                    }
+                }
+                Err(e) => error = Some(e.to_string()),
+            }
+        }
+
+        let response = GradlePluginResponse {
+            plugin_id: plugin_id.to_string(),
+            marker,
+            repository: GRADLE_PLUGIN_PORTAL.to_string(),
+            latest: LatestVersions {
+                stable: metadata.latest_stable.clone(),
+                rc: metadata.latest_rc.clone(),
+                beta: metadata.latest_beta.clone(),
+                alpha: metadata.latest_alpha.clone(),
+                milestone: metadata.latest_milestone.clone(),
+                any: metadata.latest_any.clone(),
+            },
+            total_versions: metadata.all_versions.len(),
+            stable_versions: metadata.stable_versions.len(),
+            last_updated: metadata.last_updated.clone(),
+            resolved_version,
+            implementation,
+            error,
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Check if a specific version exists on Maven Central
+    #[tool(
+        name = "check_version_exists",
+        description = "Verify if a specific version of a Maven dependency exists on Maven Central and get its stability classification"
+    )]
+    #[instrument(skip(self))]
+    async fn check_version_exists(
+        &self,
+        params: Parameters<CheckVersionExistsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("check_version_exists: {}", params.0.dependency);
+
+        let coordinate = MavenCoordinate::parse(&params.0.dependency)
             .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
         let version = coordinate
@@ -205,7 +937,7 @@ impl MavenToolsService {
             version: version.clone(),
             exists,
             stability: if exists {
-                Some(VersionStability::classify(&version))
+                Some(self.client.classify(&coordinate, &version))
             } else {
                 None
             },
@@ -244,24 +976,31 @@ impl MavenToolsService {
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
-        let target_version = if params.0.stable_only {
-            metadata.latest_stable.clone()
-        } else {
-            metadata.latest_any.clone()
+        let current_parsed = MavenVersion::parse(&current_version);
+        let stream = current_parsed.stream.as_deref();
+        let options = CheckOptions {
+            stable_only: params.0.stable_only,
+            same_stream: params.0.same_stream,
+            update_policy: self.update_policy(params.0.update_policy),
         };
+        let targets = update_targets(&metadata, &current_parsed, options);
 
-        let target_version = target_version
-            .ok_or_else(|| McpError::internal_error("No versions found", None))?;
+        if targets.latest_major.is_none() {
+            return Err(McpError::internal_error("No versions found", None));
+        }
+
+        // Stay on the current version when the policy's line has no eligible target
+        let target_version = targets
+            .for_policy(options.update_policy)
+            .cloned()
+            .unwrap_or_else(|| current_version.clone());
 
-        let current_parsed = MavenVersion::parse(&current_version);
         let target_parsed = MavenVersion::parse(&target_version);
 
         let is_outdated = current_parsed < target_parsed;
-        let update_type = if is_outdated {
-            Some(UpdateType::between(&current_version, &target_version))
-        } else {
-            None
-        };
+        let update_type =
+            self.client
+                .update_type(&coordinate.group_id, &current_version, &target_version);
 
         // Count versions between current and latest
         let versions_behind = metadata
@@ -269,17 +1008,26 @@ impl MavenToolsService {
             .iter()
             .filter(|v| {
                 let parsed = MavenVersion::parse(v);
-                parsed > current_parsed && parsed <= target_parsed
+                (!params.0.same_stream || parsed.stream == current_parsed.stream)
+                    && parsed > current_parsed
+                    && parsed <= target_parsed
             })
             .count();
 
         let recommendation = if is_outdated {
             Some(format!(
                 "Consider upgrading from {} to {} ({} update, {} versions behind)",
-                current_version,
-                target_version,
-                update_type.unwrap_or(UpdateType::Other),
-                versions_behind
+                current_version, target_version, update_type, versions_behind
+            ))
+        } else if update_type == UpdateType::Downgrade {
+            let kind = if params.0.stable_only {
+                "stable"
+            } else {
+                "published"
+            };
+            Some(format!(
+                "You're ahead of the latest {} version ({})",
+                kind, target_version
             ))
         } else {
             Some("You're using the latest version!".to_string())
@@ -288,12 +1036,18 @@ impl MavenToolsService {
         let response = VersionComparisonResponse {
             dependency: coordinate.to_ga(),
             current_version: current_version.clone(),
-            current_stability: VersionStability::classify(&current_version),
+            current_stability: self.client.classify(&coordinate, &current_version),
             latest_version: target_version.clone(),
-            latest_stability: VersionStability::classify(&target_version),
+            latest_stability: self.client.classify(&coordinate, &target_version),
             is_outdated,
-            update_type,
+            update_type: Some(update_type),
+            update_policy: options.update_policy,
+            targets,
             versions_behind,
+            stream: current_parsed.stream.clone(),
+            alternatives: StreamAlternative::from_pairs(
+                metadata.stream_alternatives(stream, params.0.stable_only),
+            ),
             recommendation,
         };
 
@@ -306,71 +1060,55 @@ impl MavenToolsService {
     /// Check multiple dependencies for updates in bulk
     #[tool(
         name = "check_multiple_dependencies",
-        description = "Bulk check multiple Maven dependencies for available updates. Efficient for analyzing entire projects."
+        description = "Bulk check multiple Maven dependencies for available updates. Efficient for analyzing entire projects. Also accepts a CycloneDX SBOM, checking its Maven components and naming the direct dependency each transitive one comes in through"
     )]
     #[instrument(skip(self))]
-    async fn check_multiple_dependencies(
+    pub(crate) async fn check_multiple_dependencies(
         &self,
         params: Parameters<CheckMultipleDependenciesParams>,
     ) -> Result<CallToolResult, McpError> {
-        info!(
-            "check_multiple_dependencies: {} dependencies",
-            params.0.dependencies.len()
-        );
+        let params = params.0;
+        let mut inputs = DependencyInput::from_coordinates(params.dependencies);
+        let mut warnings = Vec::new();
+        let sbom_path = self.input_path(params.sbom_path.as_deref()).await?;
+        let sbom = read_input(sbom_path, params.sbom_content).await?;
+        if let Some((content, source)) = &sbom {
+            warnings = add_sbom_inputs(&mut inputs, content, source)?;
+        } else if inputs.is_empty() {
+            return Err(McpError::invalid_params(
+                "Either dependencies or an sbom_path/sbom_content is required",
+                None,
+            ));
+        }
+        info!("check_multiple_dependencies: {} dependencies", inputs.len());
 
         let client = self.client.clone();
-        let stable_only = params.0.stable_only;
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: params.same_stream,
+            update_policy: self.update_policy(params.update_policy),
+        };
 
         // Process all dependencies concurrently
-        let futures: Vec<_> = params
-            .0
-            .dependencies
-            .iter()
-            .map(|dep| {
+        let futures: Vec<_> = inputs
+            .into_iter()
+            .map(|input| {
                 let client = client.clone();
-                let dep = dep.clone();
-                async move { check_single_dependency(&client, &dep, stable_only).await }
+                async move {
+                    let repository = MAVEN_CENTRAL_BASE;
+                    let mut result =
+                        check_single_dependency(&client, repository, &input.coordinate, options)
+                            .await;
+                    result.location = input.location;
+                    result.introduced_by = input.introduced_by;
+                    result
+                }
             })
             .collect();
 
         let results = join_all(futures).await;
-
-        // Aggregate results
-        let mut outdated_count = 0;
-        let mut up_to_date_count = 0;
-        let mut error_count = 0;
-        let mut major_updates = 0;
-        let mut minor_updates = 0;
-        let mut patch_updates = 0;
-
-        for result in &results {
-            if result.error.is_some() {
-                error_count += 1;
-            } else if result.is_outdated {
-                outdated_count += 1;
-                match result.update_type {
-                    Some(UpdateType::Major) => major_updates += 1,
-                    Some(UpdateType::Minor) => minor_updates += 1,
-                    Some(UpdateType::Patch) => patch_updates += 1,
-                    _ => {}
-                }
-            } else {
-                up_to_date_count += 1;
-            }
-        }
-
-        let response = BulkCheckResponse {
-            total_checked: params.0.dependencies.len(),
-            outdated_count,
-            up_to_date_count,
-            error_count,
-            dependencies: results,
-            summary: BulkCheckSummary {
-                major_updates,
-                minor_updates,
-                patch_updates,
-            },
-        };
+        let mut response = bulk_check_response(results, options.update_policy);
+        response.warnings = warnings;
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&response)
@@ -404,48 +1142,26 @@ impl MavenToolsService {
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
+        // Classify within the version's stream, as the health tools do
+        let health = health_against_metadata(
+            &self.client,
+            &coordinate,
+            current_version.clone(),
+            &metadata,
+        );
+        let age_classification = health
+            .age_classification
+            .unwrap_or(AgeClassification::Current);
         let current_parsed = MavenVersion::parse(&current_version);
-
-        // Count total versions newer than current
-        let versions_since = metadata
-            .all_versions
-            .iter()
-            .filter(|v| MavenVersion::parse(v) > current_parsed)
-            .count();
-
-        // Count stable versions newer than current
-        let stable_versions_since = metadata
-            .stable_versions
-            .iter()
-            .filter(|v| MavenVersion::parse(v) > current_parsed)
-            .count();
-
-        // Determine age classification based on stable versions behind
-        let age_classification = if stable_versions_since == 0 {
-            AgeClassification::Current
-        } else if stable_versions_since <= 2 {
-            AgeClassification::Fresh
-        } else if stable_versions_since <= 5 {
-            AgeClassification::Aging
-        } else {
-            // Check if it's a major version behind
-            if let Some(latest) = &metadata.latest_stable {
-                let update_type = UpdateType::between(&current_version, latest);
-                if update_type == UpdateType::Major {
-                    AgeClassification::Outdated
-                } else {
-                    AgeClassification::Stale
-                }
-            } else {
-                AgeClassification::Stale
-            }
-        };
+        let versions_since = metadata.newer_in_stream(&current_parsed, false);
+        let stable_versions_since = metadata.newer_in_stream(&current_parsed, true);
+        let update_type = health.update_type.filter(|_| stable_versions_since > 0);
 
         let recommendation = match age_classification {
             AgeClassification::Current => None,
-            AgeClassification::Fresh => Some(
-                "Minor updates available. Consider upgrading when convenient.".to_string(),
-            ),
+            AgeClassification::Fresh => {
+                Some("Minor updates available. Consider upgrading when convenient.".to_string())
+            }
             AgeClassification::Aging => Some(format!(
                 "Several versions behind ({}). Plan an upgrade soon.",
                 stable_versions_since
@@ -459,13 +1175,21 @@ impl MavenToolsService {
             }
         };
 
+        let release_gap_days = health
+            .latest_version
+            .as_ref()
+            .and_then(|latest| UpdateType::elapsed_days(&current_version, latest));
+
         let response = DependencyAgeResponse {
             dependency: coordinate.to_ga(),
+            version_scheme: VersionScheme::detect(&current_version),
             version: current_version,
             age_classification,
             versions_since,
             stable_versions_since,
-            latest_stable: metadata.latest_stable.clone(),
+            latest_stable: health.latest_version,
+            update_type,
+            release_gap_days,
             recommendation,
         };
 
@@ -478,102 +1202,763 @@ impl MavenToolsService {
     /// Analyze overall project dependency health
     #[tool(
         name = "analyze_project_health",
-        description = "Comprehensive health analysis of all project dependencies with overall health score and grade (A-F)"
+        description = "Comprehensive health analysis of all project dependencies with overall health score and grade (A-F). Accepts coordinates, the resolved versions of a Gradle lockfile or 'mvn dependency:list' / 'mvn dependency:tree' output, and/or a CycloneDX SBOM"
     )]
-    #[instrument(skip(self))]
-    async fn analyze_project_health(
+    #[instrument(skip(self, params))]
+    pub(crate) async fn analyze_project_health(
         &self,
         params: Parameters<AnalyzeProjectHealthParams>,
     ) -> Result<CallToolResult, McpError> {
-        info!(
-            "analyze_project_health: {} dependencies",
-            params.0.dependencies.len()
-        );
+        let params = params.0;
+        let mut inputs = DependencyInput::from_coordinates(params.dependencies);
+        let mut warnings = Vec::new();
+
+        let resolved_path = self.input_path(params.resolved_path.as_deref()).await?;
+        let resolved = read_input(resolved_path, params.resolved_content).await?;
+        if let Some((content, source)) = &resolved {
+            let format = params.resolved_format.unwrap_or_else(|| {
+                ResolvedFormat::detect(params.resolved_path.as_deref(), content)
+            });
+            add_resolved_inputs(&mut inputs, source, &parse_resolved(format, content));
+        }
+        let sbom_path = self.input_path(params.sbom_path.as_deref()).await?;
+        let sbom = read_input(sbom_path, params.sbom_content).await?;
+        if let Some((content, source)) = &sbom {
+            warnings = add_sbom_inputs(&mut inputs, content, source)?;
+        }
+        if inputs.is_empty() && resolved.is_none() && sbom.is_none() {
+            return Err(McpError::invalid_params(
+                "Either dependencies, a resolved_path/resolved_content or an sbom_path/sbom_content is required",
+                None,
+            ));
+        }
+        info!("analyze_project_health: {} dependencies", inputs.len());
 
         let client = self.client.clone();
 
         // Process all dependencies concurrently
-        let futures: Vec<_> = params
-            .0
-            .dependencies
-            .iter()
-            .map(|dep| {
+        let futures: Vec<_> = inputs
+            .into_iter()
+            .map(|input| {
                 let client = client.clone();
-                let dep = dep.clone();
-                async move { analyze_single_health(&client, &dep).await }
+                async move {
+                    let mut result =
+                        analyze_single_health(&client, MAVEN_CENTRAL_BASE, &input.coordinate).await;
+                    result.location = input.location;
+                    result.introduced_by = input.introduced_by;
+                    result
+                }
             })
             .collect();
 
         let results = join_all(futures).await;
+        let mut response = project_health_response(results);
+        response.warnings = warnings;
+
+        render_report(
+            &self.client,
+            &response,
+            "Dependency health",
+            &response,
+            params.output_format,
+        )
+        .await
+    }
 
-        // Calculate summary
-        let mut summary = HealthSummary {
-            current: 0,
-            fresh: 0,
-            aging: 0,
-            stale: 0,
-            outdated: 0,
-            errors: 0,
-        };
-
-        let mut total_score = 0.0;
-        let mut scored_count = 0;
-
-        for result in &results {
-            if result.error.is_some() {
-                summary.errors += 1;
-            } else if let Some(age) = result.age_classification {
-                scored_count += 1;
-                total_score += result.health_score;
-                match age {
-                    AgeClassification::Current => summary.current += 1,
-                    AgeClassification::Fresh => summary.fresh += 1,
-                    AgeClassification::Aging => summary.aging += 1,
-                    AgeClassification::Stale => summary.stale += 1,
-                    AgeClassification::Outdated => summary.outdated += 1,
-                }
-            }
+    /// Resolve a Maven version range or Gradle dynamic version
+    #[tool(
+        name = "resolve_version_range",
+        description = "Resolve a Maven version range ('[1.2,2.0)', '(,1.5]') or Gradle dynamic version ('1.+', 'latest.release') against Maven Central, returning the selected version and every matching version"
+    )]
+    #[instrument(skip(self))]
+    async fn resolve_version_range(
+        &self,
+        params: Parameters<ResolveVersionRangeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("resolve_version_range: {}", params.0.dependency);
+
+        let coordinate = MavenCoordinate::parse(&params.0.dependency)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let range = params
+            .0
+            .range
+            .clone()
+            .or_else(|| coordinate.version.clone())
+            .ok_or_else(|| McpError::invalid_params("A version range is required", None))?;
+
+        let parsed_range = VersionRange::parse(&range)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let matching_versions = self
+            .client
+            .resolve_range(&coordinate, &parsed_range, params.0.stable_only)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let metadata = self
+            .client
+            .get_metadata(&coordinate)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let resolved_version = matching_versions.first().cloned();
+
+        let response = VersionRangeResponse {
+            dependency: coordinate.to_ga(),
+            range,
+            parsed_range,
+            resolved_stability: resolved_version
+                .as_ref()
+                .map(|v| self.client.classify(&coordinate, v)),
+            resolved_version,
+            matching_versions,
+            total_versions: metadata.all_versions.len(),
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Find versions satisfying several range constraints at once
+    #[tool(
+        name = "solve_version_constraints",
+        description = "Intersect several version constraints (Maven ranges, Gradle dynamic versions, comparators like '>=2.14.2') over the published versions. Returns the newest version satisfying all of them, or a minimal explanation of which constraints conflict"
+    )]
+    #[instrument(skip(self))]
+    async fn solve_version_constraints(
+        &self,
+        params: Parameters<SolveVersionConstraintsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        info!(
+            "solve_version_constraints: {} ({} constraints)",
+            params.0.dependency,
+            params.0.constraints.len()
+        );
+
+        let coordinate = MavenCoordinate::parse(&params.0.dependency)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        if params.0.constraints.is_empty() {
+            return Err(McpError::invalid_params(
+                "At least one constraint is required",
+                None,
+            ));
         }
 
-        let health_score = if scored_count > 0 {
-            total_score / scored_count as f32
+        let constraints = ConstraintSet::parse(&params.0.constraints)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let metadata = self
+            .client
+            .get_metadata(&coordinate)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let versions: Vec<(String, VersionStability)> = metadata
+            .all_versions
+            .iter()
+            .map(|v| (v.clone(), self.client.classify(&coordinate, v)))
+            .filter(|(_, s)| !params.0.stable_only || s.is_stable())
+            .collect();
+
+        let solution = constraints.solve(&versions);
+
+        let results: Vec<ConstraintResult> = constraints
+            .raw()
+            .zip(constraints.ranges())
+            .map(|(raw, range)| {
+                let matches: Vec<&String> = versions
+                    .iter()
+                    .filter(|(v, s)| range.matches(v, *s))
+                    .map(|(v, _)| v)
+                    .collect();
+                ConstraintResult {
+                    constraint: raw.to_string(),
+                    parsed: range.clone(),
+                    matching_versions: matches.len(),
+                    newest_match: matches.first().map(|v| v.to_string()),
+                }
+            })
+            .collect();
+
+        let conflict = if solution.conflict.is_empty() {
+            None
         } else {
-            0.0
+            let names: Vec<String> = solution
+                .conflict
+                .iter()
+                .map(|&i| results[i].constraint.clone())
+                .collect();
+            let explanation = match names.as_slice() {
+                [single] => format!("'{}' matches no published version", single),
+                [a, b] => format!("'{}' and '{}' have no published version in common", a, b),
+                _ => format!(
+                    "No published version satisfies {} together, although any smaller subset can be satisfied",
+                    names
+                        .iter()
+                        .map(|n| format!("'{}'", n))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            Some(ConstraintConflict {
+                constraints: names,
+                explanation,
+            })
         };
 
-        let health_grade = HealthGrade::from_score(health_score);
+        let response = ConstraintSolveResponse {
+            dependency: coordinate.to_ga(),
+            constraints: results,
+            satisfiable: !solution.satisfying.is_empty(),
+            newest_version: solution.satisfying.first().cloned(),
+            satisfying_versions: solution.satisfying,
+            conflict,
+        };
 
-        // Generate recommendations
-        let mut recommendations = Vec::new();
-        if summary.outdated > 0 {
-            recommendations.push(format!(
-                "{} dependencies are a major version behind. Prioritize these upgrades.",
-                summary.outdated
-            ));
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Analyze the dependencies and plugins declared in a pom.xml
+    #[tool(
+        name = "analyze_pom",
+        description = "Analyze a pom.xml (path or inline content): extracts dependencies, dependencyManagement, build plugins and pluginManagement, resolves ${property} versions through the POM and its parents, and runs update and health checks with the XML line of each declaration"
+    )]
+    #[instrument(skip(self, params))]
+    pub(crate) async fn analyze_pom(
+        &self,
+        params: Parameters<AnalyzePomParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let path = self.input_path(params.path.as_deref()).await?;
+        let (content, source) = require_input(path.as_deref(), params.content).await?;
+        info!("analyze_pom: {}", source);
+
+        let pom =
+            Pom::parse(&content).map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let effective =
+            EffectivePom::resolve(pom, path.as_deref(), self.file_root(), &self.client).await;
+        let declarations = effective.declarations();
+
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let (updates, health) =
+            check_declarations(&self.client, &source, &declarations, options).await;
+
+        let parents = effective.parents.iter().filter_map(Pom::to_gav).collect();
+
+        let response = PomAnalysisResponse {
+            pom: source,
+            project: effective.pom.to_gav(),
+            parents,
+            unresolved_count: declarations
+                .iter()
+                .filter(|d| d.unresolved_reason.is_some())
+                .count(),
+            declarations,
+            updates,
+            health,
+            warnings: effective.warnings,
+        };
+
+        render_report(
+            &self.client,
+            &response,
+            &format!("Dependency health: {}", response.pom),
+            &response.health,
+            params.output_format,
+        )
+        .await
+    }
+
+    /// Analyze the dependencies declared in a Gradle build script
+    #[tool(
+        name = "analyze_gradle_build",
+        description = "Statically extract dependencies from build.gradle or build.gradle.kts (string and map notation, platform()/enforcedPlatform(), versioned plugins { id(...) } requests, ext/val version variables) and run update and health checks with the line of each declaration"
+    )]
+    #[instrument(skip(self, params))]
+    async fn analyze_gradle_build(
+        &self,
+        params: Parameters<AnalyzeGradleBuildParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let path = self.input_path(params.path.as_deref()).await?;
+        let (content, source) = require_input(path.as_deref(), params.content).await?;
+        let properties = read_gradle_properties(path.as_deref(), self.file_root()).await;
+        let build = GradleBuild::parse(&content, &properties);
+        info!("analyze_gradle_build: {}", source);
+
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let (updates, health) =
+            check_declarations(&self.client, &source, &build.dependencies, options).await;
+
+        let response = GradleAnalysisResponse {
+            build_file: source,
+            unresolved_count: build
+                .dependencies
+                .iter()
+                .filter(|d| d.unresolved_reason.is_some())
+                .count(),
+            declarations: build.dependencies,
+            updates,
+            health,
+        };
+
+        render_report(
+            &self.client,
+            &response,
+            &format!("Dependency health: {}", response.build_file),
+            &response.health,
+            params.output_format,
+        )
+        .await
+    }
+
+    /// Analyze a Gradle version catalog
+    #[tool(
+        name = "analyze_version_catalog",
+        description = "Analyze a Gradle version catalog (libs.versions.toml): checks every library on Maven Central and every plugin on the Gradle Plugin Portal, grouping updates by version.ref so one bump covers all libraries sharing it"
+    )]
+    #[instrument(skip(self, params))]
+    async fn analyze_version_catalog(
+        &self,
+        params: Parameters<AnalyzeVersionCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let path = self.input_path(params.path.as_deref()).await?;
+        let (content, source) = require_input(path, params.content).await?;
+        let catalog = VersionCatalog::parse(&content)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        info!("analyze_version_catalog: {}", source);
+
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let declarations = catalog.declarations();
+        let (updates, health) =
+            check_declarations(&self.client, &source, &declarations, options).await;
+
+        // Check result of a declaration, looked up by coordinate and version
+        let latest_for = |d: &DeclaredDependency| {
+            updates
+                .dependencies
+                .iter()
+                .find(|r| r.dependency == d.to_ga() && r.current_version == d.resolved_version)
+        };
+        let is_outdated = |d: &DeclaredDependency| latest_for(d).is_some_and(|r| r.is_outdated);
+
+        let mut version_refs = Vec::new();
+        for (name, members) in catalog.version_refs() {
+            let entries: Vec<&DeclaredDependency> = catalog
+                .libraries
+                .iter()
+                .filter(|e| members.libraries.contains(&e.alias))
+                .chain(
+                    catalog
+                        .plugins
+                        .iter()
+                        .filter(|e| members.plugins.contains(&e.alias)),
+                )
+                .map(|e| &e.declaration)
+                .filter(|d| d.unresolved_reason.is_none())
+                .collect();
+            let version = catalog.version(&name);
+            let current = version.and_then(|v| v.version.clone());
+
+            let candidates: Vec<String> = entries
+                .iter()
+                .filter_map(|d| latest_for(d))
+                .filter(|r| r.is_outdated)
+                .filter_map(|r| r.latest_version.clone())
+                .collect();
+            let target = if candidates.is_empty() {
+                None
+            } else {
+                common_target(&self.client, &entries, candidates).await
+            };
+            let update_type = match (&current, &target) {
+                (Some(current), Some(target)) => {
+                    let group_id = entries.first().map_or("", |d| d.group_id.as_str());
+                    Some(self.client.update_type(group_id, current, target))
+                }
+                _ => None,
+            };
+
+            version_refs.push(VersionRefUpdate {
+                version_ref: name,
+                current_version: current,
+                line: version.map_or(0, |v| v.line),
+                libraries: members.libraries,
+                plugins: members.plugins,
+                is_outdated: target.is_some(),
+                target_version: target,
+                update_type,
+            });
         }
-        if summary.stale > 0 {
-            recommendations.push(format!(
-                "{} dependencies are significantly outdated. Plan upgrades soon.",
-                summary.stale
-            ));
+
+        let bundles = catalog
+            .bundles
+            .iter()
+            .map(|bundle| BundleStatus {
+                bundle: bundle.name.clone(),
+                outdated_libraries: catalog
+                    .libraries
+                    .iter()
+                    .filter(|e| bundle.libraries.contains(&e.alias))
+                    .filter(|e| is_outdated(&e.declaration))
+                    .map(|e| e.alias.clone())
+                    .collect(),
+                libraries: bundle.libraries.clone(),
+            })
+            .collect();
+
+        let response = VersionCatalogResponse {
+            catalog: source,
+            version_refs,
+            bundles,
+            unresolved_count: declarations
+                .iter()
+                .filter(|d| d.unresolved_reason.is_some())
+                .count(),
+            declarations,
+            updates,
+            health,
+            warnings: catalog.warnings,
+        };
+
+        render_report(
+            &self.client,
+            &response,
+            &format!("Dependency health: {}", response.catalog),
+            &response.health,
+            params.output_format,
+        )
+        .await
+    }
+
+    /// Analyze Maven build plugins and extensions
+    #[tool(
+        name = "analyze_maven_plugins",
+        description = "Check Maven build plugins, pluginManagement, <build><extensions> and .mvn/extensions.xml for updates, reported separately from library dependencies. Plugins without a groupId default to org.apache.maven.plugins, and goal prefixes like 'surefire:test' are resolved through group-level maven-metadata.xml"
+    )]
+    #[instrument(skip(self, params))]
+    async fn analyze_maven_plugins(
+        &self,
+        params: Parameters<AnalyzeMavenPluginsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let path = match self.input_path(params.path.as_deref()).await? {
+            Some(path) => {
+                self.input_path(Some(file_in_dir(&path, "pom.xml").await))
+                    .await?
+            }
+            None => None,
+        };
+        let (content, source) = require_input(path.as_deref(), params.content).await?;
+        info!("analyze_maven_plugins: {}", source);
+
+        let pom =
+            Pom::parse(&content).map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let effective =
+            EffectivePom::resolve(pom, path.as_deref(), self.file_root(), &self.client).await;
+        let mut warnings = effective.warnings.clone();
+
+        let (mut plugins, pom_extensions): (Vec<_>, Vec<_>) = effective
+            .declarations()
+            .into_iter()
+            .filter(|d| {
+                matches!(
+                    d.section.as_str(),
+                    "plugins" | "pluginManagement" | "extensions"
+                )
+            })
+            .partition(|d| d.section != "extensions");
+
+        let extensions_file = match path.as_deref().and_then(|p| p.parent()) {
+            Some(dir) => find_extensions_file(dir, self.file_root()).await,
+            None => None,
+        };
+        let mut core_extensions = Vec::new();
+        if let Some(file) = &extensions_file {
+            match tokio::fs::read_to_string(file)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|c| Pom::parse_extensions(&c).map_err(|e| e.to_string()))
+            {
+                Ok(entries) => core_extensions = extension_declarations(&entries),
+                Err(e) => warnings.push(format!("Skipped {}: {}", file.display(), e)),
+            }
+        }
+
+        let mut goals = Vec::new();
+        let mut goal_plugins = Vec::new();
+        for goal in &params.goals {
+            let mut resolved = ResolvedGoal {
+                goal: goal.clone(),
+                prefix: None,
+                plugin: None,
+                version: None,
+                resolved_from: None,
+                error: None,
+            };
+            match GoalReference::parse(goal) {
+                None => {
+                    resolved.error = Some("Lifecycle phase, not a plugin goal".to_string());
+                }
+                Some(GoalReference::Prefix { prefix, .. }) => {
+                    let declared: Vec<&DeclaredDependency> = plugins.iter().collect();
+                    match resolve_plugin_prefix(&self.client, &prefix, &declared).await {
+                        Ok((plugin, from)) => {
+                            resolved.version = plugins
+                                .iter()
+                                .find(|d| d.to_ga() == plugin)
+                                .and_then(|d| d.resolved_version.clone());
+                            resolved.plugin = Some(plugin);
+                            resolved.resolved_from = Some(from);
+                        }
+                        Err(e) => resolved.error = Some(e),
+                    }
+                    resolved.prefix = Some(prefix);
+                }
+                Some(GoalReference::Plugin {
+                    group_id,
+                    artifact_id,
+                    version,
+                    ..
+                }) => {
+                    let plugin = format!("{}:{}", group_id, artifact_id);
+                    // Versions pinned on the command line are checked like declared plugins
+                    if let Some(version) = &version {
+                        goal_plugins.push(DeclaredDependency {
+                            group_id,
+                            artifact_id,
+                            section: "goals".to_string(),
+                            scope: None,
+                            declared_version: Some(version.clone()),
+                            version_property: None,
+                            resolved_version: Some(version.clone()),
+                            repository: None,
+                            line: 0,
+                            unresolved_reason: None,
+                        });
+                    }
+                    resolved.version = version.or_else(|| {
+                        plugins
+                            .iter()
+                            .find(|d| d.to_ga() == plugin)
+                            .and_then(|d| d.resolved_version.clone())
+                    });
+                    resolved.plugin = Some(plugin);
+                }
+            }
+            goals.push(resolved);
         }
-        if summary.aging > 0 {
-            recommendations.push(format!(
-                "{} dependencies have updates available. Consider upgrading.",
-                summary.aging
+        plugins.extend(goal_plugins);
+
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let (plugin_updates, plugin_health) =
+            check_declarations(&self.client, &source, &plugins, options).await;
+        let extensions_source = extensions_file
+            .as_ref()
+            .map(|f| f.display().to_string())
+            .unwrap_or_default();
+        let (extension_updates, extension_health) = merge_checks(
+            vec![
+                check_declarations(&self.client, &source, &pom_extensions, options).await,
+                check_declarations(&self.client, &extensions_source, &core_extensions, options)
+                    .await,
+            ],
+            options.update_policy,
+        );
+
+        let response = PluginAnalysisResponse {
+            pom: source,
+            project: effective.pom.to_gav(),
+            plugins,
+            extensions: pom_extensions.into_iter().chain(core_extensions).collect(),
+            extensions_file: extensions_file.map(|f| f.display().to_string()),
+            goals,
+            plugin_updates,
+            plugin_health,
+            extension_updates,
+            extension_health,
+            warnings,
+        };
+
+        // Reports cover plugins and extensions together
+        let health = project_health_response(
+            response
+                .plugin_health
+                .dependencies
+                .iter()
+                .chain(&response.extension_health.dependencies)
+                .cloned()
+                .collect(),
+        );
+        render_report(
+            &self.client,
+            &response,
+            &format!("Build plugin health: {}", response.pom),
+            &health,
+            params.output_format,
+        )
+        .await
+    }
+
+    /// Check Maven Wrapper and Gradle Wrapper versions
+    #[tool(
+        name = "check_wrapper_versions",
+        description = "Read .mvn/wrapper/maven-wrapper.properties and gradle/wrapper/gradle-wrapper.properties, extract the Maven distribution, Maven Wrapper and Gradle distribution versions, and compare them with the latest releases like compare_versions. Gradle versions come from a configurable distributions endpoint"
+    )]
+    #[instrument(skip(self))]
+    async fn check_wrapper_versions(
+        &self,
+        params: Parameters<CheckWrapperVersionsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        info!("check_wrapper_versions: {}", params.path);
+
+        let root = self
+            .input_path(Some(&params.path))
+            .await?
+            .unwrap_or_default();
+        let mut files = Vec::new();
+        match tokio::fs::metadata(&root).await {
+            Ok(metadata) if metadata.is_dir() => {
+                for kind in [WrapperKind::Maven, WrapperKind::Gradle] {
+                    let file = root.join(kind.default_location());
+                    if tokio::fs::metadata(&file).await.is_ok_and(|m| m.is_file())
+                        && is_within(&file, self.file_root()).await
+                    {
+                        files.push(file);
+                    }
+                }
+            }
+            _ => files.push(root),
+        }
+        if files.is_empty() {
+            return Err(McpError::invalid_params(
+                format!(
+                    "No {} or {} found in {}",
+                    MAVEN_WRAPPER_PROPERTIES, GRADLE_WRAPPER_PROPERTIES, params.path
+                ),
+                None,
             ));
         }
-        if summary.current + summary.fresh == params.0.dependencies.len() - summary.errors {
-            recommendations.push("All dependencies are up to date!".to_string());
+
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let endpoint = params
+            .gradle_versions_url
+            .as_deref()
+            .unwrap_or(GRADLE_VERSIONS_ENDPOINT);
+
+        let mut wrappers = Vec::new();
+        let mut warnings = Vec::new();
+        for file in files {
+            let kind = WrapperKind::detect(&file).ok_or_else(|| {
+                McpError::invalid_params(
+                    format!(
+                        "Expected {} or {}, got {}",
+                        MAVEN_WRAPPER_PROPERTIES,
+                        GRADLE_WRAPPER_PROPERTIES,
+                        file.display()
+                    ),
+                    None,
+                )
+            })?;
+            let (content, name) = require_input(Some(&file), None).await?;
+            let wrapper = WrapperProperties::parse(kind, &content);
+
+            let mut checks = Vec::new();
+            for component in &wrapper.components {
+                let Some(coordinate) = component.checkable_coordinate() else {
+                    if let Some(reason) = &component.unresolved_reason {
+                        warnings.push(format!("{}:{}: {}", name, component.line, reason));
+                    }
+                    continue;
+                };
+                let mut check = match wrapper.kind {
+                    WrapperKind::Maven => {
+                        check_single_dependency(
+                            &self.client,
+                            MAVEN_CENTRAL_BASE,
+                            &coordinate,
+                            options,
+                        )
+                        .await
+                    }
+                    WrapperKind::Gradle => {
+                        let coordinate = MavenCoordinate::parse(&coordinate)
+                            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+                        match self.client.get_gradle_versions(endpoint).await {
+                            Ok(metadata) => check_against_metadata(
+                                &self.client,
+                                &coordinate,
+                                &metadata,
+                                options,
+                            ),
+                            Err(e) => DependencyCheckResult {
+                                dependency: coordinate.to_ga(),
+                                current_version: coordinate.version.clone(),
+                                latest_version: None,
+                                is_outdated: false,
+                                update_type: None,
+                                targets: None,
+                                stream: None,
+                                alternatives: Vec::new(),
+                                error: Some(e.to_string()),
+                                location: None,
+                                introduced_by: None,
+                            },
+                        }
+                    }
+                };
+                check.location = Some(SourceLocation {
+                    file: name.clone(),
+                    line: component.line,
+                });
+                checks.push(check);
+            }
+
+            wrappers.push(WrapperReport {
+                file: name,
+                kind: wrapper.kind,
+                distribution_url: wrapper.distribution_url,
+                checks,
+            });
         }
 
-        let response = ProjectHealthResponse {
-            total_dependencies: params.0.dependencies.len(),
-            health_score,
-            health_grade,
-            summary,
-            dependencies: results,
-            recommendations,
+        let response = WrapperCheckResponse {
+            outdated_count: wrappers
+                .iter()
+                .flat_map(|w| &w.checks)
+                .filter(|c| c.is_outdated)
+                .count(),
+            wrappers,
+            warnings,
         };
 
         Ok(CallToolResult::success(vec![Content::text(
@@ -581,183 +1966,534 @@ impl MavenToolsService {
                 .map_err(|e| McpError::internal_error(e.to_string(), None))?,
         )]))
     }
-}
 
-/// Helper function to check a single dependency
-async fn check_single_dependency(
-    client: &MavenClient,
-    dependency: &str,
-    stable_only: bool,
-) -> DependencyCheckResult {
-    let coordinate = match MavenCoordinate::parse(dependency) {
-        Ok(c) => c,
-        Err(e) => {
-            return DependencyCheckResult {
-                dependency: dependency.to_string(),
-                current_version: None,
-                latest_version: None,
-                is_outdated: false,
-                update_type: None,
-                error: Some(e.to_string()),
+    /// Analyze the artifacts pinned in a Bazel maven_install.json
+    #[tool(
+        name = "analyze_bazel_pins",
+        description = "Analyze a Bazel rules_jvm_external maven_install.json: runs update and health checks for every pinned artifact against the repository it was resolved from, and reports which pinned artifacts pull in each outdated one"
+    )]
+    #[instrument(skip(self, params))]
+    async fn analyze_bazel_pins(
+        &self,
+        params: Parameters<AnalyzeBazelPinsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let path = match self.input_path(params.path.as_deref()).await? {
+            Some(path) => {
+                self.input_path(Some(file_in_dir(&path, MAVEN_INSTALL_JSON).await))
+                    .await?
             }
-        }
-    };
+            None => None,
+        };
+        let (content, source) = require_input(path.as_deref(), params.content).await?;
+        let pins = MavenInstall::parse(&content)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        info!("analyze_bazel_pins: {}", source);
 
-    let current_version = coordinate.version.clone();
-
-    let metadata = match client.get_metadata(&coordinate).await {
-        Ok(m) => m,
-        Err(e) => {
-            error!("Failed to fetch metadata for {}: {}", dependency, e);
-            return DependencyCheckResult {
-                dependency: coordinate.to_ga(),
-                current_version,
-                latest_version: None,
-                is_outdated: false,
-                update_type: None,
-                error: Some(e.to_string()),
-            };
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let declarations = pins.declarations();
+        let (updates, health) =
+            check_declarations(&self.client, &source, &declarations, options).await;
+
+        let mut required_by = BTreeMap::new();
+        for artifact in &pins.artifacts {
+            let outdated = updates.dependencies.iter().any(|r| {
+                r.is_outdated
+                    && r.dependency == artifact.declaration.to_ga()
+                    && r.current_version == artifact.declaration.resolved_version
+            });
+            let dependents = pins.required_by(&artifact.key);
+            if outdated && !dependents.is_empty() {
+                required_by.insert(
+                    artifact.key.clone(),
+                    dependents.into_iter().map(String::from).collect(),
+                );
+            }
         }
-    };
 
-    let latest_version = if stable_only {
-        metadata.latest_stable.clone()
-    } else {
-        metadata.latest_any.clone()
-    };
+        let response = BazelPinsResponse {
+            pin_file: source,
+            lock_version: pins.version.clone(),
+            repositories: pins.repositories.clone(),
+            unresolved_count: declarations
+                .iter()
+                .filter(|d| d.unresolved_reason.is_some())
+                .count(),
+            top_level: pins.top_level().iter().map(|a| a.key.clone()).collect(),
+            required_by,
+            declarations,
+            updates,
+            health,
+        };
 
-    match (&current_version, &latest_version) {
-        (Some(current), Some(latest)) => {
-            let current_parsed = MavenVersion::parse(current);
-            let latest_parsed = MavenVersion::parse(latest);
-            let is_outdated = current_parsed < latest_parsed;
-            let update_type = if is_outdated {
-                Some(UpdateType::between(current, latest))
-            } else {
-                None
-            };
+        render_report(
+            &self.client,
+            &response,
+            &format!("Bazel pin health: {}", response.pin_file),
+            &response.health,
+            params.output_format,
+        )
+        .await
+    }
 
-            DependencyCheckResult {
-                dependency: coordinate.to_ga(),
-                current_version: Some(current.clone()),
-                latest_version: Some(latest.clone()),
-                is_outdated,
-                update_type,
-                error: None,
-            }
+    /// Generate a CycloneDX or SPDX SBOM for a set of coordinates
+    #[tool(
+        name = "generate_sbom",
+        description = "Generate a CycloneDX 1.5 or SPDX 2.3 SBOM for Maven coordinates, optionally with their transitive runtime dependencies. Components carry licenses and supplier from their POMs, checksums published on Maven Central, and latest version and age classification as properties"
+    )]
+    #[instrument(skip(self, params))]
+    async fn generate_sbom(
+        &self,
+        params: Parameters<GenerateSbomParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if params.dependencies.is_empty() {
+            return Err(McpError::invalid_params(
+                "At least one dependency is required",
+                None,
+            ));
         }
-        (None, Some(latest)) => {
-            // No current version specified, just return latest
-            DependencyCheckResult {
-                dependency: coordinate.to_ga(),
-                current_version: None,
-                latest_version: Some(latest.clone()),
-                is_outdated: false,
-                update_type: None,
-                error: None,
+        let mut roots = Vec::new();
+        for dependency in &params.dependencies {
+            let coordinate = MavenCoordinate::parse(dependency)
+                .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+            if coordinate.version.is_none() || coordinate.has_dynamic_version() {
+                return Err(McpError::invalid_params(
+                    format!("{} needs a concrete version", dependency),
+                    None,
+                ));
             }
+            roots.push(coordinate);
         }
-        _ => DependencyCheckResult {
-            dependency: coordinate.to_ga(),
-            current_version,
-            latest_version: None,
-            is_outdated: false,
-            update_type: None,
-            error: Some("No versions found".to_string()),
-        },
-    }
-}
-
-/// Helper function to analyze health of a single dependency
-async fn analyze_single_health(client: &MavenClient, dependency: &str) -> DependencyHealthResult {
-    let coordinate = match MavenCoordinate::parse(dependency) {
-        Ok(c) => c,
-        Err(e) => {
-            return DependencyHealthResult {
-                dependency: dependency.to_string(),
-                current_version: None,
-                latest_version: None,
-                age_classification: None,
-                health_score: 0.0,
-                update_type: None,
-                error: Some(e.to_string()),
+        info!(
+            "generate_sbom: {} dependencies, transitive: {}",
+            roots.len(),
+            params.include_transitive
+        );
+
+        let mut warnings = Vec::new();
+        let artifacts = resolve_artifacts(
+            &self.client,
+            roots,
+            params.include_transitive,
+            &mut warnings,
+        )
+        .await;
+
+        let details = join_all(artifacts.iter().map(|artifact| {
+            let client = self.client.clone();
+            let packaging = artifact
+                .pom
+                .as_ref()
+                .and_then(|p| p.pom.packaging.clone())
+                .unwrap_or_else(|| "jar".to_string());
+            async move {
+                let coordinate = &artifact.coordinate;
+                let extension = artifact_extension(&packaging);
+                let mut hashes = Vec::new();
+                for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Sha1] {
+                    if let Ok(value) = client
+                        .get_checksum(
+                            MAVEN_CENTRAL_BASE,
+                            coordinate,
+                            extension,
+                            algorithm.extension(),
+                        )
+                        .await
+                    {
+                        hashes.push(ComponentHash { algorithm, value });
+                    }
+                }
+                let gav = coordinate.to_gav().unwrap_or_default();
+                let health = analyze_single_health(&client, MAVEN_CENTRAL_BASE, &gav).await;
+                (packaging, hashes, health)
+            }
+        }))
+        .await;
+
+        let mut components = Vec::new();
+        for (artifact, (packaging, hashes, health)) in artifacts.into_iter().zip(details) {
+            let gav = artifact.coordinate.to_gav().unwrap_or_default();
+            if hashes.is_empty() {
+                warnings.push(format!("No checksums found for {}", gav));
             }
+            if let Some(error) = &health.error {
+                warnings.push(format!("Health check failed for {}: {}", gav, error));
+            }
+
+            let mut properties = Vec::new();
+            if let Some(latest) = health.latest_version {
+                properties.push(("latest_version".to_string(), latest));
+            }
+            if let Some(age) = health.age_classification {
+                properties.push(("age_classification".to_string(), age.to_string()));
+                properties.push(("health_score".to_string(), health.health_score.to_string()));
+            }
+            if let Some(update_type) = health.update_type {
+                properties.push(("update_type".to_string(), update_type.to_string()));
+            }
+
+            let pom = artifact.pom.as_ref();
+            components.push(SbomComponent {
+                group_id: artifact.coordinate.group_id,
+                artifact_id: artifact.coordinate.artifact_id,
+                version: artifact.coordinate.version.unwrap_or_default(),
+                packaging,
+                licenses: pom.map(|p| p.licenses().to_vec()).unwrap_or_default(),
+                supplier: pom.and_then(|p| p.organization().cloned()),
+                hashes,
+                dependencies: artifact.dependencies,
+                direct: artifact.direct,
+                properties,
+            });
         }
-    };
 
-    let current_version = match &coordinate.version {
-        Some(v) => v.clone(),
-        None => {
-            return DependencyHealthResult {
-                dependency: coordinate.to_ga(),
-                current_version: None,
-                latest_version: None,
-                age_classification: None,
-                health_score: 0.0,
-                update_type: None,
-                error: Some("Version is required for health analysis".to_string()),
+        let name = params.name.as_deref().unwrap_or("application");
+        let sbom = match params.format {
+            SbomFormat::Cyclonedx => cyclonedx(name, &components, chrono::Utc::now()),
+            SbomFormat::Spdx => spdx(name, &components, chrono::Utc::now()),
+        };
+
+        if params.output_path.is_some() {
+            self.check_writable("output_path")?;
+        }
+
+        if let Some(path) = &params.output_path {
+            let document = serde_json::to_string_pretty(&sbom)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            tokio::fs::write(path, document).await.map_err(|e| {
+                McpError::internal_error(format!("Failed to write {}: {}", path, e), None)
+            })?;
+        }
+
+        let response = SbomResponse {
+            format: params.format,
+            component_count: components.len(),
+            output_path: params.output_path,
+            warnings,
+            sbom,
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Produce a unified diff that applies an update policy to a build file
+    #[tool(
+        name = "generate_update_diff",
+        description = "Generate a unified diff of minimal, format-preserving version edits for a pom.xml, build.gradle(.kts) or libs.versions.toml under an update policy. Shared <properties> and version.ref entries are updated instead of inlining versions. Files are only modified when write is true"
+    )]
+    #[instrument(skip(self, params))]
+    async fn generate_update_diff(
+        &self,
+        params: Parameters<GenerateUpdateDiffParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if params.write {
+            self.check_writable("write")?;
+            if params.path.is_none() {
+                return Err(McpError::invalid_params(
+                    "write requires a path; inline content cannot be written",
+                    None,
+                ));
             }
         }
-    };
+        let path = self.input_path(params.path.as_deref()).await?;
+        let (content, source) = require_input(path.as_deref(), params.content).await?;
+        let kind = params
+            .kind
+            .unwrap_or_else(|| BuildFileKind::detect(params.path.as_deref(), &content));
+        info!("generate_update_diff: {} ({:?})", source, kind);
+
+        let mut warnings = Vec::new();
+        let mut properties: HashMap<String, (usize, String)> = HashMap::new();
+        let declarations = match kind {
+            BuildFileKind::Pom => {
+                let pom = Pom::parse(&content)
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                let effective =
+                    EffectivePom::resolve(pom, path.as_deref(), self.file_root(), &self.client)
+                        .await;
+                for property in &effective.pom.properties {
+                    properties.insert(
+                        property.name.clone(),
+                        (property.line, property.value.clone()),
+                    );
+                }
+                warnings = effective.warnings.clone();
+                effective.declarations()
+            }
+            BuildFileKind::Gradle => {
+                let gradle_properties =
+                    read_gradle_properties(path.as_deref(), self.file_root()).await;
+                let build = GradleBuild::parse(&content, &gradle_properties);
+                // Later definitions win; gradle.properties values live in another file
+                for variable in build.variables.iter().filter(|v| v.line > 0) {
+                    properties.insert(
+                        variable.name.clone(),
+                        (variable.line, variable.value.clone()),
+                    );
+                }
+                build.dependencies
+            }
+            BuildFileKind::VersionCatalog => {
+                let catalog = VersionCatalog::parse(&content)
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                for version in &catalog.versions {
+                    if let Some(value) = &version.version {
+                        properties.insert(version.name.clone(), (version.line, value.clone()));
+                    }
+                }
+                warnings = catalog.warnings.clone();
+                catalog.declarations()
+            }
+        };
 
-    let metadata = match client.get_metadata(&coordinate).await {
-        Ok(m) => m,
-        Err(e) => {
-            error!("Failed to fetch metadata for {}: {}", dependency, e);
-            return DependencyHealthResult {
-                dependency: coordinate.to_ga(),
-                current_version: Some(current_version),
-                latest_version: None,
-                age_classification: None,
-                health_score: 0.0,
-                update_type: None,
-                error: Some(e.to_string()),
-            };
+        let options = CheckOptions {
+            stable_only: params.stable_only,
+            same_stream: true,
+            update_policy: self.update_policy(params.update_policy),
+        };
+        let (updates, _) = check_declarations(&self.client, &source, &declarations, options).await;
+        let (edits, skipped) =
+            plan_version_edits(&self.client, &content, &declarations, &updates, |name| {
+                properties.get(name).map(|(line, value)| VersionDefinition {
+                    line: *line,
+                    value: value.clone(),
+                })
+            })
+            .await;
+
+        let edited = apply_edits(&content, &edits);
+        let base_dir = self.input_path(params.base_dir.as_deref()).await?;
+        let diff_path = kind
+            .diff_path(path.as_deref(), base_dir.as_deref(), self.file_root())
+            .await;
+        let diff = unified_diff(&diff_path, &content, &edited);
+        let written = match &params.path {
+            Some(path) if params.write && !edits.is_empty() => {
+                tokio::fs::write(path, &edited).await.map_err(|e| {
+                    McpError::internal_error(format!("Failed to write {}: {}", path, e), None)
+                })?;
+                true
+            }
+            _ => false,
+        };
+
+        let response = UpdateDiffResponse {
+            file: source,
+            kind,
+            update_policy: options.update_policy,
+            edits,
+            skipped,
+            diff,
+            written,
+            warnings,
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Analyze every module of a multi-module Maven build
+    #[tool(
+        name = "analyze_maven_reactor",
+        description = "Walk a root pom.xml's <modules> recursively, resolve inter-module parents, and report per-module and aggregate dependency health plus external dependencies declared at different versions in different modules"
+    )]
+    #[instrument(skip(self))]
+    async fn analyze_maven_reactor(
+        &self,
+        params: Parameters<AnalyzeMavenReactorParams>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("analyze_maven_reactor: {}", params.0.path);
+
+        // Scanning reads every module's pom.xml from disk
+        let root = self
+            .input_path(Some(&params.0.path))
+            .await?
+            .unwrap_or_default();
+        let within = self.file_root().map(Path::to_path_buf);
+        let reactor = tokio::task::spawn_blocking(move || Reactor::scan(root, within.as_deref()))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let internal = reactor.module_coordinates();
+
+        let mut warnings = reactor.warnings.clone();
+        let mut modules = Vec::new();
+        for module in &reactor.modules {
+            let effective = EffectivePom::resolve_in_reactor(
+                module.pom.clone(),
+                &module.path,
+                self.file_root(),
+                &reactor.modules,
+                &self.client,
+            )
+            .await;
+            let name = reactor.relative_path(&module.path);
+            warnings.extend(
+                effective
+                    .warnings
+                    .iter()
+                    .map(|w| format!("{}: {}", name, w)),
+            );
+            modules.push((name, effective.declarations()));
         }
-    };
 
-    let current_parsed = MavenVersion::parse(&current_version);
-
-    // Count stable versions newer than current
-    let stable_versions_since = metadata
-        .stable_versions
-        .iter()
-        .filter(|v| MavenVersion::parse(v) > current_parsed)
-        .count();
-
-    // Calculate health score and age classification
-    let (age_classification, health_score) = if stable_versions_since == 0 {
-        (AgeClassification::Current, 100.0)
-    } else if stable_versions_since <= 2 {
-        (AgeClassification::Fresh, 90.0)
-    } else if stable_versions_since <= 5 {
-        (AgeClassification::Aging, 70.0)
-    } else {
-        // Check for major version difference
-        if let Some(latest) = &metadata.latest_stable {
-            let update_type = UpdateType::between(&current_version, latest);
-            if update_type == UpdateType::Major {
-                (AgeClassification::Outdated, 40.0)
-            } else {
-                (AgeClassification::Stale, 50.0)
+        // Check every distinct external coordinate once, keeping its first location
+        let mut locations: HashMap<String, SourceLocation> = HashMap::new();
+        let mut coordinates = Vec::new();
+        for (name, declarations) in &modules {
+            for declaration in declarations {
+                if internal.contains(&declaration.to_ga()) {
+                    continue;
+                }
+                if let Some(coordinate) = declaration.checkable_coordinate() {
+                    if !locations.contains_key(&coordinate) {
+                        locations.insert(
+                            coordinate.clone(),
+                            SourceLocation {
+                                file: name.clone(),
+                                line: declaration.line,
+                            },
+                        );
+                        coordinates.push(coordinate);
+                    }
+                }
             }
-        } else {
-            (AgeClassification::Stale, 50.0)
         }
-    };
+        let health = health_by_coordinate(&self.client, &coordinates).await;
+
+        let module_health = reactor
+            .modules
+            .iter()
+            .zip(&modules)
+            .map(|(module, (name, declarations))| {
+                let mut seen = HashSet::new();
+                let results: Vec<DependencyHealthResult> = declarations
+                    .iter()
+                    .filter(|d| !internal.contains(&d.to_ga()))
+                    .filter_map(|d| {
+                        let coordinate = d.checkable_coordinate()?;
+                        let mut result = health.get(&coordinate)?.clone();
+                        result.location = Some(SourceLocation {
+                            file: name.clone(),
+                            line: d.line,
+                        });
+                        seen.insert(coordinate).then_some(result)
+                    })
+                    .collect();
+                let report = project_health_response(results);
+                ModuleHealth {
+                    module: name.clone(),
+                    project: module.pom.to_gav(),
+                    total_dependencies: report.total_dependencies,
+                    unresolved_count: declarations
+                        .iter()
+                        .filter(|d| d.unresolved_reason.is_some())
+                        .count(),
+                    health_score: report.health_score,
+                    health_grade: report.health_grade,
+                    summary: report.summary,
+                }
+            })
+            .collect();
+
+        let aggregate = coordinates
+            .iter()
+            .filter_map(|coordinate| {
+                let mut result = health.get(coordinate)?.clone();
+                result.location = locations.remove(coordinate);
+                Some(result)
+            })
+            .collect();
+
+        let response = ReactorAnalysisResponse {
+            root: reactor.root.display().to_string(),
+            total_modules: reactor.modules.len(),
+            modules: module_health,
+            health: project_health_response(aggregate),
+            version_conflicts: find_version_conflicts(&modules, &internal),
+            warnings,
+        };
+
+        render_report(
+            &self.client,
+            &response,
+            &format!("Reactor health: {}", response.root),
+            &response.health,
+            params.0.output_format,
+        )
+        .await
+    }
+
+    /// Show the effective server config
+    #[tool(
+        name = "get_server_config",
+        description = "Show the effective server configuration (repositories, cache, HTTP, stability rules, scoring and default update policy) with passwords and tokens redacted"
+    )]
+    #[instrument(skip(self))]
+    async fn get_server_config(&self) -> Result<CallToolResult, McpError> {
+        info!("get_server_config");
 
-    let update_type = metadata
-        .latest_stable
-        .as_ref()
-        .map(|latest| UpdateType::between(&current_version, latest));
-
-    DependencyHealthResult {
-        dependency: coordinate.to_ga(),
-        current_version: Some(current_version),
-        latest_version: metadata.latest_stable.clone(),
-        age_classification: Some(age_classification),
-        health_score,
-        update_type,
-        error: None,
+        let response = ServerConfigResponse {
+            server_version: env!("CARGO_PKG_VERSION").to_string(),
+            config_file: self.config_path.as_ref().map(|p| p.display().to_string()),
+            config: self.client.config().redacted(),
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
+    }
+
+    /// Explain which rule classified a version's stability
+    #[tool(
+        name = "explain_version_stability",
+        description = "Explain how a version's stability (stable, RC, beta, alpha, milestone, snapshot) was determined, including which configured or built-in rule matched"
+    )]
+    #[instrument(skip(self))]
+    async fn explain_version_stability(
+        &self,
+        params: Parameters<ExplainVersionStabilityParams>,
+    ) -> Result<CallToolResult, McpError> {
+        info!("explain_version_stability: {}", params.0.dependency);
+
+        let coordinate = MavenCoordinate::parse(&params.0.dependency)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let version = coordinate
+            .version
+            .clone()
+            .ok_or_else(|| McpError::invalid_params("Version is required", None))?;
+
+        let classification = self
+            .client
+            .stability_rules()
+            .explain(&coordinate.group_id, &version);
+        let (builtin_stability, _) = VersionStability::classify_builtin(&version);
+
+        let response = StabilityExplanationResponse {
+            dependency: coordinate.to_ga(),
+            version,
+            stability: classification.stability,
+            rule: classification.source,
+            builtin_stability,
+            overridden: classification.stability != builtin_stability,
+        };
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&response)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+        )]))
     }
 }